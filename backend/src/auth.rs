@@ -0,0 +1,485 @@
+//! ユーザー認証。
+//! ユーザーは `<設定ファイル名>.users.json` に Argon2 のパスワードハッシュで保存し、
+//! `nekotimer-backend user add` などの管理コマンドで作成する（API からは作成できない）。
+//! ログインで発行したトークンは SHA-256 ハッシュだけを `<設定ファイル名>.sessions.json` に保存する。
+//! /api 以下（ログインを除く）は Authorization: Bearer（セッションか API トークン）かセッション Cookie が必要。
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, IsTerminal};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header;
+use actix_web::middleware::Next;
+use actix_web::{web, HttpMessage, HttpResponse};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use nekotimer_shared::{
+    validate_password, validate_username, ApiResponse, ErrorCode, UserInfo, ValidationError,
+    SESSION_COOKIE,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::persistence;
+use crate::runs::now_unix;
+use crate::tokens::{self, TokenError};
+use crate::AppState;
+
+/// セッションの有効期間（30日）
+pub const SESSION_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+/// 認証なしで呼べる API
+const PUBLIC_PATHS: [&str; 1] = ["/api/auth/login"];
+/// 認証なしで呼べる API（前方一致）。共有リンクの閲覧
+const PUBLIC_PREFIXES: [&str; 1] = ["/api/shared/"];
+/// この回数まではログインに続けて失敗しても待たせない
+const LOGIN_FREE_FAILURES: u32 = 5;
+/// それ以降は失敗するたびに待ち時間を1秒から倍にしていく。その上限（15分）
+const LOGIN_MAX_BACKOFF_SECS: u64 = 15 * 60;
+/// 最後の失敗からこの時間が経てば失敗回数を忘れる（1時間）
+const LOGIN_FAILURE_WINDOW_SECS: u64 = 60 * 60;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredUser {
+    pub id: String,
+    pub username: String,
+    pub password_hash: String,
+    #[serde(default)]
+    pub is_admin: bool,
+    pub created_at: u64,
+    /// これより前に発行したセッションは無効
+    #[serde(default)]
+    pub password_changed_at: u64,
+}
+
+impl StoredUser {
+    pub fn info(&self) -> UserInfo {
+        UserInfo {
+            id: self.id.clone(),
+            username: self.username.clone(),
+            is_admin: self.is_admin,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct UserFile {
+    pub users: Vec<StoredUser>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Session {
+    pub token_hash: String,
+    pub user_id: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SessionFile {
+    pub sessions: Vec<Session>,
+}
+
+/// ユーザーファイル。管理コマンドでの変更を再起動なしで反映するため、更新時刻が変わったら読み直す
+pub struct UserStore {
+    path: String,
+    cache: Mutex<(Option<SystemTime>, UserFile)>,
+}
+
+impl UserStore {
+    pub fn new(path: String) -> Self {
+        let store = UserStore {
+            path,
+            cache: Mutex::new((None, UserFile::default())),
+        };
+        store.refresh();
+        store
+    }
+
+    fn refresh(&self) {
+        let modified = std::fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        let mut cache = self.cache.lock().unwrap();
+        if cache.0 != modified || modified.is_none() {
+            cache.1 = persistence::load_json(&self.path).unwrap_or_default();
+            cache.0 = modified;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.refresh();
+        self.cache.lock().unwrap().1.users.is_empty()
+    }
+
+    pub fn find_by_name(&self, username: &str) -> Option<StoredUser> {
+        self.refresh();
+        let cache = self.cache.lock().unwrap();
+        cache.1.users.iter().find(|u| u.username == username).cloned()
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Option<StoredUser> {
+        self.refresh();
+        let cache = self.cache.lock().unwrap();
+        cache.1.users.iter().find(|u| u.id == id).cloned()
+    }
+}
+
+pub fn hash_password(password: &str) -> Result<String, String> {
+    let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes()).map_err(|e| e.to_string())?;
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|h| h.to_string())
+        .map_err(|e| e.to_string())
+}
+
+pub fn verify_password(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash)
+        .is_ok_and(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+}
+
+/// ユーザー名とパスワードを確かめる。ユーザーがいないときもダミーのハッシュで同じだけ計算し、
+/// 応答時間からユーザー名の有無がわからないようにする。Argon2 は重いので web::block から呼ぶ
+pub fn verify_login(users: &UserStore, username: &str, password: &str) -> Option<StoredUser> {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+    match users.find_by_name(username) {
+        Some(user) => verify_password(&user.password_hash, password).then_some(user),
+        None => {
+            let dummy = DUMMY_HASH.get_or_init(|| hash_password(&generate_token()).unwrap_or_default());
+            verify_password(dummy, password);
+            None
+        }
+    }
+}
+
+struct LoginFailures {
+    count: u32,
+    last_failed_at: u64,
+}
+
+impl LoginFailures {
+    /// 次のログインを受け付ける時刻
+    fn locked_until(&self) -> u64 {
+        match self.count.checked_sub(LOGIN_FREE_FAILURES) {
+            None | Some(0) => 0,
+            Some(over) => {
+                let backoff = 1u64.checked_shl(over - 1).unwrap_or(u64::MAX);
+                self.last_failed_at + backoff.min(LOGIN_MAX_BACKOFF_SECS)
+            }
+        }
+    }
+}
+
+/// ログインの失敗回数。ユーザー名ごと・接続元 IP ごとに数え、続けて失敗すると次の試行まで待たせる
+#[derive(Default)]
+pub struct LoginThrottle {
+    failures: Mutex<HashMap<String, LoginFailures>>,
+}
+
+impl LoginThrottle {
+    /// 数える単位（ユーザー名と接続元 IP）
+    pub fn keys(username: &str, ip: Option<&str>) -> Vec<String> {
+        let mut keys = vec![format!("user:{}", username)];
+        keys.extend(ip.map(|ip| format!("ip:{}", ip)));
+        keys
+    }
+
+    /// まだ受け付けないなら、受け付けるまでの秒数
+    pub fn retry_after(&self, keys: &[String]) -> Option<u64> {
+        let now = now_unix();
+        let failures = self.failures.lock().unwrap();
+        keys.iter()
+            .filter_map(|key| failures.get(key))
+            .map(|f| f.locked_until())
+            .max()
+            .filter(|&until| until > now)
+            .map(|until| until - now)
+    }
+
+    pub fn failed(&self, keys: &[String]) {
+        let now = now_unix();
+        let mut failures = self.failures.lock().unwrap();
+        failures.retain(|_, f| f.last_failed_at + LOGIN_FAILURE_WINDOW_SECS > now);
+        for key in keys {
+            let entry = failures.entry(key.clone()).or_insert(LoginFailures {
+                count: 0,
+                last_failed_at: now,
+            });
+            entry.count = entry.count.saturating_add(1);
+            entry.last_failed_at = now;
+        }
+    }
+
+    pub fn succeeded(&self, keys: &[String]) {
+        let mut failures = self.failures.lock().unwrap();
+        for key in keys {
+            failures.remove(key);
+        }
+    }
+}
+
+pub fn generate_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// 新しいセッションを登録して保存する
+pub fn create_session(data: &AppState, user: &StoredUser) -> (String, u64) {
+    let token = generate_token();
+    let now = now_unix();
+    let expires_at = now + SESSION_TTL_SECS;
+    let mut sessions = data.sessions.lock().unwrap();
+    sessions.sessions.retain(|s| s.expires_at > now);
+    sessions.sessions.push(Session {
+        token_hash: hash_token(&token),
+        user_id: user.id.clone(),
+        created_at: now,
+        expires_at,
+    });
+    if let Err(e) = persistence::save_json(&data.sessions_path, &*sessions) {
+        log::error!("セッションの保存に失敗しました: {}", e);
+    }
+    (token, expires_at)
+}
+
+pub fn remove_session(data: &AppState, token: &str) {
+    let token_hash = hash_token(token);
+    let mut sessions = data.sessions.lock().unwrap();
+    let before = sessions.sessions.len();
+    sessions.sessions.retain(|s| s.token_hash != token_hash);
+    if sessions.sessions.len() != before {
+        if let Err(e) = persistence::save_json(&data.sessions_path, &*sessions) {
+            log::error!("セッションの保存に失敗しました: {}", e);
+        }
+    }
+}
+
+/// リクエストのトークン（Authorization: Bearer を優先し、なければ Cookie）
+pub fn request_token(req: &actix_web::HttpRequest) -> Option<String> {
+    let bearer = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|t| t.trim().to_string());
+    bearer.or_else(|| req.cookie(SESSION_COOKIE).map(|c| c.value().to_string()))
+}
+
+/// トークンに対応するユーザー（期限切れ・削除済み・パスワード変更前のセッションは None）
+fn authenticate(data: &AppState, token: &str) -> Option<UserInfo> {
+    let token_hash = hash_token(token);
+    let session = {
+        let sessions = data.sessions.lock().unwrap();
+        sessions
+            .sessions
+            .iter()
+            .find(|s| s.token_hash == token_hash)
+            .cloned()?
+    };
+    if session.expires_at <= now_unix() {
+        return None;
+    }
+    let user = data.users.find_by_id(&session.user_id)?;
+    (session.created_at >= user.password_changed_at).then(|| user.info())
+}
+
+fn forbidden() -> HttpResponse {
+    HttpResponse::Forbidden().json(ApiResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new("auth", ErrorCode::TokenScope)]),
+    })
+}
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized().json(ApiResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new("auth", ErrorCode::Unauthenticated)]),
+    })
+}
+
+/// /api 以下の認証。認証済みユーザーはリクエストの拡張データ（web::ReqData<UserInfo>）に入れる
+pub async fn require_auth(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, actix_web::Error> {
+    let path = req.path();
+    let is_api = path == "/api" || path.starts_with("/api/");
+    let is_public = PUBLIC_PATHS.contains(&path) || PUBLIC_PREFIXES.iter().any(|p| path.starts_with(p));
+    if !is_api || is_public {
+        return next.call(req).await.map(ServiceResponse::map_into_left_body);
+    }
+
+    let (Some(data), Some(token)) = (req.app_data::<web::Data<AppState>>(), request_token(req.request())) else {
+        return Ok(req.into_response(unauthorized()).map_into_right_body());
+    };
+    let user = if tokens::is_api_token(&token) {
+        tokens::authenticate(data, &token, req.method(), req.path())
+    } else {
+        authenticate(data, &token).ok_or(TokenError::Invalid)
+    };
+    match user {
+        Ok(user) => {
+            req.extensions_mut().insert(user);
+            next.call(req).await.map(ServiceResponse::map_into_left_body)
+        }
+        Err(TokenError::Forbidden) => Ok(req.into_response(forbidden()).map_into_right_body()),
+        Err(TokenError::Invalid) => Ok(req.into_response(unauthorized()).map_into_right_body()),
+    }
+}
+
+/// `nekotimer-backend user ...` の管理コマンド
+#[derive(clap::Subcommand, Debug)]
+pub enum UserCommand {
+    /// ユーザーを作成する（パスワードは入力を求める）
+    Add {
+        username: String,
+        /// 管理者にする（全ユーザーのタイマーを扱える）
+        #[arg(long)]
+        admin: bool,
+        /// パスワードを標準入力の1行目から読む
+        #[arg(long)]
+        password_stdin: bool,
+    },
+    /// ユーザーの一覧
+    List,
+    /// ユーザーを削除する（タイマーは残り、管理者のみ扱える）
+    Delete { username: String },
+    /// パスワードを変更する（既存のログインは無効になる）
+    Passwd {
+        username: String,
+        #[arg(long)]
+        password_stdin: bool,
+    },
+}
+
+fn read_password(from_stdin: bool) -> Result<String, String> {
+    let password = if from_stdin || !io::stdin().is_terminal() {
+        let mut line = String::new();
+        io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .map_err(|e| e.to_string())?;
+        line.trim_end_matches(['\r', '\n']).to_string()
+    } else {
+        let password = rpassword::prompt_password("パスワード: ").map_err(|e| e.to_string())?;
+        let confirm = rpassword::prompt_password("パスワード（確認）: ").map_err(|e| e.to_string())?;
+        if password != confirm {
+            return Err("パスワードが一致しません".to_string());
+        }
+        password
+    };
+    validate_password(&password).map_err(|e| e.message)?;
+    Ok(password)
+}
+
+fn run_user_command(users_path: &str, command: UserCommand) -> Result<(), String> {
+    let mut file: UserFile = persistence::load_json(users_path).unwrap_or_default();
+    match command {
+        UserCommand::Add {
+            username,
+            admin,
+            password_stdin,
+        } => {
+            validate_username(&username).map_err(|e| e.message)?;
+            if file.users.iter().any(|u| u.username == username) {
+                return Err(format!("ユーザー {} は既に存在します", username));
+            }
+            let password = read_password(password_stdin)?;
+            let now = now_unix();
+            file.users.push(StoredUser {
+                id: Uuid::new_v4().to_string(),
+                username: username.clone(),
+                password_hash: hash_password(&password)?,
+                is_admin: admin,
+                created_at: now,
+                password_changed_at: now,
+            });
+            println!("ユーザー {} を作成しました", username);
+        }
+        UserCommand::List => {
+            for user in &file.users {
+                println!(
+                    "{}\t{}\t{}",
+                    user.username,
+                    if user.is_admin { "管理者" } else { "一般" },
+                    user.id
+                );
+            }
+            return Ok(());
+        }
+        UserCommand::Delete { username } => {
+            let before = file.users.len();
+            file.users.retain(|u| u.username != username);
+            if file.users.len() == before {
+                return Err(format!("ユーザー {} が見つかりません", username));
+            }
+            println!("ユーザー {} を削除しました", username);
+        }
+        UserCommand::Passwd {
+            username,
+            password_stdin,
+        } => {
+            let Some(index) = file.users.iter().position(|u| u.username == username) else {
+                return Err(format!("ユーザー {} が見つかりません", username));
+            };
+            let password = read_password(password_stdin)?;
+            let user = &mut file.users[index];
+            user.password_hash = hash_password(&password)?;
+            user.password_changed_at = now_unix();
+            println!("ユーザー {} のパスワードを変更しました", username);
+        }
+    }
+    persistence::save_json(users_path, &file).map_err(|e| format!("保存に失敗しました: {}", e))
+}
+
+pub fn run_admin(config_path: &str, command: UserCommand) -> Result<(), String> {
+    run_user_command(&persistence::sibling_path(config_path, "users"), command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn login_throttle_backs_off_after_free_failures() {
+        let throttle = LoginThrottle::default();
+        let keys = LoginThrottle::keys("alice", Some("127.0.0.1"));
+        for _ in 0..LOGIN_FREE_FAILURES {
+            throttle.failed(&keys);
+        }
+        assert_eq!(throttle.retry_after(&keys), None);
+
+        for _ in 0..3 {
+            throttle.failed(&keys);
+        }
+        assert!(throttle.retry_after(&keys).is_some_and(|secs| secs <= 4));
+        // 同じ IP からなら別のユーザー名でも待たせる
+        assert!(throttle.retry_after(&LoginThrottle::keys("bob", Some("127.0.0.1"))).is_some());
+        assert_eq!(throttle.retry_after(&LoginThrottle::keys("bob", Some("10.0.0.1"))), None);
+
+        throttle.succeeded(&keys);
+        assert_eq!(throttle.retry_after(&keys), None);
+    }
+
+    #[test]
+    fn login_backoff_doubles_up_to_cap() {
+        let locked_for = |count| {
+            let failures = LoginFailures {
+                count,
+                last_failed_at: 1000,
+            };
+            failures.locked_until().saturating_sub(1000)
+        };
+        assert_eq!(locked_for(LOGIN_FREE_FAILURES), 0);
+        assert_eq!(locked_for(LOGIN_FREE_FAILURES + 1), 1);
+        assert_eq!(locked_for(LOGIN_FREE_FAILURES + 2), 2);
+        assert_eq!(locked_for(LOGIN_FREE_FAILURES + 4), 8);
+        assert_eq!(locked_for(200), LOGIN_MAX_BACKOFF_SECS);
+    }
+}
@@ -0,0 +1,72 @@
+//! CORS ポリシー。既定では同一オリジン（ブラウザの Origin がリクエストの Host と一致）のみ許可する。
+//! 別オリジンのページから使う場合は設定の cors_origins に列挙する（"*" ですべて許可）。
+
+use actix_cors::Cors;
+use actix_web::http::header;
+
+/// 別オリジンに許可するメソッドの既定値
+pub const DEFAULT_METHODS: [&str; 4] = ["GET", "POST", "PUT", "DELETE"];
+/// 別オリジンに許可するリクエストヘッダーの既定値
+pub const DEFAULT_HEADERS: [&str; 2] = ["content-type", "authorization"];
+/// プリフライトの結果をブラウザにキャッシュさせる秒数
+const MAX_AGE_SECS: usize = 3600;
+
+pub const ANY_ORIGIN: &str = "*";
+
+#[derive(Clone, Debug)]
+pub struct CorsPolicy {
+    pub origins: Vec<String>,
+    pub methods: Vec<String>,
+    pub headers: Vec<String>,
+}
+
+impl CorsPolicy {
+    fn allows_any_origin(&self) -> bool {
+        self.origins.iter().any(|o| o == ANY_ORIGIN)
+    }
+
+    pub fn build(&self) -> Cors {
+        let mut cors = Cors::default()
+            .allowed_methods(self.methods.iter().map(String::as_str))
+            .allowed_headers(self.headers.iter().map(String::as_str))
+            .max_age(MAX_AGE_SECS);
+        if self.allows_any_origin() {
+            return cors.allow_any_origin();
+        }
+        for origin in &self.origins {
+            cors = cors.allowed_origin(origin);
+        }
+        // 許可していないオリジンからのリクエストは（プリフライト不要な POST も含め）処理せず拒否する
+        cors.allowed_origin_fn(is_same_origin)
+            .block_on_origin_mismatch(true)
+    }
+
+    /// 起動時のログ用
+    pub fn describe(&self) -> String {
+        let origins = if self.allows_any_origin() {
+            "すべてのオリジン".to_string()
+        } else if self.origins.is_empty() {
+            "同一オリジンのみ".to_string()
+        } else {
+            format!("同一オリジン, {}", self.origins.join(", "))
+        };
+        format!(
+            "{}（メソッド: {} / ヘッダー: {}）",
+            origins,
+            self.methods.join(", "),
+            self.headers.join(", ")
+        )
+    }
+}
+
+/// Origin のホスト部分がリクエストの Host と同じか（http/https は区別しない）
+fn is_same_origin(origin: &header::HeaderValue, req: &actix_web::dev::RequestHead) -> bool {
+    let Some(host) = req.headers().get(header::HOST).and_then(|h| h.to_str().ok()) else {
+        return false;
+    };
+    origin
+        .to_str()
+        .ok()
+        .and_then(|o| o.split_once("://"))
+        .is_some_and(|(_, authority)| authority.eq_ignore_ascii_case(host))
+}
@@ -1,47 +1,138 @@
+pub mod auth;
+pub mod history;
+pub mod hooks;
+pub mod runs;
+pub mod schedules;
+pub mod shares;
+pub mod sound_profiles;
+pub mod sounds;
+pub mod tokens;
+pub mod webhooks;
+pub mod workspaces;
+
 use actix_web::{web, HttpResponse};
-use nekotimer_shared::{ApiResponse, TimerConfig, ValidationError};
+use nekotimer_shared::{
+    ApiResponse, ErrorCode, Resource, TimerConfig, TransferTimerRequest, UserInfo, ValidationError,
+};
 use uuid::Uuid;
 
 use crate::AppState;
 use crate::persistence;
+use crate::workspaces::{permission_denied, timer_role};
+
+/// タイマーの検証ルール（フロントエンドが保存前の検証に使う）
+pub async fn validation_policy(data: web::Data<AppState>) -> HttpResponse {
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(data.validation.clone()),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+fn timer_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<TimerConfig> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "id",
+            ErrorCode::NotFound { resource: Resource::Timer },
+        )]),
+    })
+}
+
+fn workspace_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<TimerConfig> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "workspace_id",
+            ErrorCode::NotFound { resource: Resource::Workspace },
+        )]),
+    })
+}
+
+fn name_conflict() -> HttpResponse {
+    HttpResponse::Conflict().json(ApiResponse::<TimerConfig> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new("name", ErrorCode::DuplicateName)]),
+    })
+}
+
+fn save_failed(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::<TimerConfig> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "system",
+            ErrorCode::SaveFailed { detail: e.to_string() },
+        )]),
+    })
+}
+
+/// 同じスペース（同じワークスペース、または同じユーザーの個人タイマー）か。名前はスペースごとに一意
+fn same_space(a: &TimerConfig, b: &TimerConfig) -> bool {
+    a.workspace_id == b.workspace_id && (a.workspace_id.is_some() || a.owner == b.owner)
+}
+
+/// 移動・コピー先のワークスペースにタイマーを置けるか
+fn check_destination(data: &AppState, user: &UserInfo, workspace_id: &Option<String>) -> Result<(), HttpResponse> {
+    let Some(workspace_id) = workspace_id else {
+        return Ok(());
+    };
+    match data.workspaces.lock().unwrap().role_in(user, workspace_id) {
+        None => Err(workspace_not_found()),
+        Some(role) if !role.can_edit() => Err(permission_denied()),
+        Some(_) => Ok(()),
+    }
+}
 
-pub async fn list_timers(data: web::Data<AppState>) -> HttpResponse {
+pub async fn list_timers(data: web::Data<AppState>, user: web::ReqData<UserInfo>) -> HttpResponse {
     let config = data.config.lock().unwrap();
+    let timers: Vec<TimerConfig> = config
+        .timers
+        .iter()
+        .filter(|t| timer_role(&data, &user, t).is_some())
+        .cloned()
+        .collect();
     HttpResponse::Ok().json(ApiResponse {
         success: true,
-        data: Some(config.timers.clone()),
+        data: Some(timers),
         errors: None::<Vec<ValidationError>>,
     })
 }
 
-pub async fn get_timer(data: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+pub async fn get_timer(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
     let id = path.into_inner();
     let config = data.config.lock().unwrap();
-    match config.timers.iter().find(|t| t.id == id) {
+    match config
+        .timers
+        .iter()
+        .find(|t| t.id == id && timer_role(&data, &user, t).is_some())
+    {
         Some(timer) => HttpResponse::Ok().json(ApiResponse {
             success: true,
             data: Some(timer.clone()),
             errors: None::<Vec<ValidationError>>,
         }),
-        None => HttpResponse::NotFound().json(ApiResponse::<TimerConfig> {
-            success: false,
-            data: None,
-            errors: Some(vec![ValidationError {
-                field: "id".into(),
-                message: "タイマーが見つかりません".into(),
-            }]),
-        }),
+        None => timer_not_found(),
     }
 }
 
 pub async fn create_timer(
     data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
     body: web::Json<TimerConfig>,
 ) -> HttpResponse {
     let mut timer = body.into_inner();
     timer.id = Uuid::new_v4().to_string();
+    timer.owner = Some(user.id.clone());
 
-    if let Err(errors) = timer.validate() {
+    if let Err(errors) = timer.validate(&data.validation) {
         return HttpResponse::BadRequest().json(ApiResponse::<TimerConfig> {
             success: false,
             data: None,
@@ -49,29 +140,20 @@ pub async fn create_timer(
         });
     }
 
+    // ワークスペースに作るには編集者以上の役割が必要
+    if let Err(resp) = check_destination(&data, &user, &timer.workspace_id) {
+        return resp;
+    }
+
     let mut config = data.config.lock().unwrap();
 
-    if config.timers.iter().any(|t| t.name == timer.name) {
-        return HttpResponse::Conflict().json(ApiResponse::<TimerConfig> {
-            success: false,
-            data: None,
-            errors: Some(vec![ValidationError {
-                field: "name".into(),
-                message: "同じ名前のタイマーが既に存在します".into(),
-            }]),
-        });
+    if config.timers.iter().any(|t| same_space(t, &timer) && t.name == timer.name) {
+        return name_conflict();
     }
 
     config.timers.push(timer.clone());
     if let Err(e) = persistence::save_config(&data.config_path, &config) {
-        return HttpResponse::InternalServerError().json(ApiResponse::<TimerConfig> {
-            success: false,
-            data: None,
-            errors: Some(vec![ValidationError {
-                field: "system".into(),
-                message: format!("保存に失敗しました: {}", e),
-            }]),
-        });
+        return save_failed(e);
     }
 
     HttpResponse::Created().json(ApiResponse {
@@ -83,6 +165,7 @@ pub async fn create_timer(
 
 pub async fn update_timer(
     data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
     path: web::Path<String>,
     body: web::Json<TimerConfig>,
 ) -> HttpResponse {
@@ -90,7 +173,7 @@ pub async fn update_timer(
     let mut timer = body.into_inner();
     timer.id = id.clone();
 
-    if let Err(errors) = timer.validate() {
+    if let Err(errors) = timer.validate(&data.validation) {
         return HttpResponse::BadRequest().json(ApiResponse::<TimerConfig> {
             success: false,
             data: None,
@@ -100,17 +183,24 @@ pub async fn update_timer(
 
     let mut config = data.config.lock().unwrap();
 
+    let role = config
+        .timers
+        .iter()
+        .find(|t| t.id == id)
+        .and_then(|t| timer_role(&data, &user, t));
+    match role {
+        None => return timer_not_found(),
+        Some(role) if !role.can_edit() => return permission_denied(),
+        Some(_) => {}
+    }
+
     if let Some(existing) = config.timers.iter_mut().find(|t| t.id == id) {
+        // 所有者と所属はクライアントから変更できない（移動は transfer で行う）
+        timer.owner = existing.owner.clone();
+        timer.workspace_id = existing.workspace_id.clone();
         *existing = timer.clone();
         if let Err(e) = persistence::save_config(&data.config_path, &config) {
-            return HttpResponse::InternalServerError().json(ApiResponse::<TimerConfig> {
-                success: false,
-                data: None,
-                errors: Some(vec![ValidationError {
-                    field: "system".into(),
-                    message: format!("保存に失敗しました: {}", e),
-                }]),
-            });
+            return save_failed(e);
         }
         HttpResponse::Ok().json(ApiResponse {
             success: true,
@@ -118,49 +208,139 @@ pub async fn update_timer(
             errors: None::<Vec<ValidationError>>,
         })
     } else {
-        HttpResponse::NotFound().json(ApiResponse::<TimerConfig> {
-            success: false,
-            data: None,
-            errors: Some(vec![ValidationError {
-                field: "id".into(),
-                message: "タイマーが見つかりません".into(),
-            }]),
-        })
+        timer_not_found()
     }
 }
 
-pub async fn delete_timer(data: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+pub async fn delete_timer(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
     let id = path.into_inner();
     let mut config = data.config.lock().unwrap();
 
-    let len_before = config.timers.len();
+    let role = config
+        .timers
+        .iter()
+        .find(|t| t.id == id)
+        .and_then(|t| timer_role(&data, &user, t));
+    match role {
+        None => return timer_not_found(),
+        Some(role) if !role.can_edit() => return permission_denied(),
+        Some(_) => {}
+    }
     config.timers.retain(|t| t.id != id);
 
-    if config.timers.len() == len_before {
-        return HttpResponse::NotFound().json(ApiResponse::<()> {
+    if let Err(e) = persistence::save_config(&data.config_path, &config) {
+        return save_failed(e);
+    }
+
+    // 削除したタイマーのスケジュールも消す
+    let mut schedules = data.schedules.lock().unwrap();
+    let schedules_before = schedules.schedules.len();
+    schedules.schedules.retain(|s| s.timer_id != id);
+    if schedules.schedules.len() != schedules_before {
+        if let Err(e) = persistence::save_json(&data.schedules_path, &*schedules) {
+            log::error!("スケジュールの保存に失敗しました: {}", e);
+        }
+    }
+    drop(schedules);
+
+    // 共有リンクも消す
+    let mut shares = data.shares.lock().unwrap();
+    let shares_before = shares.links.len();
+    shares.links.retain(|l| l.timer_id != id);
+    if shares.links.len() != shares_before {
+        if let Err(e) = persistence::save_json(&data.shares_path, &*shares) {
+            log::error!("共有リンクの保存に失敗しました: {}", e);
+        }
+    }
+    drop(shares);
+
+    // そのタイマー専用の Webhook も消す
+    let mut webhooks = data.webhooks.lock().unwrap();
+    let webhooks_before = webhooks.webhooks.len();
+    webhooks.webhooks.retain(|w| w.timer_id.as_deref() != Some(id.as_str()));
+    if webhooks.webhooks.len() != webhooks_before {
+        if let Err(e) = persistence::save_json(&data.webhooks_path, &*webhooks) {
+            log::error!("Webhook の保存に失敗しました: {}", e);
+        }
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
+
+/// タイマーを個人とワークスペースの間で移動・コピーする。
+/// 移動は元のタイマーの編集権限、コピーは閲覧権限があればよい。どちらも移動先には編集者以上の役割が必要
+pub async fn transfer_timer(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+    body: web::Json<TransferTimerRequest>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let req = body.into_inner();
+
+    if let Err(resp) = check_destination(&data, &user, &req.workspace_id) {
+        return resp;
+    }
+
+    let mut config = data.config.lock().unwrap();
+
+    let Some(source) = config.timers.iter().find(|t| t.id == id).cloned() else {
+        return timer_not_found();
+    };
+    match timer_role(&data, &user, &source) {
+        None => return timer_not_found(),
+        Some(role) if !req.copy && !role.can_edit() => return permission_denied(),
+        Some(_) => {}
+    }
+
+    let mut timer = source.clone();
+    timer.workspace_id = req.workspace_id;
+    // コピーと個人への移動は自分のタイマーになる。ワークスペースへの移動は作成者を残す
+    if req.copy || timer.workspace_id.is_none() {
+        timer.owner = Some(user.id.clone());
+    }
+    if req.copy {
+        timer.id = Uuid::new_v4().to_string();
+    } else if same_space(&timer, &source) {
+        return HttpResponse::BadRequest().json(ApiResponse::<TimerConfig> {
             success: false,
             data: None,
-            errors: Some(vec![ValidationError {
-                field: "id".into(),
-                message: "タイマーが見つかりません".into(),
-            }]),
+            errors: Some(vec![ValidationError::new("workspace_id", ErrorCode::SameDestination)]),
         });
     }
 
+    if config
+        .timers
+        .iter()
+        .any(|t| t.id != timer.id && same_space(t, &timer) && t.name == timer.name)
+    {
+        return name_conflict();
+    }
+
+    match config.timers.iter_mut().find(|t| t.id == timer.id) {
+        Some(existing) => *existing = timer.clone(),
+        None => config.timers.push(timer.clone()),
+    }
     if let Err(e) = persistence::save_config(&data.config_path, &config) {
-        return HttpResponse::InternalServerError().json(ApiResponse::<()> {
-            success: false,
-            data: None,
-            errors: Some(vec![ValidationError {
-                field: "system".into(),
-                message: format!("保存に失敗しました: {}", e),
-            }]),
-        });
+        return save_failed(e);
     }
 
-    HttpResponse::Ok().json(ApiResponse::<()> {
+    let mut resp = if req.copy {
+        HttpResponse::Created()
+    } else {
+        HttpResponse::Ok()
+    };
+    resp.json(ApiResponse {
         success: true,
-        data: None,
-        errors: None,
+        data: Some(timer),
+        errors: None::<Vec<ValidationError>>,
     })
 }
@@ -0,0 +1,86 @@
+use actix_web::cookie::{time, Cookie, SameSite};
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse};
+use nekotimer_shared::{
+    ApiResponse, ErrorCode, LoginRequest, LoginResponse, UserInfo, ValidationError, SESSION_COOKIE,
+};
+
+use crate::auth::{self, LoginThrottle, SESSION_TTL_SECS};
+use crate::AppState;
+
+fn session_cookie(value: String, max_age_secs: i64) -> Cookie<'static> {
+    Cookie::build(SESSION_COOKIE, value)
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::Strict)
+        .max_age(time::Duration::seconds(max_age_secs))
+        .finish()
+}
+
+fn login_error(status: StatusCode, code: ErrorCode) -> HttpResponse {
+    HttpResponse::build(status).json(ApiResponse::<LoginResponse> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new("auth", code)]),
+    })
+}
+
+pub async fn login(data: web::Data<AppState>, req: HttpRequest, body: web::Json<LoginRequest>) -> HttpResponse {
+    let LoginRequest { username, password } = body.into_inner();
+    let ip = req.peer_addr().map(|addr| addr.ip().to_string());
+    let keys = LoginThrottle::keys(&username, ip.as_deref());
+    if let Some(retry_after_secs) = data.login_throttle.retry_after(&keys) {
+        return login_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::TooManyLoginAttempts { retry_after_secs },
+        );
+    }
+
+    let user = {
+        let data = data.clone();
+        web::block(move || auth::verify_login(&data.users, &username, &password))
+            .await
+            .ok()
+            .flatten()
+    };
+    let Some(user) = user else {
+        data.login_throttle.failed(&keys);
+        return login_error(StatusCode::UNAUTHORIZED, ErrorCode::LoginFailed);
+    };
+    data.login_throttle.succeeded(&keys);
+
+    let (token, expires_at) = auth::create_session(&data, &user);
+    log::info!("ログイン: {}", user.username);
+    HttpResponse::Ok()
+        .cookie(session_cookie(token.clone(), SESSION_TTL_SECS as i64))
+        .json(ApiResponse {
+            success: true,
+            data: Some(LoginResponse {
+                token,
+                expires_at,
+                user: user.info(),
+            }),
+            errors: None::<Vec<ValidationError>>,
+        })
+}
+
+pub async fn logout(data: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    if let Some(token) = auth::request_token(&req) {
+        auth::remove_session(&data, &token);
+    }
+    HttpResponse::Ok()
+        .cookie(session_cookie(String::new(), 0))
+        .json(ApiResponse::<()> {
+            success: true,
+            data: None,
+            errors: None,
+        })
+}
+
+pub async fn me(user: web::ReqData<UserInfo>) -> HttpResponse {
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(user.into_inner()),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
@@ -0,0 +1,145 @@
+use actix_web::{web, HttpResponse};
+use chrono_tz::Tz;
+use nekotimer_shared::{ApiResponse, ErrorCode, RunRecord, RunStats, UserInfo, ValidationError};
+use serde::Deserialize;
+
+use crate::history;
+use crate::workspaces::run_record_filter;
+use crate::AppState;
+
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    pub timer_id: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatsQuery {
+    pub timer_id: Option<String>,
+    /// IANA タイムゾーン名（例: Asia/Tokyo）。省略時は UTC。
+    pub tz: Option<String>,
+}
+
+/// 見えるタイマー（削除したものを含む）の実行履歴を新しい順に返す
+pub async fn list_history(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    query: web::Query<HistoryQuery>,
+) -> HttpResponse {
+    let visible = run_record_filter(&data, &user);
+    let history = data.history.lock().unwrap();
+    let runs: Vec<RunRecord> = history
+        .runs
+        .iter()
+        .rev()
+        .filter(|r| visible(r))
+        .filter(|r| query.timer_id.as_ref().is_none_or(|id| r.timer_id == *id))
+        .skip(query.offset.unwrap_or(0))
+        .take(query.limit.unwrap_or(DEFAULT_HISTORY_LIMIT))
+        .cloned()
+        .collect();
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(runs),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn history_stats(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    query: web::Query<StatsQuery>,
+) -> HttpResponse {
+    let tz_name = query.tz.clone().unwrap_or_else(|| "UTC".to_string());
+    let tz: Tz = match tz_name.parse() {
+        Ok(tz) => tz,
+        Err(_) => {
+            return HttpResponse::BadRequest().json(ApiResponse::<RunStats> {
+                success: false,
+                data: None,
+                errors: Some(vec![ValidationError::new(
+                    "tz",
+                    ErrorCode::InvalidFormat { detail: Some(tz_name.to_string()) },
+                )]),
+            });
+        }
+    };
+
+    let visible = run_record_filter(&data, &user);
+    let history = data.history.lock().unwrap();
+    let records = history
+        .runs
+        .iter()
+        .filter(|r| visible(r))
+        .filter(|r| query.timer_id.as_ref().is_none_or(|id| r.timer_id == *id));
+    let stats = history::compute_stats(records, &tz);
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(stats),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+    use nekotimer_shared::{RunOutcome, RunRecord};
+
+    use super::*;
+    use crate::handlers;
+    use crate::test_support::{admin, request_as, state, timer, user};
+
+    fn record(timer: &nekotimer_shared::TimerConfig) -> RunRecord {
+        RunRecord {
+            id: format!("run-{}", timer.id),
+            timer_id: timer.id.clone(),
+            timer: timer.clone(),
+            started_at: 1_700_000_000,
+            ended_at: 1_700_000_060,
+            outcome: RunOutcome::Completed,
+            aborted_block_ix: None,
+            aborted_countdown_run: None,
+            active_secs: 60,
+        }
+    }
+
+    #[actix_web::test]
+    async fn deleted_timer_history_stays_visible_to_its_owner() {
+        let data = state();
+        let alice_timer = timer("t1", "alice");
+        data.config.lock().unwrap().timers.push(alice_timer.clone());
+        data.history.lock().unwrap().runs.push(record(&alice_timer));
+        let app = test::init_service(
+            App::new()
+                .app_data(data.clone())
+                .route("/api/timers/{id}", web::delete().to(handlers::delete_timer))
+                .route("/api/history", web::get().to(list_history))
+                .route("/api/history/stats", web::get().to(history_stats)),
+        )
+        .await;
+
+        let alice = user("alice");
+        let req = request_as(test::TestRequest::delete().uri("/api/timers/t1").to_request(), &alice);
+        assert!(test::call_service(&app, req).await.status().is_success());
+        assert!(data.config.lock().unwrap().timers.is_empty());
+
+        let list = |who| {
+            let req = request_as(test::TestRequest::get().uri("/api/history").to_request(), who);
+            test::call_and_read_body_json::<_, _, ApiResponse<Vec<RunRecord>>>(&app, req)
+        };
+        let runs = |resp: ApiResponse<Vec<RunRecord>>| resp.data.unwrap_or_default();
+        assert_eq!(runs(list(&alice).await).len(), 1);
+        let (root, bob) = (admin("root"), user("bob"));
+        assert_eq!(runs(list(&root).await).len(), 1);
+        assert!(runs(list(&bob).await).is_empty());
+
+        let req = request_as(test::TestRequest::get().uri("/api/history/stats").to_request(), &alice);
+        let stats: ApiResponse<RunStats> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(stats.data.unwrap().total_runs, 1);
+    }
+}
@@ -0,0 +1,49 @@
+use actix_web::{web, HttpResponse};
+use nekotimer_shared::{ApiResponse, CommandHookExecution, UserInfo, ValidationError};
+use serde::Deserialize;
+
+use crate::workspaces::permission_denied;
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct ExecutionQuery {
+    pub hook_id: Option<String>,
+}
+
+/// 読み込み済みのコマンドフック（定義の変更はファイルを編集して再起動する）。
+/// コマンドラインと出力にはサーバーの情報が含まれるので、フックの API は管理者だけが使える
+pub async fn list_hooks(data: web::Data<AppState>, user: web::ReqData<UserInfo>) -> HttpResponse {
+    if !user.is_admin {
+        return permission_denied();
+    }
+    let hooks = data.hooks.lock().unwrap();
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(hooks.hooks.clone()),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// 実行ログ（新しい順）
+pub async fn list_executions(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    query: web::Query<ExecutionQuery>,
+) -> HttpResponse {
+    if !user.is_admin {
+        return permission_denied();
+    }
+    let log = data.hook_executions.lock().unwrap();
+    let list: Vec<CommandHookExecution> = log
+        .iter()
+        .rev()
+        .filter(|e| query.hook_id.as_ref().is_none_or(|id| e.hook_id == *id))
+        .cloned()
+        .collect();
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(list),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
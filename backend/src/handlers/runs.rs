@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use actix_web::http::header;
+use actix_web::web::Bytes;
+use actix_web::{web, HttpResponse};
+use futures_util::stream::{self, StreamExt};
+use nekotimer_shared::{
+    ApiResponse, ErrorCode, Resource, RunSession, RunUpdate, StartRunRequest, UserInfo,
+    ValidationError,
+};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::runs::RunCommand;
+use crate::workspaces::{permission_denied, timer_role};
+use crate::AppState;
+
+/// SSE 接続維持用のコメント送信間隔
+const SSE_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+fn session_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<RunSession> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "id",
+            ErrorCode::NotFound { resource: Resource::RunSession },
+        )]),
+    })
+}
+
+/// 見えるタイマーの実行セッションか（開始時のタイマーの所属で判断する）
+fn can_see(data: &AppState, user: &UserInfo, session: &RunSession) -> bool {
+    timer_role(data, user, &session.timer).is_some()
+}
+
+pub async fn list_runs(data: web::Data<AppState>, user: web::ReqData<UserInfo>) -> HttpResponse {
+    let sessions: Vec<RunSession> = data
+        .runs
+        .list()
+        .into_iter()
+        .filter(|s| can_see(&data, &user, s))
+        .collect();
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(sessions),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn get_run(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    match data.runs.get(&path.into_inner()).filter(|s| can_see(&data, &user, s)) {
+        Some(session) => HttpResponse::Ok().json(ApiResponse {
+            success: true,
+            data: Some(session),
+            errors: None::<Vec<ValidationError>>,
+        }),
+        None => session_not_found(),
+    }
+}
+
+pub async fn start_run(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    body: web::Json<StartRunRequest>,
+) -> HttpResponse {
+    let timer_id = body.into_inner().timer_id;
+    let found = {
+        let config = data.config.lock().unwrap();
+        config
+            .timers
+            .iter()
+            .find(|t| t.id == timer_id)
+            .and_then(|t| timer_role(&data, &user, t).map(|role| (t.clone(), role)))
+    };
+
+    match found {
+        Some((_, role)) if !role.can_run() => permission_denied(),
+        Some((timer, _)) => HttpResponse::Created().json(ApiResponse {
+            success: true,
+            data: Some(data.runs.start(timer)),
+            errors: None::<Vec<ValidationError>>,
+        }),
+        None => HttpResponse::NotFound().json(ApiResponse::<RunSession> {
+            success: false,
+            data: None,
+            errors: Some(vec![ValidationError::new(
+                "timer_id",
+                ErrorCode::NotFound { resource: Resource::Timer },
+            )]),
+        }),
+    }
+}
+
+/// 一時停止・再開・スキップ・中止は実行者以上
+fn control(data: &AppState, user: &UserInfo, id: &str, command: RunCommand) -> HttpResponse {
+    match data.runs.get(id).and_then(|s| timer_role(data, user, &s.timer)) {
+        None => return session_not_found(),
+        Some(role) if !role.can_run() => return permission_denied(),
+        Some(_) => {}
+    }
+    match data.runs.control(id, command) {
+        Some(session) => HttpResponse::Ok().json(ApiResponse {
+            success: true,
+            data: Some(session),
+            errors: None::<Vec<ValidationError>>,
+        }),
+        None => session_not_found(),
+    }
+}
+
+pub async fn pause_run(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    control(&data, &user, &path.into_inner(), RunCommand::Pause)
+}
+
+pub async fn resume_run(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    control(&data, &user, &path.into_inner(), RunCommand::Resume)
+}
+
+pub async fn skip_run(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    control(&data, &user, &path.into_inner(), RunCommand::Skip)
+}
+
+pub async fn stop_run(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    control(&data, &user, &path.into_inner(), RunCommand::Stop)
+}
+
+fn sse_frame(update: &RunUpdate) -> Result<Bytes, actix_web::Error> {
+    let json = serde_json::to_string(update).map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(Bytes::from(format!("data: {}\n\n", json)))
+}
+
+/// ユーザーに見えるセッションの状態変化を Server-Sent Events で配信する。
+/// 接続直後に保持中セッションのスナップショットを送る。
+pub async fn run_events(data: web::Data<AppState>, user: web::ReqData<UserInfo>) -> HttpResponse {
+    let user = user.into_inner();
+    let rx = data.runs.subscribe();
+    let initial: Vec<_> = data
+        .runs
+        .list()
+        .into_iter()
+        .filter(|s| can_see(&data, &user, s))
+        .map(|session| sse_frame(&RunUpdate {
+            session,
+            events: Vec::new(),
+        }))
+        .collect();
+
+    let live = stream::unfold((rx, data, user), |(mut rx, data, user)| async move {
+        loop {
+            match tokio::time::timeout(SSE_KEEP_ALIVE, rx.recv()).await {
+                Err(_) => return Some((Ok(Bytes::from_static(b": keep-alive\n\n")), (rx, data, user))),
+                Ok(Ok(update)) if can_see(&data, &user, &update.session) => {
+                    return Some((sse_frame(&update), (rx, data, user)))
+                }
+                Ok(Ok(_)) => continue,
+                Ok(Err(RecvError::Lagged(_))) => continue,
+                Ok(Err(RecvError::Closed)) => return None,
+            }
+        }
+    });
+
+    HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .content_type("text/event-stream")
+        .streaming(stream::iter(initial).chain(live))
+}
@@ -0,0 +1,195 @@
+use actix_web::{web, HttpResponse};
+use nekotimer_shared::{ApiResponse, ErrorCode, Resource, Schedule, UserInfo, ValidationError};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::persistence;
+use crate::scheduler;
+use crate::workspaces::{permission_denied, timer_role_by_id, visible_timer_ids};
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct ScheduleQuery {
+    pub timer_id: Option<String>,
+}
+
+fn schedule_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<Schedule> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "id",
+            ErrorCode::NotFound { resource: Resource::Schedule },
+        )]),
+    })
+}
+
+/// 入力値・cron 式・タイムゾーン・対象タイマー（ユーザーが編集できるもの）の存在を確認する
+fn check_schedule(data: &AppState, user: &UserInfo, schedule: &Schedule) -> Result<(), HttpResponse> {
+    let mut errors = match schedule.validate() {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors,
+    };
+    if let Err(rule_errors) = scheduler::check_rule(schedule) {
+        errors.extend(rule_errors);
+    }
+    match timer_role_by_id(data, user, &schedule.timer_id) {
+        None if !schedule.timer_id.is_empty() => errors.push(ValidationError::new(
+            "timer_id",
+            ErrorCode::NotFound { resource: Resource::Timer },
+        )),
+        // スケジュールの設定は編集者以上
+        Some(role) if !role.can_edit() => return Err(permission_denied()),
+        _ => {}
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(HttpResponse::BadRequest().json(ApiResponse::<Schedule> {
+            success: false,
+            data: None,
+            errors: Some(errors),
+        }))
+    }
+}
+
+/// 既存のスケジュールを変更できるか（対象タイマーの編集者以上）
+fn check_existing(data: &AppState, user: &UserInfo, id: &str) -> Result<(), HttpResponse> {
+    let timer_id = {
+        let schedules = data.schedules.lock().unwrap();
+        match schedules.schedules.iter().find(|s| s.id == id) {
+            Some(s) => s.timer_id.clone(),
+            None => return Err(schedule_not_found()),
+        }
+    };
+    match timer_role_by_id(data, user, &timer_id) {
+        None => Err(schedule_not_found()),
+        Some(role) if !role.can_edit() => Err(permission_denied()),
+        Some(_) => Ok(()),
+    }
+}
+
+fn save_failed(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::<Schedule> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "system",
+            ErrorCode::SaveFailed { detail: e.to_string() },
+        )]),
+    })
+}
+
+pub async fn list_schedules(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    query: web::Query<ScheduleQuery>,
+) -> HttpResponse {
+    let timer_ids = visible_timer_ids(&data, &user);
+    let schedules = data.schedules.lock().unwrap();
+    let list: Vec<Schedule> = schedules
+        .schedules
+        .iter()
+        .filter(|s| timer_ids.contains(&s.timer_id))
+        .filter(|s| query.timer_id.as_ref().is_none_or(|id| s.timer_id == *id))
+        .map(scheduler::with_next_run)
+        .collect();
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(list),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn create_schedule(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    body: web::Json<Schedule>,
+) -> HttpResponse {
+    let mut schedule = body.into_inner();
+    schedule.id = Uuid::new_v4().to_string();
+    schedule.next_run_at = None;
+    schedule.last_run_at = None;
+
+    if let Err(resp) = check_schedule(&data, &user, &schedule) {
+        return resp;
+    }
+
+    let mut schedules = data.schedules.lock().unwrap();
+    schedules.schedules.push(schedule.clone());
+    if let Err(e) = persistence::save_json(&data.schedules_path, &*schedules) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Created().json(ApiResponse {
+        success: true,
+        data: Some(scheduler::with_next_run(&schedule)),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn update_schedule(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+    body: web::Json<Schedule>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let mut schedule = body.into_inner();
+    schedule.id = id.clone();
+    schedule.next_run_at = None;
+
+    if let Err(resp) = check_existing(&data, &user, &id) {
+        return resp;
+    }
+    if let Err(resp) = check_schedule(&data, &user, &schedule) {
+        return resp;
+    }
+
+    let mut schedules = data.schedules.lock().unwrap();
+    match schedules.schedules.iter_mut().find(|s| s.id == id) {
+        Some(existing) => {
+            schedule.last_run_at = existing.last_run_at;
+            *existing = schedule.clone();
+        }
+        None => return schedule_not_found(),
+    }
+    if let Err(e) = persistence::save_json(&data.schedules_path, &*schedules) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(scheduler::with_next_run(&schedule)),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn delete_schedule(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    if let Err(resp) = check_existing(&data, &user, &id) {
+        return resp;
+    }
+    let mut schedules = data.schedules.lock().unwrap();
+
+    let len_before = schedules.schedules.len();
+    schedules.schedules.retain(|s| s.id != id);
+    if schedules.schedules.len() == len_before {
+        return schedule_not_found();
+    }
+    if let Err(e) = persistence::save_json(&data.schedules_path, &*schedules) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
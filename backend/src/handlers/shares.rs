@@ -0,0 +1,175 @@
+use actix_web::{web, HttpResponse};
+use nekotimer_shared::{
+    ApiResponse, CreateShareLinkRequest, ErrorCode, Resource, ShareLink, SharedTimer, UserInfo,
+    ValidationError,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::persistence;
+use crate::runs::now_unix;
+use crate::workspaces::{editable_timer_ids, permission_denied, timer_role_by_id};
+use crate::AppState;
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Deserialize)]
+pub struct ShareQuery {
+    pub timer_id: Option<String>,
+}
+
+fn not_found(field: &str, code: ErrorCode) -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(field, code)]),
+    })
+}
+
+fn save_failed(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "system",
+            ErrorCode::SaveFailed { detail: e.to_string() },
+        )]),
+    })
+}
+
+/// 編集者以上として扱えるタイマーの共有リンク（期限切れを含む、新しい順）。
+/// リンクの URL を知れば誰でも開けるので、作成・取り消しと同じく閲覧者には見せない
+pub async fn list_shares(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    query: web::Query<ShareQuery>,
+) -> HttpResponse {
+    let timer_ids = editable_timer_ids(&data, &user);
+    let shares = data.shares.lock().unwrap();
+    let list: Vec<ShareLink> = shares
+        .links
+        .iter()
+        .rev()
+        .filter(|l| timer_ids.contains(&l.timer_id))
+        .filter(|l| query.timer_id.as_ref().is_none_or(|id| l.timer_id == *id))
+        .cloned()
+        .collect();
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(list),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn create_share(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    body: web::Json<CreateShareLinkRequest>,
+) -> HttpResponse {
+    let req = body.into_inner();
+    if let Err(errors) = req.validate() {
+        return HttpResponse::BadRequest().json(ApiResponse::<ShareLink> {
+            success: false,
+            data: None,
+            errors: Some(errors),
+        });
+    }
+    // 共有リンクの作成・取り消しは編集者以上
+    match timer_role_by_id(&data, &user, &req.timer_id) {
+        None => return not_found("timer_id", ErrorCode::NotFound { resource: Resource::Timer }),
+        Some(role) if !role.can_edit() => return permission_denied(),
+        Some(_) => {}
+    }
+
+    let now = now_unix();
+    let link = ShareLink {
+        id: Uuid::new_v4().to_string(),
+        timer_id: req.timer_id,
+        token: Uuid::new_v4().simple().to_string(),
+        allow_run: req.allow_run,
+        created_at: now,
+        expires_at: req.expires_in_days.map(|days| now + days as u64 * DAY_SECS),
+    };
+    let mut shares = data.shares.lock().unwrap();
+    shares.links.retain(|l| !l.is_expired(now));
+    shares.links.push(link.clone());
+    if let Err(e) = persistence::save_json(&data.shares_path, &*shares) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Created().json(ApiResponse {
+        success: true,
+        data: Some(link),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// 共有リンクを取り消す
+pub async fn delete_share(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let timer_id = {
+        let shares = data.shares.lock().unwrap();
+        shares.links.iter().find(|l| l.id == id).map(|l| l.timer_id.clone())
+    };
+    match timer_id.and_then(|timer_id| timer_role_by_id(&data, &user, &timer_id)) {
+        None => return not_found("id", ErrorCode::NotFound { resource: Resource::ShareLink }),
+        Some(role) if !role.can_edit() => return permission_denied(),
+        Some(_) => {}
+    }
+    let mut shares = data.shares.lock().unwrap();
+    let before = shares.links.len();
+    shares.links.retain(|l| l.id != id);
+    if shares.links.len() == before {
+        return not_found("id", ErrorCode::NotFound { resource: Resource::ShareLink });
+    }
+    if let Err(e) = persistence::save_json(&data.shares_path, &*shares) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
+
+/// 共有リンクでの閲覧（認証なし）
+pub async fn get_shared(data: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let token = path.into_inner();
+    let link = {
+        let shares = data.shares.lock().unwrap();
+        shares
+            .links
+            .iter()
+            .find(|l| l.token == token && !l.is_expired(now_unix()))
+            .cloned()
+    };
+    let timer = link.as_ref().and_then(|link| {
+        let config = data.config.lock().unwrap();
+        config.timers.iter().find(|t| t.id == link.timer_id).cloned()
+    });
+    let (Some(link), Some(mut timer)) = (link, timer) else {
+        return not_found("token", ErrorCode::ShareLinkUnavailable);
+    };
+    timer.owner = None;
+    timer.workspace_id = None;
+    let sound_profile = timer.sound_profile.as_ref().and_then(|id| {
+        let profiles = data.sound_profiles.lock().unwrap();
+        profiles.profiles.iter().find(|p| p.id == *id).cloned()
+    });
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(SharedTimer {
+            timer,
+            allow_run: link.allow_run,
+            expires_at: link.expires_at,
+            sound_profile,
+        }),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
@@ -0,0 +1,194 @@
+use actix_web::{web, HttpResponse};
+use nekotimer_shared::{ApiResponse, ErrorCode, Resource, SoundProfile, UserInfo, ValidationError};
+use uuid::Uuid;
+
+use crate::persistence;
+use crate::workspaces::permission_denied;
+use crate::AppState;
+
+fn profile_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<SoundProfile> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "id",
+            ErrorCode::NotFound { resource: Resource::SoundProfile },
+        )]),
+    })
+}
+
+fn save_failed(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::<SoundProfile> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "system",
+            ErrorCode::SaveFailed { detail: e.to_string() },
+        )]),
+    })
+}
+
+fn check_profile(profile: &SoundProfile) -> Result<(), HttpResponse> {
+    profile.validate().map_err(|errors| {
+        HttpResponse::BadRequest().json(ApiResponse::<SoundProfile> {
+            success: false,
+            data: None,
+            errors: Some(errors),
+        })
+    })
+}
+
+/// 変更できるのは作成者と管理者だけ
+fn can_modify(user: &UserInfo, profile: &SoundProfile) -> bool {
+    user.is_admin || profile.owner == user.id
+}
+
+/// すべての効果音プロファイル（サーバー全体で共有）
+pub async fn list_sound_profiles(data: web::Data<AppState>) -> HttpResponse {
+    let profiles = data.sound_profiles.lock().unwrap();
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(profiles.profiles.clone()),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn create_sound_profile(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    body: web::Json<SoundProfile>,
+) -> HttpResponse {
+    let mut profile = body.into_inner();
+    profile.id = Uuid::new_v4().to_string();
+    profile.owner = user.id.clone();
+    profile.name = profile.name.trim().to_string();
+    if let Err(resp) = check_profile(&profile) {
+        return resp;
+    }
+
+    let mut profiles = data.sound_profiles.lock().unwrap();
+    profiles.profiles.push(profile.clone());
+    if let Err(e) = persistence::save_json(&data.sound_profiles_path, &*profiles) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Created().json(ApiResponse {
+        success: true,
+        data: Some(profile),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn update_sound_profile(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+    body: web::Json<SoundProfile>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let mut profile = body.into_inner();
+    profile.id = id.clone();
+    profile.name = profile.name.trim().to_string();
+    if let Err(resp) = check_profile(&profile) {
+        return resp;
+    }
+
+    let mut profiles = data.sound_profiles.lock().unwrap();
+    match profiles.profiles.iter_mut().find(|p| p.id == id) {
+        Some(existing) if can_modify(&user, existing) => {
+            profile.owner = existing.owner.clone();
+            *existing = profile.clone();
+        }
+        Some(_) => return permission_denied(),
+        None => return profile_not_found(),
+    }
+    if let Err(e) = persistence::save_json(&data.sound_profiles_path, &*profiles) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(profile),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// 削除したプロファイルを使っていたタイマーは既定の効果音に戻る
+pub async fn delete_sound_profile(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    // ロックは config → profiles の順に取る（delete_sound と同じ）
+    let mut config = data.config.lock().unwrap();
+    let mut profiles = data.sound_profiles.lock().unwrap();
+    match profiles.profiles.iter().find(|p| p.id == id) {
+        Some(existing) if can_modify(&user, existing) => {}
+        Some(_) => return permission_denied(),
+        None => return profile_not_found(),
+    }
+    profiles.profiles.retain(|p| p.id != id);
+    if let Err(e) = persistence::save_json(&data.sound_profiles_path, &*profiles) {
+        return save_failed(e);
+    }
+    drop(profiles);
+
+    let mut changed = false;
+    for timer in &mut config.timers {
+        if timer.sound_profile.as_deref() == Some(id.as_str()) {
+            timer.sound_profile = None;
+            changed = true;
+        }
+    }
+    if changed {
+        if let Err(e) = persistence::save_config(&data.config_path, &config) {
+            return save_failed(e);
+        }
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+
+    use super::*;
+    use crate::test_support::{request_as, state, timer, user};
+
+    #[actix_web::test]
+    async fn deleting_profile_clears_it_from_timers() {
+        let data = state();
+        let mut uses_profile = timer("t1", "alice");
+        uses_profile.sound_profile = Some("p1".into());
+        let mut other_profile = timer("t2", "alice");
+        other_profile.sound_profile = Some("p2".into());
+        data.config.lock().unwrap().timers.extend([uses_profile, other_profile]);
+        data.sound_profiles.lock().unwrap().profiles.push(SoundProfile {
+            id: "p1".into(),
+            name: "profile".into(),
+            owner: "alice".into(),
+            sounds: Default::default(),
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(data.clone())
+                .route("/api/sound-profiles/{id}", web::delete().to(delete_sound_profile)),
+        )
+        .await;
+
+        let req = test::TestRequest::delete().uri("/api/sound-profiles/p1").to_request();
+        let resp = test::call_service(&app, request_as(req, &user("alice"))).await;
+        assert!(resp.status().is_success());
+
+        let config = data.config.lock().unwrap();
+        assert_eq!(config.timers[0].sound_profile, None);
+        assert_eq!(config.timers[1].sound_profile.as_deref(), Some("p2"));
+        assert!(data.sound_profiles.lock().unwrap().profiles.is_empty());
+    }
+}
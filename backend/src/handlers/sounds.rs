@@ -0,0 +1,250 @@
+//! アップロードした効果音（効果音ライブラリ）。
+//! ファイルは設定ファイルと同じ場所の `<名前>.sounds/` に ID のファイル名で置き、一覧は `<名前>.custom_sounds.json` に持つ。
+
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse};
+use futures_util::StreamExt;
+use nekotimer_shared::{
+    base_content_type, forget_sound, matches_sound_signature, validate_custom_sound_name,
+    validate_custom_sound_upload,
+    ApiResponse, CustomSound, ErrorCode, RenameCustomSoundRequest, Resource, UserInfo,
+    ValidationError, CUSTOM_SOUND_MAX_BYTES,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::persistence;
+use crate::runs::now_unix;
+use crate::workspaces::permission_denied;
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct UploadQuery {
+    pub name: String,
+}
+
+fn sound_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<CustomSound> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "id",
+            ErrorCode::NotFound { resource: Resource::Sound },
+        )]),
+    })
+}
+
+fn bad_request(errors: Vec<ValidationError>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::<CustomSound> {
+        success: false,
+        data: None,
+        errors: Some(errors),
+    })
+}
+
+fn save_failed(e: impl std::fmt::Display) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::<CustomSound> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "system",
+            ErrorCode::SaveFailed { detail: e.to_string() },
+        )]),
+    })
+}
+
+/// 変更できるのは作成者と管理者だけ
+fn can_modify(user: &UserInfo, sound: &CustomSound) -> bool {
+    user.is_admin || sound.owner == user.id
+}
+
+/// アップロードした効果音の一覧（サーバー全体で共有）
+pub async fn list_sounds(data: web::Data<AppState>) -> HttpResponse {
+    let sounds = data.custom_sounds.lock().unwrap();
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(sounds.sounds.clone()),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// 効果音をアップロードする。本文はファイルそのもの、名前はクエリ（`?name=`）で受け取る。
+/// 上限を超えた時点で読み込みをやめる
+pub async fn upload_sound(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    req: HttpRequest,
+    query: web::Query<UploadQuery>,
+    mut payload: web::Payload,
+) -> HttpResponse {
+    let name = query.name.trim().to_string();
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(base_content_type)
+        .unwrap_or_default();
+
+    let mut body = web::BytesMut::new();
+    let mut too_large = false;
+    while let Some(chunk) = payload.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                let detail = Some(e.to_string());
+                return bad_request(vec![ValidationError::new("file", ErrorCode::InvalidFormat { detail })]);
+            }
+        };
+        if body.len() + chunk.len() > CUSTOM_SOUND_MAX_BYTES {
+            too_large = true;
+            break;
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let size = if too_large { CUSTOM_SOUND_MAX_BYTES + 1 } else { body.len() };
+    if let Err(errors) = validate_custom_sound_upload(&name, &content_type, size) {
+        return bad_request(errors);
+    }
+    if !matches_sound_signature(&content_type, &body) {
+        return bad_request(vec![ValidationError::new("file", ErrorCode::UnsupportedFileType)]);
+    }
+
+    let sound = CustomSound {
+        id: Uuid::new_v4().to_string(),
+        name,
+        owner: user.id.clone(),
+        content_type,
+        size,
+        uploaded_at: now_unix(),
+    };
+    if let Err(e) = tokio::fs::create_dir_all(&data.sounds_dir).await {
+        return save_failed(e);
+    }
+    let file_path = data.sounds_dir.join(&sound.id);
+    if let Err(e) = tokio::fs::write(&file_path, &body).await {
+        return save_failed(e);
+    }
+
+    let mut sounds = data.custom_sounds.lock().unwrap();
+    sounds.sounds.push(sound.clone());
+    if let Err(e) = persistence::save_json(&data.custom_sounds_path, &*sounds) {
+        sounds.sounds.retain(|s| s.id != sound.id);
+        let _ = std::fs::remove_file(&file_path);
+        return save_failed(e);
+    }
+
+    HttpResponse::Created().json(ApiResponse {
+        success: true,
+        data: Some(sound),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn rename_sound(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+    body: web::Json<RenameCustomSoundRequest>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let name = body.name.trim().to_string();
+    if let Err(errors) = validate_custom_sound_name(&name) {
+        return bad_request(errors);
+    }
+
+    let mut sounds = data.custom_sounds.lock().unwrap();
+    let sound = match sounds.sounds.iter_mut().find(|s| s.id == id) {
+        Some(existing) if can_modify(&user, existing) => {
+            existing.name = name;
+            existing.clone()
+        }
+        Some(_) => return permission_denied(),
+        None => return sound_not_found(),
+    };
+    if let Err(e) = persistence::save_json(&data.custom_sounds_path, &*sounds) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(sound),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// 効果音を削除し、タイマーと効果音プロファイルで使っていた箇所は既定の効果音に戻す
+pub async fn delete_sound(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let sound_id = {
+        let mut sounds = data.custom_sounds.lock().unwrap();
+        let sound_id = match sounds.sounds.iter().find(|s| s.id == id) {
+            Some(existing) if can_modify(&user, existing) => existing.sound_id(),
+            Some(_) => return permission_denied(),
+            None => return sound_not_found(),
+        };
+        sounds.sounds.retain(|s| s.id != id);
+        if let Err(e) = persistence::save_json(&data.custom_sounds_path, &*sounds) {
+            return save_failed(e);
+        }
+        sound_id
+    };
+    if let Err(e) = tokio::fs::remove_file(data.sounds_dir.join(&id)).await {
+        log::warn!("効果音ファイルを削除できませんでした ({}): {}", id, e);
+    }
+
+    {
+        let mut config = data.config.lock().unwrap();
+        let mut changed = false;
+        for timer in &mut config.timers {
+            changed |= forget_sound(timer, &sound_id);
+        }
+        if changed {
+            if let Err(e) = persistence::save_config(&data.config_path, &config) {
+                return save_failed(e);
+            }
+        }
+    }
+    {
+        let mut profiles = data.sound_profiles.lock().unwrap();
+        let mut changed = false;
+        for profile in &mut profiles.profiles {
+            changed |= profile.sounds.forget(&sound_id);
+        }
+        if changed {
+            if let Err(e) = persistence::save_json(&data.sound_profiles_path, &*profiles) {
+                return save_failed(e);
+            }
+        }
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
+
+/// `/sound/custom/{id}` の配信（ログイン不要。共有リンクの画面でも鳴らすため）。
+/// 同じ ID の中身は変わらないので長期キャッシュさせる。ブラウザに中身から形式を推測させない
+pub async fn serve_sound(data: web::Data<AppState>, path: web::Path<String>) -> HttpResponse {
+    let id = path.into_inner();
+    let content_type = {
+        let sounds = data.custom_sounds.lock().unwrap();
+        match sounds.sounds.iter().find(|s| s.id == id) {
+            Some(sound) => sound.content_type.clone(),
+            None => return HttpResponse::NotFound().finish(),
+        }
+    };
+    match tokio::fs::read(data.sounds_dir.join(&id)).await {
+        Ok(body) => HttpResponse::Ok()
+            .insert_header((header::CACHE_CONTROL, "public, max-age=31536000, immutable"))
+            .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+            .content_type(content_type)
+            .body(body),
+        Err(_) => HttpResponse::NotFound().finish(),
+    }
+}
@@ -0,0 +1,97 @@
+use actix_web::{web, HttpResponse};
+use nekotimer_shared::{
+    ApiResponse, ApiToken, CreateApiTokenRequest, CreatedApiToken, ErrorCode, Resource, UserInfo,
+    ValidationError,
+};
+
+use crate::persistence;
+use crate::tokens;
+use crate::AppState;
+
+fn save_failed(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "system",
+            ErrorCode::SaveFailed { detail: e.to_string() },
+        )]),
+    })
+}
+
+/// 自分のトークン一覧（新しい順）
+pub async fn list_tokens(data: web::Data<AppState>, user: web::ReqData<UserInfo>) -> HttpResponse {
+    let tokens = data.api_tokens.lock().unwrap();
+    let list: Vec<ApiToken> = tokens
+        .tokens
+        .iter()
+        .rev()
+        .filter(|t| t.user_id == user.id)
+        .map(|t| t.info())
+        .collect();
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(list),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn create_token(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    body: web::Json<CreateApiTokenRequest>,
+) -> HttpResponse {
+    let req = body.into_inner();
+    if let Err(errors) = req.validate() {
+        return HttpResponse::BadRequest().json(ApiResponse::<CreatedApiToken> {
+            success: false,
+            data: None,
+            errors: Some(errors),
+        });
+    }
+
+    let (token, record) = tokens::issue(&user.id, &req.name, req.scope);
+    let info = record.info();
+    let mut tokens = data.api_tokens.lock().unwrap();
+    tokens.tokens.push(record);
+    if let Err(e) = persistence::save_json(&data.api_tokens_path, &*tokens) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Created().json(ApiResponse {
+        success: true,
+        data: Some(CreatedApiToken { token, info }),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// トークンを失効させる（自分のトークンのみ）
+pub async fn revoke_token(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let mut tokens = data.api_tokens.lock().unwrap();
+    let before = tokens.tokens.len();
+    tokens.tokens.retain(|t| t.id != id || t.user_id != user.id);
+    if tokens.tokens.len() == before {
+        return HttpResponse::NotFound().json(ApiResponse::<()> {
+            success: false,
+            data: None,
+            errors: Some(vec![ValidationError::new(
+                "id",
+                ErrorCode::NotFound { resource: Resource::ApiToken },
+            )]),
+        });
+    }
+    if let Err(e) = persistence::save_json(&data.api_tokens_path, &*tokens) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
@@ -0,0 +1,388 @@
+use std::collections::HashSet;
+
+use actix_web::{web, HttpResponse};
+use nekotimer_shared::{
+    ApiResponse, ErrorCode, Resource, UserInfo, ValidationError, Webhook, WebhookDelivery,
+    WebhookEvent, WebhookPayload,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::persistence;
+use crate::runs::now_unix;
+use crate::webhooks;
+use crate::workspaces::{permission_denied, timer_role_by_id, visible_timer_ids};
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryQuery {
+    pub webhook_id: Option<String>,
+}
+
+fn webhook_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<Webhook> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "id",
+            ErrorCode::NotFound { resource: Resource::Webhook },
+        )]),
+    })
+}
+
+/// Webhook が見えるか。タイマー専用なら対象タイマーが見えること、
+/// すべてのタイマー向けなら登録したユーザーか管理者であること
+fn can_see(user: &UserInfo, timer_ids: &HashSet<String>, webhook: &Webhook) -> bool {
+    match &webhook.timer_id {
+        Some(timer_id) => timer_ids.contains(timer_id),
+        None => user.is_admin || webhook.owner.as_deref() == Some(user.id.as_str()),
+    }
+}
+
+/// 登録したユーザーか（認証導入前の Webhook は管理者）
+fn is_owner(user: &UserInfo, webhook: &Webhook) -> bool {
+    match &webhook.owner {
+        Some(owner) => *owner == user.id,
+        None => user.is_admin,
+    }
+}
+
+/// 返す形にする。秘密鍵は登録したユーザーにだけ返す
+fn redact(user: &UserInfo, mut webhook: Webhook) -> Webhook {
+    if !is_owner(user, &webhook) {
+        webhook.secret.clear();
+    }
+    webhook
+}
+
+/// 入力値と対象タイマー（ユーザーが編集できるもの）の存在を確認する。
+/// すべてのタイマー向けの Webhook は他のユーザーのタイマーのイベントも受け取るので、管理者だけが登録できる
+fn check_webhook(data: &AppState, user: &UserInfo, webhook: &Webhook) -> Result<(), HttpResponse> {
+    let mut errors = match webhook.validate() {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors,
+    };
+    if webhook.timer_id.is_none() && !user.is_admin {
+        return Err(permission_denied());
+    }
+    if let Some(timer_id) = &webhook.timer_id {
+        match timer_role_by_id(data, user, timer_id) {
+            None => errors.push(ValidationError::new(
+                "timer_id",
+                ErrorCode::NotFound { resource: Resource::Timer },
+            )),
+            Some(role) if !role.can_edit() => return Err(permission_denied()),
+            Some(_) => {}
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(HttpResponse::BadRequest().json(ApiResponse::<Webhook> {
+            success: false,
+            data: None,
+            errors: Some(errors),
+        }))
+    }
+}
+
+/// 既存の Webhook を変更できるか。タイマー専用なら対象タイマーの編集者以上、
+/// すべてのタイマー向けなら登録したユーザーか管理者が必要
+fn check_existing(data: &AppState, user: &UserInfo, id: &str) -> Result<Webhook, HttpResponse> {
+    let webhook = {
+        let hooks = data.webhooks.lock().unwrap();
+        hooks.webhooks.iter().find(|w| w.id == id).cloned()
+    };
+    let Some(webhook) = webhook else {
+        return Err(webhook_not_found());
+    };
+    match &webhook.timer_id {
+        Some(timer_id) => match timer_role_by_id(data, user, timer_id) {
+            None => return Err(webhook_not_found()),
+            Some(role) if !role.can_edit() => return Err(permission_denied()),
+            Some(_) => {}
+        },
+        None if !can_see(user, &HashSet::new(), &webhook) => return Err(webhook_not_found()),
+        None => {}
+    }
+    Ok(webhook)
+}
+
+fn save_failed(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::<Webhook> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "system",
+            ErrorCode::SaveFailed { detail: e.to_string() },
+        )]),
+    })
+}
+
+/// 見えるタイマー専用の Webhook と、自分の（管理者はすべての）すべてのタイマー向けの Webhook
+pub async fn list_webhooks(data: web::Data<AppState>, user: web::ReqData<UserInfo>) -> HttpResponse {
+    let timer_ids = visible_timer_ids(&data, &user);
+    let webhooks = data.webhooks.lock().unwrap();
+    let list: Vec<Webhook> = webhooks
+        .webhooks
+        .iter()
+        .filter(|w| can_see(&user, &timer_ids, w))
+        .map(|w| redact(&user, w.clone()))
+        .collect();
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(list),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn create_webhook(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    body: web::Json<Webhook>,
+) -> HttpResponse {
+    let mut webhook = body.into_inner();
+    webhook.id = Uuid::new_v4().to_string();
+    webhook.owner = Some(user.id.clone());
+    if webhook.secret.is_empty() {
+        webhook.secret = webhooks::generate_secret();
+    }
+
+    if let Err(resp) = check_webhook(&data, &user, &webhook) {
+        return resp;
+    }
+
+    let mut hooks = data.webhooks.lock().unwrap();
+    hooks.webhooks.push(webhook.clone());
+    if let Err(e) = persistence::save_json(&data.webhooks_path, &*hooks) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Created().json(ApiResponse {
+        success: true,
+        data: Some(webhook),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn update_webhook(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+    body: web::Json<Webhook>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let mut webhook = body.into_inner();
+    webhook.id = id.clone();
+
+    if let Err(resp) = check_existing(&data, &user, &id) {
+        return resp;
+    }
+    if let Err(resp) = check_webhook(&data, &user, &webhook) {
+        return resp;
+    }
+
+    let mut hooks = data.webhooks.lock().unwrap();
+    match hooks.webhooks.iter_mut().find(|w| w.id == id) {
+        Some(existing) => {
+            // 秘密鍵を空で送った場合は既存の鍵を引き継ぐ。ただし登録者以外が送信先を変えたときは
+            // 登録者の鍵で署名した本文を別の宛先に送らないよう、新しい鍵を生成する
+            if webhook.secret.is_empty() {
+                webhook.secret = if webhook.url != existing.url && !is_owner(&user, existing) {
+                    webhooks::generate_secret()
+                } else {
+                    existing.secret.clone()
+                };
+            }
+            webhook.owner = existing.owner.clone();
+            *existing = webhook.clone();
+        }
+        None => return webhook_not_found(),
+    }
+    if let Err(e) = persistence::save_json(&data.webhooks_path, &*hooks) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(redact(&user, webhook)),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+pub async fn delete_webhook(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    if let Err(resp) = check_existing(&data, &user, &id) {
+        return resp;
+    }
+    let mut hooks = data.webhooks.lock().unwrap();
+
+    let len_before = hooks.webhooks.len();
+    hooks.webhooks.retain(|w| w.id != id);
+    if hooks.webhooks.len() == len_before {
+        return webhook_not_found();
+    }
+    if let Err(e) = persistence::save_json(&data.webhooks_path, &*hooks) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
+
+/// 見える Webhook の送信ログ（新しい順）
+pub async fn list_deliveries(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    query: web::Query<DeliveryQuery>,
+) -> HttpResponse {
+    let timer_ids = visible_timer_ids(&data, &user);
+    let webhook_ids: HashSet<String> = {
+        let webhooks = data.webhooks.lock().unwrap();
+        webhooks
+            .webhooks
+            .iter()
+            .filter(|w| can_see(&user, &timer_ids, w))
+            .map(|w| w.id.clone())
+            .collect()
+    };
+    let log = data.webhook_deliveries.lock().unwrap();
+    let list: Vec<WebhookDelivery> = log
+        .iter()
+        .rev()
+        .filter(|d| webhook_ids.contains(&d.webhook_id))
+        .filter(|d| query.webhook_id.as_ref().is_none_or(|id| d.webhook_id == *id))
+        .cloned()
+        .collect();
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(list),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// テスト送信。再試行を含めて送信を待ち、結果を返す。
+pub async fn test_webhook(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let webhook = match check_existing(&data, &user, &id) {
+        Ok(webhook) => webhook,
+        Err(resp) => return resp,
+    };
+
+    let (timer_id, timer_name) = {
+        let config = data.config.lock().unwrap();
+        let timer = webhook
+            .timer_id
+            .as_ref()
+            .and_then(|id| config.timers.iter().find(|t| t.id == *id));
+        match timer {
+            Some(t) => (t.id.clone(), t.name.clone()),
+            None => (String::new(), "テスト".to_string()),
+        }
+    };
+    let payload = WebhookPayload {
+        delivery_id: Uuid::new_v4().to_string(),
+        event: WebhookEvent::Test,
+        timestamp: now_unix(),
+        session_id: String::new(),
+        timer_id,
+        timer_name,
+        block_index: None,
+        block_name: None,
+        repetition: None,
+        repetitions: None,
+    };
+
+    let delivery = webhooks::deliver(&webhooks::http_client(), &webhook, &payload).await;
+    webhooks::log_delivery(&data, delivery.clone());
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(delivery),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+    use nekotimer_shared::WorkspaceRole;
+
+    use super::*;
+    use crate::test_support::{request_as, state, timer, user};
+    use crate::workspaces::{StoredMember, StoredWorkspace};
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook {
+            id: "h1".into(),
+            url: url.into(),
+            timer_id: Some("t1".into()),
+            events: Vec::new(),
+            secret: String::new(),
+            enabled: true,
+            owner: Some("alice".into()),
+        }
+    }
+
+    #[actix_web::test]
+    async fn editor_changing_url_does_not_inherit_owner_secret() {
+        let data = state();
+        let mut shared = timer("t1", "alice");
+        shared.workspace_id = Some("w1".into());
+        data.config.lock().unwrap().timers.push(shared);
+        data.workspaces.lock().unwrap().workspaces.push(StoredWorkspace {
+            id: "w1".into(),
+            name: "w1".into(),
+            members: vec![
+                StoredMember { user_id: "alice".into(), role: WorkspaceRole::Owner },
+                StoredMember { user_id: "bob".into(), role: WorkspaceRole::Editor },
+            ],
+            created_at: 0,
+        });
+        data.webhooks.lock().unwrap().webhooks.push(Webhook {
+            secret: "alice-secret".into(),
+            ..webhook("https://alice.example/hook")
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(data.clone())
+                .route("/api/webhooks/{id}", web::put().to(update_webhook)),
+        )
+        .await;
+
+        let update = |who, url: &str| {
+            let req = test::TestRequest::put()
+                .uri("/api/webhooks/h1")
+                .set_json(webhook(url))
+                .to_request();
+            test::call_service(&app, request_as(req, who))
+        };
+        let secret = || data.webhooks.lock().unwrap().webhooks[0].secret.clone();
+        let (alice, bob) = (user("alice"), user("bob"));
+
+        // 送信先が同じなら登録者以外の変更でも鍵を引き継ぐ
+        assert!(update(&bob, "https://alice.example/hook").await.status().is_success());
+        assert_eq!(secret(), "alice-secret");
+        // 登録者は送信先を変えても鍵を引き継ぐ
+        assert!(update(&alice, "https://alice.example/v2").await.status().is_success());
+        assert_eq!(secret(), "alice-secret");
+        // 登録者以外が送信先を変えたら新しい鍵になる
+        assert!(update(&bob, "https://bob.example/hook").await.status().is_success());
+        let regenerated = secret();
+        assert!(!regenerated.is_empty());
+        assert_ne!(regenerated, "alice-secret");
+    }
+}
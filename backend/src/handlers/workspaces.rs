@@ -0,0 +1,264 @@
+use actix_web::{web, HttpResponse};
+use nekotimer_shared::{
+    ApiResponse, CreateWorkspaceRequest, ErrorCode, Resource, SetMemberRequest, UserInfo,
+    ValidationError, Workspace, WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::persistence;
+use crate::runs::now_unix;
+use crate::workspaces::{permission_denied, StoredMember, StoredWorkspace};
+use crate::AppState;
+
+fn workspace_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<Workspace> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "id",
+            ErrorCode::NotFound { resource: Resource::Workspace },
+        )]),
+    })
+}
+
+fn bad_request(field: &str, code: ErrorCode) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::<Workspace> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(field, code)]),
+    })
+}
+
+fn save_failed(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ApiResponse::<Workspace> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "system",
+            ErrorCode::SaveFailed { detail: e.to_string() },
+        )]),
+    })
+}
+
+/// 所属するワークスペース（管理者はすべて）
+pub async fn list_workspaces(data: web::Data<AppState>, user: web::ReqData<UserInfo>) -> HttpResponse {
+    let workspaces = data.workspaces.lock().unwrap();
+    let list: Vec<Workspace> = workspaces
+        .workspaces
+        .iter()
+        .filter(|w| user.is_admin || w.member_role(&user.id).is_some())
+        .map(|w| w.info(&user, &data.users))
+        .collect();
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(list),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// 作成したユーザーがオーナーになる
+pub async fn create_workspace(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    body: web::Json<CreateWorkspaceRequest>,
+) -> HttpResponse {
+    let req = body.into_inner();
+    if let Err(errors) = req.validate() {
+        return HttpResponse::BadRequest().json(ApiResponse::<Workspace> {
+            success: false,
+            data: None,
+            errors: Some(errors),
+        });
+    }
+
+    let workspace = StoredWorkspace {
+        id: Uuid::new_v4().to_string(),
+        name: req.name.trim().to_string(),
+        members: vec![StoredMember {
+            user_id: user.id.clone(),
+            role: WorkspaceRole::Owner,
+        }],
+        created_at: now_unix(),
+    };
+    let mut workspaces = data.workspaces.lock().unwrap();
+    workspaces.workspaces.push(workspace.clone());
+    if let Err(e) = persistence::save_json(&data.workspaces_path, &*workspaces) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Created().json(ApiResponse {
+        success: true,
+        data: Some(workspace.info(&user, &data.users)),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// ワークスペースを削除する（オーナーのみ）。タイマーが残っている間は削除できない
+pub async fn delete_workspace(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let config = data.config.lock().unwrap();
+    let mut workspaces = data.workspaces.lock().unwrap();
+    match workspaces.role_in(&user, &id) {
+        None => return workspace_not_found(),
+        Some(role) if !role.can_manage() => return permission_denied(),
+        Some(_) => {}
+    }
+    if config.timers.iter().any(|t| t.workspace_id.as_deref() == Some(id.as_str())) {
+        return bad_request("id", ErrorCode::WorkspaceNotEmpty);
+    }
+    drop(config);
+
+    workspaces.workspaces.retain(|w| w.id != id);
+    if let Err(e) = persistence::save_json(&data.workspaces_path, &*workspaces) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
+
+/// メンバーを追加する。既にメンバーなら役割を変える（オーナーのみ）
+pub async fn set_member(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<String>,
+    body: web::Json<SetMemberRequest>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let req = body.into_inner();
+    let mut workspaces = data.workspaces.lock().unwrap();
+    match workspaces.role_in(&user, &id) {
+        None => return workspace_not_found(),
+        Some(role) if !role.can_manage() => return permission_denied(),
+        Some(_) => {}
+    }
+    // 役割を確かめてから引く。先に引くと権限のない人にユーザー名の有無が漏れる
+    let Some(member) = data.users.find_by_name(req.username.trim()) else {
+        return HttpResponse::NotFound().json(ApiResponse::<Workspace> {
+            success: false,
+            data: None,
+            errors: Some(vec![ValidationError::new(
+                "username",
+                ErrorCode::NotFound { resource: Resource::User },
+            )]),
+        });
+    };
+    let Some(workspace) = workspaces.workspaces.iter_mut().find(|w| w.id == id) else {
+        return workspace_not_found();
+    };
+    let owner_count = workspace.owner_count();
+    match workspace.members.iter_mut().find(|m| m.user_id == member.id) {
+        Some(existing) => {
+            if existing.role == WorkspaceRole::Owner && req.role != WorkspaceRole::Owner && owner_count == 1 {
+                return bad_request("role", ErrorCode::LastOwner);
+            }
+            existing.role = req.role;
+        }
+        None => workspace.members.push(StoredMember {
+            user_id: member.id,
+            role: req.role,
+        }),
+    }
+    let info = workspace.info(&user, &data.users);
+    if let Err(e) = persistence::save_json(&data.workspaces_path, &*workspaces) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        data: Some(info),
+        errors: None::<Vec<ValidationError>>,
+    })
+}
+
+/// メンバーを外す（オーナー、または自分が抜けるとき）。最後のオーナーは外せない
+pub async fn remove_member(
+    data: web::Data<AppState>,
+    user: web::ReqData<UserInfo>,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let (id, user_id) = path.into_inner();
+    let mut workspaces = data.workspaces.lock().unwrap();
+    match workspaces.role_in(&user, &id) {
+        None => return workspace_not_found(),
+        Some(role) if !role.can_manage() && user_id != user.id => return permission_denied(),
+        Some(_) => {}
+    }
+    let Some(workspace) = workspaces.workspaces.iter_mut().find(|w| w.id == id) else {
+        return workspace_not_found();
+    };
+    let Some(role) = workspace.member_role(&user_id) else {
+        return HttpResponse::NotFound().json(ApiResponse::<()> {
+            success: false,
+            data: None,
+            errors: Some(vec![ValidationError::new(
+                "user_id",
+                ErrorCode::NotFound { resource: Resource::Member },
+            )]),
+        });
+    };
+    if role == WorkspaceRole::Owner && workspace.owner_count() == 1 {
+        return bad_request("user_id", ErrorCode::LastOwner);
+    }
+    workspace.members.retain(|m| m.user_id != user_id);
+    if let Err(e) = persistence::save_json(&data.workspaces_path, &*workspaces) {
+        return save_failed(e);
+    }
+
+    HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        data: None,
+        errors: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+
+    use super::*;
+    use crate::test_support::{request_as, state, user};
+
+    #[actix_web::test]
+    async fn set_member_checks_role_before_looking_up_username() {
+        let data = state();
+        data.workspaces.lock().unwrap().workspaces.push(StoredWorkspace {
+            id: "w1".into(),
+            name: "w1".into(),
+            members: vec![
+                StoredMember { user_id: "alice".into(), role: WorkspaceRole::Owner },
+                StoredMember { user_id: "bob".into(), role: WorkspaceRole::Viewer },
+            ],
+            created_at: 0,
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(data.clone())
+                .route("/api/workspaces/{id}/members", web::put().to(set_member)),
+        )
+        .await;
+
+        let set = |who, username: &str| {
+            let req = test::TestRequest::put()
+                .uri("/api/workspaces/w1/members")
+                .set_json(SetMemberRequest {
+                    username: username.into(),
+                    role: WorkspaceRole::Editor,
+                })
+                .to_request();
+            test::call_service(&app, request_as(req, who))
+        };
+        // 管理できない人には、名前があってもなくても同じ答えを返す
+        let bob = user("bob");
+        assert_eq!(set(&bob, "nobody").await.status(), 403);
+        let alice = user("alice");
+        assert_eq!(set(&alice, "nobody").await.status(), 404);
+    }
+}
@@ -0,0 +1,291 @@
+//! 実行履歴: 実行セッションの完了・中止を記録し、日・週・タイマーごとに集計する。
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use actix_web::web;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use nekotimer_shared::{
+    PeriodRunStats, RunEvent, RunOutcome, RunRecord, RunStats, RunUpdate, TimerRunStats,
+};
+use tokio::sync::mpsc;
+
+use crate::persistence;
+use crate::runs::now_unix;
+use crate::AppState;
+
+/// 完了・中止イベントを含む更新から実行記録を作る
+fn record_from_update(update: &RunUpdate) -> Option<RunRecord> {
+    let outcome = update.events.iter().find_map(|e| match e {
+        RunEvent::Completed => Some(RunOutcome::Completed),
+        RunEvent::Aborted { .. } => Some(RunOutcome::Aborted),
+        _ => None,
+    })?;
+    let session = &update.session;
+    let aborted = outcome == RunOutcome::Aborted;
+    Some(RunRecord {
+        id: session.id.clone(),
+        timer_id: session.timer.id.clone(),
+        timer: session.timer.clone(),
+        started_at: session.started_at,
+        ended_at: now_unix(),
+        outcome,
+        aborted_block_ix: aborted.then_some(session.progress.block_ix),
+        aborted_countdown_run: if aborted { session.progress.countdown_run } else { None },
+        active_secs: session.progress.elapsed_secs,
+    })
+}
+
+/// 実行セッションの更新を監視し、終了したものを履歴ファイルに追記し続ける
+pub async fn record_runs(data: web::Data<AppState>, mut updates: mpsc::UnboundedReceiver<RunUpdate>) {
+    while let Some(update) = updates.recv().await {
+        let Some(record) = record_from_update(&update) else {
+            continue;
+        };
+        let history = {
+            let mut history = data.history.lock().unwrap();
+            history.runs.push(record);
+            history.clone()
+        };
+        // ファイルの書き込みで非同期の処理を止めないよう、別スレッドで保存する
+        let path = data.history_path.clone();
+        let saved = tokio::task::spawn_blocking(move || {
+            persistence::save_json(&path, &history).map_err(|e| e.to_string())
+        })
+        .await;
+        if let Err(e) = saved.map_err(|e| e.to_string()).and_then(|r| r) {
+            log::error!("実行履歴の保存に失敗しました: {}", e);
+        }
+    }
+}
+
+fn local_date(ts: u64, tz: &Tz) -> NaiveDate {
+    DateTime::<Utc>::from_timestamp(ts as i64, 0)
+        .unwrap_or_default()
+        .with_timezone(tz)
+        .date_naive()
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn add_to_period(map: &mut BTreeMap<NaiveDate, PeriodRunStats>, key: NaiveDate, record: &RunRecord) {
+    let entry = map.entry(key).or_insert_with(|| PeriodRunStats {
+        start: key.format("%Y-%m-%d").to_string(),
+        runs: 0,
+        completed: 0,
+        total_active_secs: 0,
+    });
+    entry.runs += 1;
+    if record.outcome == RunOutcome::Completed {
+        entry.completed += 1;
+    }
+    entry.total_active_secs += record.active_secs;
+}
+
+/// date から遡って連続している日数
+fn streak_ending_at(days: &BTreeSet<NaiveDate>, date: NaiveDate) -> u32 {
+    let mut count = 0;
+    let mut day = date;
+    while days.contains(&day) {
+        count += 1;
+        day -= Duration::days(1);
+    }
+    count
+}
+
+/// 記録を集計する。日付の区切りは tz のローカル時刻で判定する。
+pub fn compute_stats<'a>(records: impl IntoIterator<Item = &'a RunRecord>, tz: &Tz) -> RunStats {
+    let mut stats = RunStats::default();
+    let mut per_timer: BTreeMap<String, TimerRunStats> = BTreeMap::new();
+    let mut per_day = BTreeMap::new();
+    let mut per_week = BTreeMap::new();
+    let mut completed_days = BTreeSet::new();
+
+    for record in records {
+        let completed = record.outcome == RunOutcome::Completed;
+        stats.total_runs += 1;
+        if completed {
+            stats.completed_runs += 1;
+        } else {
+            stats.aborted_runs += 1;
+        }
+        stats.total_active_secs += record.active_secs;
+
+        let timer = per_timer
+            .entry(record.timer_id.clone())
+            .or_insert_with(|| TimerRunStats {
+                timer_id: record.timer_id.clone(),
+                timer_name: record.timer.name.clone(),
+                runs: 0,
+                completed: 0,
+                aborted: 0,
+                total_active_secs: 0,
+                last_run_at: 0,
+            });
+        timer.runs += 1;
+        if completed {
+            timer.completed += 1;
+        } else {
+            timer.aborted += 1;
+        }
+        timer.total_active_secs += record.active_secs;
+        if record.started_at >= timer.last_run_at {
+            timer.last_run_at = record.started_at;
+            timer.timer_name = record.timer.name.clone();
+        }
+
+        let date = local_date(record.started_at, tz);
+        add_to_period(&mut per_day, date, record);
+        add_to_period(&mut per_week, week_start(date), record);
+        if completed {
+            completed_days.insert(date);
+        }
+    }
+
+    let mut longest = 0;
+    let mut current_run = 0;
+    let mut prev: Option<NaiveDate> = None;
+    for day in &completed_days {
+        current_run = match prev {
+            Some(p) if *day - p == Duration::days(1) => current_run + 1,
+            _ => 1,
+        };
+        longest = longest.max(current_run);
+        prev = Some(*day);
+    }
+    let today = local_date(now_unix(), tz);
+    stats.longest_streak_days = longest;
+    stats.current_streak_days = match streak_ending_at(&completed_days, today) {
+        0 => streak_ending_at(&completed_days, today - Duration::days(1)),
+        n => n,
+    };
+
+    stats.per_timer = per_timer.into_values().collect();
+    stats.per_timer.sort_by_key(|t| std::cmp::Reverse(t.last_run_at));
+    stats.per_day = per_day.into_values().collect();
+    stats.per_week = per_week.into_values().collect();
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use chrono_tz::{America::New_York, Asia::Tokyo, UTC};
+
+    use super::*;
+    use crate::test_support::timer;
+
+    /// tz のローカル時刻の Unix 秒
+    fn at(tz: &Tz, y: i32, m: u32, d: u32, h: u32, min: u32) -> u64 {
+        tz.with_ymd_and_hms(y, m, d, h, min, 0).single().unwrap().timestamp() as u64
+    }
+
+    fn record(timer_id: &str, started_at: u64, outcome: RunOutcome) -> RunRecord {
+        RunRecord {
+            id: format!("run-{started_at}"),
+            timer_id: timer_id.into(),
+            timer: timer(timer_id, "alice"),
+            started_at,
+            ended_at: started_at + 60,
+            aborted_block_ix: (outcome == RunOutcome::Aborted).then_some(0),
+            outcome,
+            aborted_countdown_run: None,
+            active_secs: 60,
+        }
+    }
+
+    fn starts(periods: &[PeriodRunStats]) -> Vec<&str> {
+        periods.iter().map(|p| p.start.as_str()).collect()
+    }
+
+    #[test]
+    fn totals_split_completed_and_aborted() {
+        let records = [
+            record("t1", at(&UTC, 2024, 5, 1, 9, 0), RunOutcome::Completed),
+            record("t1", at(&UTC, 2024, 5, 1, 10, 0), RunOutcome::Aborted),
+            record("t2", at(&UTC, 2024, 5, 2, 9, 0), RunOutcome::Completed),
+        ];
+        let stats = compute_stats(&records, &UTC);
+        assert_eq!((stats.total_runs, stats.completed_runs, stats.aborted_runs), (3, 2, 1));
+        assert_eq!(stats.total_active_secs, 180);
+
+        // 最後に実行したタイマーが先頭
+        let per_timer: Vec<_> = stats
+            .per_timer
+            .iter()
+            .map(|t| (t.timer_id.as_str(), t.runs, t.completed, t.aborted))
+            .collect();
+        assert_eq!(per_timer, [("t2", 1, 1, 0), ("t1", 2, 1, 1)]);
+        assert_eq!(stats.per_day[0].runs, 2);
+        assert_eq!(stats.per_day[0].completed, 1);
+    }
+
+    #[test]
+    fn longest_streak_counts_only_completed_days() {
+        let records = [
+            // 5/1〜5/3 の3日連続
+            record("t1", at(&UTC, 2024, 5, 1, 9, 0), RunOutcome::Completed),
+            record("t1", at(&UTC, 2024, 5, 2, 9, 0), RunOutcome::Completed),
+            record("t1", at(&UTC, 2024, 5, 2, 20, 0), RunOutcome::Completed),
+            record("t1", at(&UTC, 2024, 5, 3, 9, 0), RunOutcome::Completed),
+            // 5/4 は中止だけなので途切れる
+            record("t1", at(&UTC, 2024, 5, 4, 9, 0), RunOutcome::Aborted),
+            record("t1", at(&UTC, 2024, 5, 5, 9, 0), RunOutcome::Completed),
+            record("t1", at(&UTC, 2024, 5, 6, 9, 0), RunOutcome::Completed),
+        ];
+        let stats = compute_stats(&records, &UTC);
+        assert_eq!(stats.longest_streak_days, 3);
+        assert_eq!(stats.current_streak_days, 0);
+    }
+
+    #[test]
+    fn current_streak_continues_through_yesterday() {
+        let now = now_unix();
+        let day = 24 * 60 * 60;
+        let records = [
+            record("t1", now - 3 * day, RunOutcome::Completed),
+            record("t1", now - 2 * day, RunOutcome::Completed),
+            record("t1", now - day, RunOutcome::Completed),
+        ];
+        // 今日はまだ実行していなくても昨日までの連続を数える
+        assert_eq!(compute_stats(&records, &UTC).current_streak_days, 3);
+
+        let mut records = records.to_vec();
+        records.push(record("t1", now, RunOutcome::Aborted));
+        assert_eq!(compute_stats(&records, &UTC).current_streak_days, 3);
+        records.push(record("t1", now, RunOutcome::Completed));
+        assert_eq!(compute_stats(&records, &UTC).current_streak_days, 4);
+    }
+
+    #[test]
+    fn days_and_weeks_follow_the_local_timezone() {
+        // UTC では日曜の夜、東京では月曜の朝
+        let records = [record("t1", at(&UTC, 2024, 5, 5, 23, 30), RunOutcome::Completed)];
+        let utc = compute_stats(&records, &UTC);
+        assert_eq!(starts(&utc.per_day), ["2024-05-05"]);
+        assert_eq!(starts(&utc.per_week), ["2024-04-29"]);
+        let tokyo = compute_stats(&records, &Tokyo);
+        assert_eq!(starts(&tokyo.per_day), ["2024-05-06"]);
+        assert_eq!(starts(&tokyo.per_week), ["2024-05-06"]);
+    }
+
+    #[test]
+    fn weeks_stay_intact_across_dst_changes() {
+        let records = [
+            // 夏時間の始まり（3/10 日曜）の前後。週は 3/4 からの週にまとまる
+            record("t1", at(&New_York, 2024, 3, 9, 23, 30), RunOutcome::Completed),
+            record("t1", at(&New_York, 2024, 3, 10, 1, 30), RunOutcome::Completed),
+            record("t1", at(&New_York, 2024, 3, 10, 23, 30), RunOutcome::Completed),
+            record("t1", at(&New_York, 2024, 3, 11, 0, 30), RunOutcome::Completed),
+            // 夏時間の終わり（11/3 日曜）の夜は UTC では翌日になる
+            record("t1", at(&New_York, 2024, 11, 3, 23, 30), RunOutcome::Completed),
+        ];
+        let stats = compute_stats(&records, &New_York);
+        assert_eq!(starts(&stats.per_day), ["2024-03-09", "2024-03-10", "2024-03-11", "2024-11-03"]);
+        assert_eq!(starts(&stats.per_week), ["2024-03-04", "2024-03-11", "2024-10-28"]);
+        assert_eq!(stats.per_week.iter().map(|w| w.runs).collect::<Vec<_>>(), [3, 1, 1]);
+        assert_eq!(stats.longest_streak_days, 3);
+    }
+}
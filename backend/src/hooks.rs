@@ -0,0 +1,348 @@
+//! コマンドフック: 実行セッションのイベントでサーバー上のコマンドを起動する。
+//! 任意のコマンドを実行できるため、定義は API ではなく設定ファイル横の
+//! `<stem>.hooks.json` に書き、起動時に読み込む。
+//! フックごとに実行タスクを1つ持ち、イベントの順に1件ずつ実行する。
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use actix_web::web;
+use nekotimer_shared::{
+    CommandHook, CommandHookExecution, CommandHookFile, RunUpdate, WebhookPayload,
+};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::persistence;
+use crate::runs::now_unix;
+use crate::webhooks::payloads_for;
+use crate::AppState;
+
+/// 実行ログに残す件数
+pub const EXECUTION_LOG_CAPACITY: usize = 200;
+/// ログに残す標準出力・標準エラーの上限（バイト）
+const OUTPUT_LIMIT: usize = 4096;
+
+type Job = (CommandHook, WebhookPayload);
+
+/// フック定義を読み込む。ファイルがなければ空、不正な定義は警告して読み飛ばす。
+pub fn load_hooks(path: &str) -> CommandHookFile {
+    let file: CommandHookFile = match persistence::load_json(path) {
+        Ok(file) => file,
+        Err(e) => {
+            if std::path::Path::new(path).exists() {
+                log::error!("コマンドフックの読み込みに失敗しました ({}): {}", path, e);
+            }
+            return CommandHookFile::default();
+        }
+    };
+    let hooks = file
+        .hooks
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, mut hook)| {
+            if hook.id.is_empty() {
+                hook.id = format!("hook-{}", i + 1);
+            }
+            match hook.validate() {
+                Ok(()) => Some(hook),
+                Err(errors) => {
+                    let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+                    log::warn!("コマンドフック {} を無視します: {}", hook.id, messages.join(", "));
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    if !hooks.is_empty() {
+        log::info!("コマンドフックを {} 件読み込みました ({})", hooks.len(), path);
+    }
+    CommandHookFile { hooks }
+}
+
+fn event_name(payload: &WebhookPayload) -> String {
+    serde_json::to_value(payload.event)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// テンプレート変数の値
+fn variables(payload: &WebhookPayload) -> Vec<(&'static str, String)> {
+    let opt = |v: Option<String>| v.unwrap_or_default();
+    vec![
+        ("event", event_name(payload)),
+        ("session_id", payload.session_id.clone()),
+        ("timer_id", payload.timer_id.clone()),
+        ("timer_name", payload.timer_name.clone()),
+        ("block_index", opt(payload.block_index.map(|v| v.to_string()))),
+        ("block_name", opt(payload.block_name.clone())),
+        ("repetition", opt(payload.repetition.map(|v| v.to_string()))),
+        ("repetitions", opt(payload.repetitions.map(|v| v.to_string()))),
+    ]
+}
+
+/// `{name}` を変数の値に置き換える。置き換えた値の中の `{…}` はそのまま残す（タイマー名などに
+/// 書かれた `{session_id}` が展開されないよう、テンプレートを1回だけ走査する）。知らない名前も残す
+fn render(template: &str, vars: &[(&'static str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let token = &rest[start..];
+        let value = token.find('}').and_then(|end| {
+            let name = &token[1..end];
+            vars.iter().find(|(n, _)| *n == name).map(|(_, value)| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &token[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = &token[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn truncate_output(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if text.len() <= OUTPUT_LIMIT {
+        return text.into_owned();
+    }
+    let mut end = OUTPUT_LIMIT;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &text[..end])
+}
+
+/// コマンドを1回実行する。タイムアウトしたらプロセスを終了させる。
+pub async fn execute(hook: &CommandHook, payload: &WebhookPayload) -> CommandHookExecution {
+    let vars = variables(payload);
+    let args: Vec<String> = hook.args.iter().map(|a| render(a, &vars)).collect();
+    let mut execution = CommandHookExecution {
+        id: Uuid::new_v4().to_string(),
+        hook_id: hook.id.clone(),
+        event: payload.event,
+        command_line: std::iter::once(hook.command.clone())
+            .chain(args.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" "),
+        started_at: now_unix(),
+        duration_ms: 0,
+        exit_code: None,
+        timed_out: false,
+        stdout: String::new(),
+        stderr: String::new(),
+        error: None,
+    };
+
+    let mut command = Command::new(&hook.command);
+    command
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    for (name, value) in &vars {
+        command.env(format!("NEKOTIMER_{}", name.to_uppercase()), value);
+    }
+
+    let started = Instant::now();
+    let child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            execution.error = Some(format!("起動できませんでした: {}", e));
+            return execution;
+        }
+    };
+    let timeout = Duration::from_secs(hook.timeout_secs as u64);
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(Ok(output)) => {
+            execution.exit_code = output.status.code();
+            execution.stdout = truncate_output(&output.stdout);
+            execution.stderr = truncate_output(&output.stderr);
+        }
+        Ok(Err(e)) => execution.error = Some(e.to_string()),
+        // wait_with_output の future ごと子プロセスが破棄され、kill_on_drop で終了する
+        Err(_) => execution.timed_out = true,
+    }
+    execution.duration_ms = started.elapsed().as_millis() as u64;
+    execution
+}
+
+fn log_execution(data: &AppState, execution: CommandHookExecution) {
+    let mut log = data.hook_executions.lock().unwrap();
+    if log.len() >= EXECUTION_LOG_CAPACITY {
+        log.pop_front();
+    }
+    log.push_back(execution);
+}
+
+async fn hook_worker(data: web::Data<AppState>, mut jobs: mpsc::UnboundedReceiver<Job>) {
+    while let Some((hook, payload)) = jobs.recv().await {
+        let execution = execute(&hook, &payload).await;
+        if !execution.success() {
+            log::warn!(
+                "コマンドフックが失敗しました ({}): {}",
+                execution.command_line,
+                match (&execution.error, execution.timed_out) {
+                    (Some(e), _) => e.clone(),
+                    (None, true) => "タイムアウト".to_string(),
+                    (None, false) => format!("終了コード {:?}", execution.exit_code),
+                }
+            );
+        }
+        log_execution(&data, execution);
+    }
+}
+
+/// 実行セッションの更新を監視し、該当するフックの実行タスクに振り分け続ける
+pub async fn run_hooks(data: web::Data<AppState>, mut updates: mpsc::UnboundedReceiver<RunUpdate>) {
+    let mut workers: HashMap<String, mpsc::UnboundedSender<Job>> = HashMap::new();
+
+    while let Some(update) = updates.recv().await {
+        let payloads = payloads_for(&update);
+        if payloads.is_empty() {
+            continue;
+        }
+
+        let hooks = data.hooks.lock().unwrap().hooks.clone();
+        for payload in payloads {
+            for hook in hooks
+                .iter()
+                .filter(|h| h.accepts(payload.event, &update.session.timer.id))
+            {
+                let sender = workers.entry(hook.id.clone()).or_insert_with(|| {
+                    let (tx, rx) = mpsc::unbounded_channel();
+                    tokio::spawn(hook_worker(data.clone(), rx));
+                    tx
+                });
+                let _ = sender.send((hook.clone(), payload.clone()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nekotimer_shared::WebhookEvent;
+
+    use super::*;
+
+    fn vars() -> Vec<(&'static str, String)> {
+        vec![
+            ("timer_name", "{session_id}".to_string()),
+            ("session_id", "s1".to_string()),
+        ]
+    }
+
+    #[test]
+    fn render_replaces_known_variables() {
+        assert_eq!(render("--session={session_id}", &vars()), "--session=s1");
+        assert_eq!(render("{session_id}{session_id}", &vars()), "s1s1");
+    }
+
+    #[test]
+    fn render_does_not_expand_substituted_values() {
+        assert_eq!(render("{timer_name}:{session_id}", &vars()), "{session_id}:s1");
+    }
+
+    #[test]
+    fn render_keeps_unknown_and_unclosed_braces() {
+        assert_eq!(render("{unknown} {{session_id}} {", &vars()), "{unknown} {s1} {");
+    }
+
+    fn sh(script: &str, timeout_secs: u32) -> CommandHook {
+        CommandHook {
+            id: "hook-1".into(),
+            event: WebhookEvent::Completed,
+            timer_id: None,
+            command: "sh".into(),
+            args: vec!["-c".into(), script.into()],
+            timeout_secs,
+            enabled: true,
+        }
+    }
+
+    fn payload() -> WebhookPayload {
+        WebhookPayload {
+            delivery_id: "d1".into(),
+            event: WebhookEvent::Completed,
+            timestamp: 0,
+            session_id: "s1".into(),
+            timer_id: "t1".into(),
+            timer_name: "作業".into(),
+            block_index: None,
+            block_name: None,
+            repetition: None,
+            repetitions: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_captures_stdout_and_stderr() {
+        let hook = sh("echo \"$NEKOTIMER_TIMER_NAME {session_id}\"; echo oops >&2", 5);
+        let execution = execute(&hook, &payload()).await;
+        assert!(execution.success());
+        assert!(!execution.timed_out);
+        assert_eq!(execution.stdout, "作業 s1\n");
+        assert_eq!(execution.stderr, "oops\n");
+        assert_eq!(execution.error, None);
+    }
+
+    #[tokio::test]
+    async fn execute_truncates_long_output() {
+        let hook = sh("head -c 10000 /dev/zero | tr '\\0' a", 5);
+        let execution = execute(&hook, &payload()).await;
+        assert!(execution.success());
+        assert_eq!(execution.stdout, format!("{}…", "a".repeat(OUTPUT_LIMIT)));
+    }
+
+    #[test]
+    fn truncate_output_keeps_char_boundaries() {
+        let text = "あ".repeat(OUTPUT_LIMIT);
+        let truncated = truncate_output(text.as_bytes());
+        assert!(truncated.len() <= OUTPUT_LIMIT + "…".len());
+        assert!(truncated.trim_end_matches('…').chars().all(|c| c == 'あ'));
+    }
+
+    #[tokio::test]
+    async fn execute_reports_non_zero_exit() {
+        let execution = execute(&sh("echo failed >&2; exit 3", 5), &payload()).await;
+        assert!(!execution.success());
+        assert_eq!(execution.exit_code, Some(3));
+        assert!(!execution.timed_out);
+        assert_eq!(execution.stderr, "failed\n");
+    }
+
+    #[tokio::test]
+    async fn execute_reports_spawn_failure() {
+        let mut hook = sh("", 5);
+        hook.command = "/nonexistent/nekotimer-hook".into();
+        let execution = execute(&hook, &payload()).await;
+        assert!(!execution.success());
+        assert!(execution.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn execute_kills_command_on_timeout() {
+        let marker = std::env::temp_dir().join(format!("nekotimer-hook-{}", Uuid::new_v4()));
+        let hook = sh(&format!("sleep 2; touch {}", marker.display()), 1);
+        let execution = execute(&hook, &payload()).await;
+        assert!(execution.timed_out);
+        assert_eq!(execution.exit_code, None);
+        assert!(execution.duration_ms < 2000);
+
+        // 終了させられていれば、sleep の後の touch は実行されない
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert!(!marker.exists());
+    }
+}
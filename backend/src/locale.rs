@@ -0,0 +1,50 @@
+//! エラーメッセージの言語。
+//! handlers は既定（日本語）の message で ValidationError を返し、ここで Accept-Language を見て
+//! 日本語以外の言語ならレスポンスの errors の message を code から作り直す。
+
+use actix_web::body::{self, EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header;
+use actix_web::middleware::Next;
+use actix_web::web::Bytes;
+use nekotimer_shared::{ApiResponse, Locale};
+
+/// Accept-Language から選んだ言語。ヘッダーがないか対応言語がなければ日本語
+fn request_locale(req: &ServiceRequest) -> Locale {
+    req.headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(Locale::from_accept_language)
+        .unwrap_or_default()
+}
+
+pub async fn localize_errors(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody, Bytes>>, actix_web::Error> {
+    let locale = request_locale(&req);
+    let res = next.call(req).await?;
+    let is_json = res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if locale == Locale::Ja || res.status().is_success() || !is_json {
+        return Ok(res.map_into_left_body());
+    }
+
+    let (req, res) = res.into_parts();
+    let (res, body) = res.into_parts();
+    let bytes = body::to_bytes(body).await.map_err(Into::into)?;
+    let bytes = match serde_json::from_slice::<ApiResponse<serde_json::Value>>(&bytes) {
+        Ok(mut parsed) => {
+            for e in parsed.errors.iter_mut().flatten() {
+                e.message = e.render(locale);
+            }
+            serde_json::to_vec(&parsed).map_or(bytes, Into::into)
+        }
+        Err(_) => bytes,
+    };
+    let res = res.set_body(bytes);
+    Ok(ServiceResponse::new(req, res).map_into_right_body())
+}
@@ -1,41 +1,249 @@
-use actix_cors::Cors;
+use actix_web::middleware::{self, from_fn};
 use actix_web::{web, App, HttpServer};
+use std::collections::VecDeque;
 use std::sync::Mutex;
 
+mod auth;
+mod cors;
 mod handlers;
+mod history;
+mod hooks;
+mod locale;
 mod persistence;
+mod runs;
+mod scheduler;
+mod settings;
+mod static_files;
+#[cfg(test)]
+mod test_support;
+mod tokens;
+mod webhooks;
+mod workspaces;
 
 pub struct AppState {
     pub config: Mutex<nekotimer_shared::TimerConfigFile>,
     pub config_path: String,
+    pub runs: runs::RunManager,
+    pub history: Mutex<nekotimer_shared::RunHistoryFile>,
+    pub history_path: String,
+    pub schedules: Mutex<nekotimer_shared::ScheduleFile>,
+    pub schedules_path: String,
+    pub webhooks: Mutex<nekotimer_shared::WebhookFile>,
+    pub webhooks_path: String,
+    pub webhook_deliveries: Mutex<VecDeque<nekotimer_shared::WebhookDelivery>>,
+    pub hooks: Mutex<nekotimer_shared::CommandHookFile>,
+    pub hook_executions: Mutex<VecDeque<nekotimer_shared::CommandHookExecution>>,
+    /// フロントエンドの配信元。None なら API のみ
+    pub static_files: Option<static_files::StaticSource>,
+    pub users: auth::UserStore,
+    /// ログインの失敗回数（再起動で忘れる）
+    pub login_throttle: auth::LoginThrottle,
+    pub sessions: Mutex<auth::SessionFile>,
+    pub sessions_path: String,
+    pub api_tokens: Mutex<tokens::ApiTokenFile>,
+    pub api_tokens_path: String,
+    pub shares: Mutex<nekotimer_shared::ShareLinkFile>,
+    pub shares_path: String,
+    pub workspaces: Mutex<workspaces::WorkspaceFile>,
+    pub workspaces_path: String,
+    pub sound_profiles: Mutex<nekotimer_shared::SoundProfileFile>,
+    pub sound_profiles_path: String,
+    pub custom_sounds: Mutex<nekotimer_shared::CustomSoundFile>,
+    pub custom_sounds_path: String,
+    /// アップロードした効果音のファイルを置くディレクトリ
+    pub sounds_dir: std::path::PathBuf,
+    /// タイマーの検証ルール（設定ファイルの [validation]）
+    pub validation: nekotimer_shared::ValidationPolicy,
 }
 
-const DEFAULT_CONFIG_PATH: &str = "timer.config";
+impl AppState {
+    /// 設定ファイルと付属データを読み込む（ないものは空で始める）
+    fn load(
+        config_path: String,
+        static_files: Option<static_files::StaticSource>,
+        validation: nekotimer_shared::ValidationPolicy,
+    ) -> Self {
+        let config = persistence::load_config(&config_path).unwrap_or_default();
+        let history_path = persistence::sibling_path(&config_path, "history");
+        let history = persistence::load_json(&history_path).unwrap_or_default();
+        let schedules_path = persistence::sibling_path(&config_path, "schedules");
+        let schedules = persistence::load_json(&schedules_path).unwrap_or_default();
+        let webhooks_path = persistence::sibling_path(&config_path, "webhooks");
+        let webhooks = persistence::load_json(&webhooks_path).unwrap_or_default();
+        let hooks = hooks::load_hooks(&persistence::sibling_path(&config_path, "hooks"));
+        let users = auth::UserStore::new(persistence::sibling_path(&config_path, "users"));
+        let sessions_path = persistence::sibling_path(&config_path, "sessions");
+        let sessions = persistence::load_json(&sessions_path).unwrap_or_default();
+        let api_tokens_path = persistence::sibling_path(&config_path, "tokens");
+        let api_tokens = persistence::load_json(&api_tokens_path).unwrap_or_default();
+        let shares_path = persistence::sibling_path(&config_path, "shares");
+        let shares = persistence::load_json(&shares_path).unwrap_or_default();
+        let workspaces_path = persistence::sibling_path(&config_path, "workspaces");
+        let workspaces = persistence::load_json(&workspaces_path).unwrap_or_default();
+        let sound_profiles_path = persistence::sibling_path(&config_path, "sound_profiles");
+        let sound_profiles = persistence::load_json(&sound_profiles_path).unwrap_or_default();
+        let custom_sounds_path = persistence::sibling_path(&config_path, "custom_sounds");
+        let custom_sounds = persistence::load_json(&custom_sounds_path).unwrap_or_default();
+        let sounds_dir = persistence::sibling_dir(&config_path, "sounds");
+
+        AppState {
+            config: Mutex::new(config),
+            config_path,
+            runs: runs::RunManager::new(),
+            history: Mutex::new(history),
+            history_path,
+            schedules: Mutex::new(schedules),
+            schedules_path,
+            webhooks: Mutex::new(webhooks),
+            webhooks_path,
+            webhook_deliveries: Mutex::new(VecDeque::with_capacity(webhooks::DELIVERY_LOG_CAPACITY)),
+            hooks: Mutex::new(hooks),
+            hook_executions: Mutex::new(VecDeque::with_capacity(hooks::EXECUTION_LOG_CAPACITY)),
+            static_files,
+            users,
+            login_throttle: auth::LoginThrottle::default(),
+            sessions: Mutex::new(sessions),
+            sessions_path,
+            api_tokens: Mutex::new(api_tokens),
+            api_tokens_path,
+            shares: Mutex::new(shares),
+            shares_path,
+            workspaces: Mutex::new(workspaces),
+            workspaces_path,
+            sound_profiles: Mutex::new(sound_profiles),
+            sound_profiles_path,
+            custom_sounds: Mutex::new(custom_sounds),
+            custom_sounds_path,
+            sounds_dir,
+            validation,
+        }
+    }
+}
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let config_path = std::env::var("TIMER_CONFIG_PATH").unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string());
-    let config = persistence::load_config(&config_path).unwrap_or_default();
+    let settings = match settings::Settings::load() {
+        Ok(settings::Loaded::Run(settings)) => settings,
+        Ok(settings::Loaded::Print(settings)) => {
+            print!("{}", settings.to_toml());
+            return Ok(());
+        }
+        Ok(settings::Loaded::Admin(settings, command)) => {
+            if let Err(e) = auth::run_admin(&settings.config_path, command) {
+                eprintln!("エラー: {}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Err(errors) => {
+            for error in errors {
+                eprintln!("設定エラー: {}", error);
+            }
+            std::process::exit(2);
+        }
+    };
+    env_logger::Builder::new().parse_filters(&settings.log_level).init();
 
-    println!("nekotimer backend starting on http://127.0.0.1:14990");
+    let static_files = static_files::StaticSource::resolve(settings.static_dir.clone());
+    let addrs = settings.socket_addrs();
 
-    let data = web::Data::new(AppState {
-        config: Mutex::new(config),
-        config_path,
-    });
+    for addr in &addrs {
+        log::info!("nekotimer backend starting on http://{}", addr);
+    }
+    match &static_files {
+        Some(source) => log::info!("フロントエンドを配信します: {}", source.describe()),
+        None => log::info!("フロントエンドは配信しません（API のみ）"),
+    }
+    let cors_policy = settings.cors_policy();
+    log::info!("CORS: {}", cors_policy.describe());
 
-    HttpServer::new(move || {
-        let cors = Cors::permissive();
+    let data = web::Data::new(AppState::load(
+        settings.config_path.clone(),
+        static_files,
+        settings.validation.clone(),
+    ));
+    if data.users.is_empty() {
+        log::warn!("ユーザーがいません。nekotimer-backend user add <名前> --admin で作成してください");
+    }
+
+    tokio::spawn(history::record_runs(data.clone(), data.runs.consume()));
+    tokio::spawn(scheduler::run_scheduler(data.clone()));
+    tokio::spawn(webhooks::dispatch_webhooks(data.clone(), data.runs.consume()));
+    tokio::spawn(hooks::run_hooks(data.clone(), data.runs.consume()));
+
+    let mut server = HttpServer::new(move || {
         App::new()
-            .wrap(cors)
+            .wrap(from_fn(auth::require_auth))
+            .wrap(from_fn(locale::localize_errors))
+            .wrap(cors_policy.build())
+            .wrap(middleware::Logger::default())
             .app_data(data.clone())
+            .route("/api/auth/login", web::post().to(handlers::auth::login))
+            .route("/api/auth/logout", web::post().to(handlers::auth::logout))
+            .route("/api/auth/me", web::get().to(handlers::auth::me))
+            .route("/api/tokens", web::get().to(handlers::tokens::list_tokens))
+            .route("/api/tokens", web::post().to(handlers::tokens::create_token))
+            .route("/api/tokens/{id}", web::delete().to(handlers::tokens::revoke_token))
+            .route("/api/shares", web::get().to(handlers::shares::list_shares))
+            .route("/api/shares", web::post().to(handlers::shares::create_share))
+            .route("/api/shares/{id}", web::delete().to(handlers::shares::delete_share))
+            .route("/api/shared/{token}", web::get().to(handlers::shares::get_shared))
             .route("/api/timers", web::get().to(handlers::list_timers))
             .route("/api/timers", web::post().to(handlers::create_timer))
             .route("/api/timers/{id}", web::get().to(handlers::get_timer))
             .route("/api/timers/{id}", web::put().to(handlers::update_timer))
             .route("/api/timers/{id}", web::delete().to(handlers::delete_timer))
-    })
-    .bind("127.0.0.1:14990")?
-    .run()
-    .await
+            .route("/api/timers/{id}/transfer", web::post().to(handlers::transfer_timer))
+            .route("/api/validation-policy", web::get().to(handlers::validation_policy))
+            .route("/api/sound-profiles", web::get().to(handlers::sound_profiles::list_sound_profiles))
+            .route("/api/sound-profiles", web::post().to(handlers::sound_profiles::create_sound_profile))
+            .route(
+                "/api/sound-profiles/{id}",
+                web::put().to(handlers::sound_profiles::update_sound_profile),
+            )
+            .route(
+                "/api/sound-profiles/{id}",
+                web::delete().to(handlers::sound_profiles::delete_sound_profile),
+            )
+            .route("/api/sounds", web::get().to(handlers::sounds::list_sounds))
+            .route("/api/sounds", web::post().to(handlers::sounds::upload_sound))
+            .route("/api/sounds/{id}", web::put().to(handlers::sounds::rename_sound))
+            .route("/api/sounds/{id}", web::delete().to(handlers::sounds::delete_sound))
+            .route("/api/workspaces", web::get().to(handlers::workspaces::list_workspaces))
+            .route("/api/workspaces", web::post().to(handlers::workspaces::create_workspace))
+            .route("/api/workspaces/{id}", web::delete().to(handlers::workspaces::delete_workspace))
+            .route("/api/workspaces/{id}/members", web::put().to(handlers::workspaces::set_member))
+            .route(
+                "/api/workspaces/{id}/members/{user_id}",
+                web::delete().to(handlers::workspaces::remove_member),
+            )
+            .route("/api/runs", web::get().to(handlers::runs::list_runs))
+            .route("/api/runs", web::post().to(handlers::runs::start_run))
+            .route("/api/runs/events", web::get().to(handlers::runs::run_events))
+            .route("/api/runs/{id}", web::get().to(handlers::runs::get_run))
+            .route("/api/runs/{id}/pause", web::post().to(handlers::runs::pause_run))
+            .route("/api/runs/{id}/resume", web::post().to(handlers::runs::resume_run))
+            .route("/api/runs/{id}/skip", web::post().to(handlers::runs::skip_run))
+            .route("/api/runs/{id}/stop", web::post().to(handlers::runs::stop_run))
+            .route("/api/history", web::get().to(handlers::history::list_history))
+            .route("/api/history/stats", web::get().to(handlers::history::history_stats))
+            .route("/api/schedules", web::get().to(handlers::schedules::list_schedules))
+            .route("/api/schedules", web::post().to(handlers::schedules::create_schedule))
+            .route("/api/schedules/{id}", web::put().to(handlers::schedules::update_schedule))
+            .route("/api/schedules/{id}", web::delete().to(handlers::schedules::delete_schedule))
+            .route("/api/webhooks", web::get().to(handlers::webhooks::list_webhooks))
+            .route("/api/webhooks", web::post().to(handlers::webhooks::create_webhook))
+            .route("/api/webhooks/deliveries", web::get().to(handlers::webhooks::list_deliveries))
+            .route("/api/webhooks/{id}", web::put().to(handlers::webhooks::update_webhook))
+            .route("/api/webhooks/{id}", web::delete().to(handlers::webhooks::delete_webhook))
+            .route("/api/webhooks/{id}/test", web::post().to(handlers::webhooks::test_webhook))
+            .route("/api/hooks", web::get().to(handlers::hooks::list_hooks))
+            .route("/api/hooks/executions", web::get().to(handlers::hooks::list_executions))
+            .route("/sound/custom/{id}", web::get().to(handlers::sounds::serve_sound))
+            .default_service(web::to(static_files::serve))
+    });
+    for addr in addrs {
+        server = server.bind(addr)?;
+    }
+    server.run().await
 }
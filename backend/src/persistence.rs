@@ -1,14 +1,49 @@
 use nekotimer_shared::TimerConfigFile;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 pub fn load_config(path: &str) -> Result<TimerConfigFile, Box<dyn std::error::Error>> {
-    let contents = fs::read_to_string(path)?;
-    let config: TimerConfigFile = serde_json::from_str(&contents)?;
-    Ok(config)
+    load_json(path)
 }
 
 pub fn save_config(path: &str, config: &TimerConfigFile) -> Result<(), Box<dyn std::error::Error>> {
-    let json = serde_json::to_string_pretty(config)?;
+    save_json(path, config)
+}
+
+pub fn load_json<T: DeserializeOwned>(path: &str) -> Result<T, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)?;
+    let value: T = serde_json::from_str(&contents)?;
+    Ok(value)
+}
+
+pub fn save_json<T: Serialize>(path: &str, value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(value)?;
     fs::write(path, json)?;
     Ok(())
 }
+
+/// 設定ファイルと同じ場所に置く付属データのパス。
+/// 例: `/conf/nekotimer_neko32.config` + `history` → `/conf/nekotimer_neko32.history.json`
+pub fn sibling_path(config_path: &str, kind: &str) -> String {
+    let path = Path::new(config_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "timer".to_string());
+    path.with_file_name(format!("{}.{}.json", stem, kind))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// 設定ファイルと同じ場所に置く付属ディレクトリのパス。
+/// 例: `/conf/nekotimer_neko32.config` + `sounds` → `/conf/nekotimer_neko32.sounds`
+pub fn sibling_dir(config_path: &str, kind: &str) -> PathBuf {
+    let path = Path::new(config_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "timer".to_string());
+    path.with_file_name(format!("{}.{}", stem, kind))
+}
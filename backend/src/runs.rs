@@ -0,0 +1,219 @@
+//! サーバー側の実行セッション管理。
+//! セッションごとに RunEngine を1秒ごとに進めるタスクを起動し、状態変化を broadcast で配信する。
+//! 終了したセッションはしばらく残し（後から接続した画面にも「完了」を表示するため）、その後破棄する。
+//! 履歴・Webhook などサーバー内の処理は取りこぼすと困るので、broadcast ではなく
+//! 処理ごとの上限なしのチャンネルで受け取る。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use nekotimer_shared::{RunEngine, RunEvent, RunSession, RunUpdate, TimerConfig};
+use tokio::sync::{broadcast, mpsc, Notify};
+use tokio::time::{interval_at, sleep, Instant};
+use uuid::Uuid;
+
+const TICK: Duration = Duration::from_secs(1);
+/// 終了後にセッションを保持しておく時間
+const FINISHED_RETENTION: Duration = Duration::from_secs(60);
+const UPDATE_CHANNEL_CAPACITY: usize = 256;
+
+/// 実行中セッションへの操作
+#[derive(Clone, Copy, Debug)]
+pub enum RunCommand {
+    Pause,
+    Resume,
+    Skip,
+    Stop,
+}
+
+struct Session {
+    id: String,
+    started_at: u64,
+    engine: Mutex<RunEngine>,
+    /// 再開・スキップ時に1秒の区切りをリセットするための通知
+    reset: Notify,
+}
+
+impl Session {
+    fn snapshot(&self, engine: &RunEngine) -> RunSession {
+        RunSession {
+            id: self.id.clone(),
+            timer: engine.timer().clone(),
+            status: engine.status(),
+            progress: engine.progress(),
+            started_at: self.started_at,
+        }
+    }
+
+    fn update(&self, engine: &RunEngine, events: Vec<RunEvent>) -> RunUpdate {
+        RunUpdate {
+            session: self.snapshot(engine),
+            events,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.engine.lock().unwrap().is_finished()
+    }
+}
+
+/// 状態変化の配信先
+struct Subscribers {
+    /// 画面（SSE）向け。遅れた受信者は古い更新を飛ばしてよい
+    updates: broadcast::Sender<RunUpdate>,
+    /// サーバー内の処理向け。すべての更新を順に受け取る
+    consumers: Mutex<Vec<mpsc::UnboundedSender<RunUpdate>>>,
+}
+
+impl Subscribers {
+    fn send(&self, update: RunUpdate) {
+        self.consumers
+            .lock()
+            .unwrap()
+            .retain(|consumer| consumer.send(update.clone()).is_ok());
+        let _ = self.updates.send(update);
+    }
+}
+
+#[derive(Clone)]
+pub struct RunManager {
+    sessions: Arc<Mutex<HashMap<String, Arc<Session>>>>,
+    subscribers: Arc<Subscribers>,
+}
+
+impl Default for RunManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunManager {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        Self {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            subscribers: Arc::new(Subscribers {
+                updates,
+                consumers: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// 以降の状態変化を受け取る。受信が遅れると古い更新は飛ばされる（Lagged）
+    pub fn subscribe(&self) -> broadcast::Receiver<RunUpdate> {
+        self.subscribers.updates.subscribe()
+    }
+
+    /// 以降のすべての状態変化を取りこぼさずに受け取る
+    pub fn consume(&self) -> mpsc::UnboundedReceiver<RunUpdate> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.subscribers.consumers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// 保持中のセッション一覧（開始が古い順）
+    pub fn list(&self) -> Vec<RunSession> {
+        let sessions: Vec<Arc<Session>> = self.sessions.lock().unwrap().values().cloned().collect();
+        let mut list: Vec<RunSession> = sessions
+            .iter()
+            .map(|s| s.snapshot(&s.engine.lock().unwrap()))
+            .collect();
+        list.sort_by_key(|s| s.started_at);
+        list
+    }
+
+    pub fn get(&self, id: &str) -> Option<RunSession> {
+        let session = self.sessions.lock().unwrap().get(id).cloned()?;
+        let engine = session.engine.lock().unwrap();
+        Some(session.snapshot(&engine))
+    }
+
+    /// タイマーの実行を開始し、1秒ごとに進めるタスクを起動する
+    pub fn start(&self, timer: TimerConfig) -> RunSession {
+        let mut engine = RunEngine::new(timer);
+        let events = engine.start();
+        let session = Arc::new(Session {
+            id: Uuid::new_v4().to_string(),
+            started_at: now_unix(),
+            engine: Mutex::new(engine),
+            reset: Notify::new(),
+        });
+
+        let update = session.update(&session.engine.lock().unwrap(), events);
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session.id.clone(), session.clone());
+        let snapshot = update.session.clone();
+        self.subscribers.send(update);
+
+        tokio::spawn(drive_session(
+            session,
+            self.sessions.clone(),
+            self.subscribers.clone(),
+        ));
+        snapshot
+    }
+
+    /// 一時停止・再開・スキップ・中止。セッションが無ければ None。
+    pub fn control(&self, id: &str, command: RunCommand) -> Option<RunSession> {
+        let session = self.sessions.lock().unwrap().get(id).cloned()?;
+        let update = {
+            let mut engine = session.engine.lock().unwrap();
+            let events = match command {
+                RunCommand::Pause => engine.pause(),
+                RunCommand::Resume => engine.resume(),
+                RunCommand::Skip => engine.skip(),
+                RunCommand::Stop => engine.abort(),
+            };
+            session.update(&engine, events)
+        };
+        if !update.events.is_empty() {
+            if !matches!(command, RunCommand::Pause) {
+                session.reset.notify_one();
+            }
+            self.subscribers.send(update.clone());
+        }
+        Some(update.session)
+    }
+}
+
+async fn drive_session(
+    session: Arc<Session>,
+    sessions: Arc<Mutex<HashMap<String, Arc<Session>>>>,
+    subscribers: Arc<Subscribers>,
+) {
+    let mut ticker = interval_at(Instant::now() + TICK, TICK);
+    while !session.is_finished() {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let update = {
+                    let mut engine = session.engine.lock().unwrap();
+                    let events = engine.tick();
+                    if events.is_empty() {
+                        None
+                    } else {
+                        Some(session.update(&engine, events))
+                    }
+                };
+                if let Some(update) = update {
+                    subscribers.send(update);
+                }
+            }
+            _ = session.reset.notified() => {
+                ticker.reset();
+            }
+        }
+    }
+
+    sleep(FINISHED_RETENTION).await;
+    sessions.lock().unwrap().remove(&session.id);
+}
+
+pub fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,252 @@
+//! 定期実行: 有効なスケジュールを1秒ごとに評価し、時刻になったらサーバー側の実行セッションを開始する。
+//! 開始したセッションは通常の実行と同じく SSE で各フロントエンドに通知される。
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use actix_web::web;
+use chrono::{DateTime, Datelike, Days, TimeZone, Utc};
+use chrono_tz::Tz;
+use nekotimer_shared::{ErrorCode, Schedule, ScheduleRule, ValidationError};
+use tokio::time::interval;
+
+use crate::persistence;
+use crate::AppState;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 曜日の名前（0 = 日曜）。cron クレートは曜日の数値を 1 = 日曜 と解釈するので名前で渡す
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// 標準 cron の曜日の1項目（`1-5`, `6-7`, `*/2`, `0` など）を曜日の番号（0〜6）に展開する。
+/// 日曜を 7 と書く範囲（`1-7`, `6-7`）も扱えるよう、範囲を個々の曜日に開いてから名前にする。
+/// 数値でない項目（`MON-FRI` など）は None
+fn expand_day_of_week(part: &str) -> Option<Vec<u32>> {
+    let (range, step) = match part.split_once('/') {
+        Some((range, step)) => (range, step.parse::<usize>().ok().filter(|s| *s > 0)?),
+        None => (part, 1),
+    };
+    let (first, last) = if range == "*" {
+        (0, 6)
+    } else if let Some((first, last)) = range.split_once('-') {
+        (first.parse().ok()?, last.parse().ok()?)
+    } else {
+        let day = range.parse().ok()?;
+        // `1/2` は 1 から週の終わりまで
+        (day, if part.contains('/') { 6 } else { day })
+    };
+    if first > last || last > 7 {
+        return None;
+    }
+    Some((first..=last).step_by(step).map(|day: u32| day % 7).collect())
+}
+
+/// 曜日の項目の数値（0/7 = 日曜）を SUN〜SAT の名前に置き換える
+fn normalize_day_of_week(field: &str) -> String {
+    if field == "*" || field == "?" {
+        return field.to_string();
+    }
+    let mut days = Vec::new();
+    let mut others = Vec::new();
+    for part in field.split(',') {
+        match expand_day_of_week(part) {
+            Some(expanded) => days.extend(expanded),
+            None => others.push(part.to_string()),
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    days.iter()
+        .map(|day| DAY_NAMES[*day as usize].to_string())
+        .chain(others)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// 「分 時 日 月 曜日」の標準 cron 式を cron クレートの形式に変換する。
+/// 先頭に秒 (0) を補い、曜日の数値（0/7 = 日曜）を SUN〜SAT の名前に置き換える。
+/// 秒・年を含む6〜7項目の式も、曜日の数値は同じく標準 cron の意味（0/7 = 日曜）で読む。
+fn normalize_cron(expression: &str) -> String {
+    let mut fields: Vec<String> = expression.split_whitespace().map(str::to_string).collect();
+    if fields.len() == 5 {
+        fields.insert(0, "0".to_string());
+    }
+    if let Some(day_of_week) = fields.get_mut(5) {
+        *day_of_week = normalize_day_of_week(day_of_week);
+    }
+    fields.join(" ")
+}
+
+/// タイムゾーン名と cron 式がサーバーで解釈できるか確認する
+pub fn check_rule(schedule: &Schedule) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    if Tz::from_str(&schedule.timezone).is_err() {
+        errors.push(ValidationError::new(
+            "timezone",
+            ErrorCode::InvalidFormat { detail: Some(schedule.timezone.clone()) },
+        ));
+    }
+    if let ScheduleRule::Cron { expression } = &schedule.rule {
+        if let Err(e) = cron::Schedule::from_str(&normalize_cron(expression)) {
+            errors.push(ValidationError::new(
+                "rule.expression",
+                ErrorCode::InvalidFormat { detail: Some(e.to_string()) },
+            ));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// after より後で最初に実行すべき時刻
+pub fn next_fire(schedule: &Schedule, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let tz = Tz::from_str(&schedule.timezone).ok()?;
+    match &schedule.rule {
+        ScheduleRule::Weekly { days, hour, minute } => {
+            let local_today = after.with_timezone(&tz).date_naive();
+            (0..=7u64).find_map(|offset| {
+                let date = local_today.checked_add_days(Days::new(offset))?;
+                let weekday = date.weekday().num_days_from_monday();
+                if !days.iter().any(|d| d.index_from_monday() == weekday) {
+                    return None;
+                }
+                // 夏時間の切り替えで存在しない時刻はスキップ
+                let local = tz
+                    .from_local_datetime(&date.and_hms_opt(*hour, *minute, 0)?)
+                    .earliest()?;
+                let candidate = local.with_timezone(&Utc);
+                (candidate > after).then_some(candidate)
+            })
+        }
+        ScheduleRule::Cron { expression } => {
+            let cron = cron::Schedule::from_str(&normalize_cron(expression)).ok()?;
+            let next = cron.after(&after.with_timezone(&tz)).next()?;
+            Some(next.with_timezone(&Utc))
+        }
+    }
+}
+
+/// 表示用に next_run_at を埋めたコピー（無効なスケジュールは None）
+pub fn with_next_run(schedule: &Schedule) -> Schedule {
+    let mut s = schedule.clone();
+    s.next_run_at = if s.enabled {
+        next_fire(&s, Utc::now()).map(|t| t.timestamp() as u64)
+    } else {
+        None
+    };
+    s
+}
+
+/// スケジュールを評価し続けるバックグラウンドタスク
+pub async fn run_scheduler(data: web::Data<AppState>) {
+    let mut ticker = interval(CHECK_INTERVAL);
+    let mut last_check = Utc::now();
+    loop {
+        ticker.tick().await;
+        let now = Utc::now();
+
+        let due: Vec<(String, String)> = {
+            let schedules = data.schedules.lock().unwrap();
+            schedules
+                .schedules
+                .iter()
+                .filter(|s| s.enabled)
+                .filter(|s| next_fire(s, last_check).is_some_and(|t| t <= now))
+                .map(|s| (s.id.clone(), s.timer_id.clone()))
+                .collect()
+        };
+        last_check = now;
+        if due.is_empty() {
+            continue;
+        }
+
+        let mut started = Vec::new();
+        for (schedule_id, timer_id) in due {
+            let timer = {
+                let config = data.config.lock().unwrap();
+                config.timers.iter().find(|t| t.id == timer_id).cloned()
+            };
+            match timer {
+                Some(timer) => {
+                    log::info!("スケジュール {} によりタイマー「{}」を開始します", schedule_id, timer.name);
+                    data.runs.start(timer);
+                    started.push(schedule_id);
+                }
+                None => log::warn!("スケジュール {}: タイマー {} が見つかりません", schedule_id, timer_id),
+            }
+        }
+
+        let mut schedules = data.schedules.lock().unwrap();
+        for s in schedules.schedules.iter_mut() {
+            if started.contains(&s.id) {
+                s.last_run_at = Some(now.timestamp() as u64);
+            }
+        }
+        if let Err(e) = persistence::save_json(&data.schedules_path, &*schedules) {
+            log::error!("スケジュールの保存に失敗しました: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-01-01 は月曜日。そこから1週間で式が当たる曜日（0 = 日曜）
+    fn fire_days(expression: &str) -> Vec<u32> {
+        let cron = cron::Schedule::from_str(&normalize_cron(expression)).unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut days: Vec<u32> = cron
+            .after(&start)
+            .take_while(|t| *t < start + chrono::Duration::days(7))
+            .map(|t| t.weekday().num_days_from_sunday())
+            .collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn weekdays_range() {
+        assert_eq!(normalize_cron("30 9 * * 1-5"), "0 30 9 * * MON,TUE,WED,THU,FRI");
+        assert_eq!(fire_days("30 9 * * 1-5"), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn whole_week_from_sunday_zero() {
+        assert_eq!(fire_days("0 12 * * 0-6"), vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn weekend_with_sunday_as_seven() {
+        assert_eq!(normalize_cron("0 8 * * 6-7"), "0 0 8 * * SUN,SAT");
+        assert_eq!(fire_days("0 8 * * 6-7"), vec![0, 6]);
+        assert_eq!(fire_days("0 8 * * 1-7"), vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn step_over_whole_week() {
+        assert_eq!(fire_days("0 8 * * */2"), vec![0, 2, 4, 6]);
+    }
+
+    #[test]
+    fn single_days_and_names() {
+        assert_eq!(fire_days("0 8 * * 0"), vec![0]);
+        assert_eq!(fire_days("0 8 * * 7"), vec![0]);
+        assert_eq!(fire_days("0 8 * * MON"), vec![1]);
+    }
+
+    #[test]
+    fn six_field_weekdays_use_standard_numbering() {
+        assert_eq!(normalize_cron("0 0 10 * * 1-5"), "0 0 10 * * MON,TUE,WED,THU,FRI");
+        assert_eq!(fire_days("0 0 10 * * 1-5"), vec![1, 2, 3, 4, 5]);
+        assert_eq!(normalize_cron("30 0 10 * * 0 2030"), "30 0 10 * * SUN 2030");
+    }
+
+    #[test]
+    fn invalid_day_range_is_rejected() {
+        assert!(cron::Schedule::from_str(&normalize_cron("0 8 * * 5-1")).is_err());
+    }
+}
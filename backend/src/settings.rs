@@ -0,0 +1,396 @@
+//! 起動設定。優先順位は「コマンドライン引数 > 環境変数 > 設定ファイル（TOML）> 既定値」。
+//! 設定ファイルは --settings / NEKOTIMER_SETTINGS で指定するか、作業ディレクトリの nekotimer.toml を使う。
+
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use actix_web::http::header::HeaderName;
+use actix_web::http::Method;
+use clap::Parser;
+use nekotimer_shared::ValidationPolicy;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::UserCommand;
+use crate::cors::{self, CorsPolicy};
+
+const DEFAULT_SETTINGS_FILE: &str = "nekotimer.toml";
+const DEFAULT_BIND: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 14990;
+const DEFAULT_CONFIG_PATH: &str = "timer.config";
+const DEFAULT_LOG_LEVEL: &str = "info";
+const LOG_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// コマンドライン引数（各項目は環境変数でも指定できる）
+#[derive(Parser, Debug)]
+#[command(name = "nekotimer-backend", version, about = "nekotimer のバックエンドサーバー")]
+struct Args {
+    /// 設定ファイル（TOML）
+    #[arg(long, env = "NEKOTIMER_SETTINGS")]
+    settings: Option<PathBuf>,
+    /// 待ち受けるアドレス（複数指定可）
+    #[arg(long, env = "NEKOTIMER_BIND", value_delimiter = ',')]
+    bind: Vec<String>,
+    /// 待ち受けるポート
+    #[arg(long, env = "NEKOTIMER_PORT")]
+    port: Option<u16>,
+    /// タイマー設定ファイル（履歴やスケジュールも同じ場所に保存される）
+    #[arg(long, env = "TIMER_CONFIG_PATH")]
+    config_path: Option<String>,
+    /// CORS で許可する別オリジン（複数指定可、"*" ですべて許可）
+    #[arg(long = "cors-origin", env = "NEKOTIMER_CORS_ORIGINS", value_delimiter = ',')]
+    cors_origins: Vec<String>,
+    /// 別オリジンに許可するメソッド（複数指定可）
+    #[arg(long = "cors-method", env = "NEKOTIMER_CORS_METHODS", value_delimiter = ',')]
+    cors_methods: Vec<String>,
+    /// 別オリジンに許可するリクエストヘッダー（複数指定可）
+    #[arg(long = "cors-header", env = "NEKOTIMER_CORS_HEADERS", value_delimiter = ',')]
+    cors_headers: Vec<String>,
+    /// ログの詳細度（error / warn / info / debug / trace）
+    #[arg(long, env = "NEKOTIMER_LOG_LEVEL")]
+    log_level: Option<String>,
+    /// フロントエンドの dist ディレクトリ
+    #[arg(long, env = "NEKOTIMER_STATIC_DIR")]
+    static_dir: Option<PathBuf>,
+    /// 最終的な設定を TOML で表示して終了する
+    #[arg(long)]
+    print_config: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// ユーザーの管理（サーバーを起動せずにユーザーファイルを編集する）
+    #[command(subcommand)]
+    User(UserCommand),
+}
+
+/// 設定ファイルの内容。省略した項目は既定値になる
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SettingsFile {
+    bind: Option<Vec<String>>,
+    port: Option<u16>,
+    config_path: Option<String>,
+    cors_origins: Option<Vec<String>>,
+    cors_methods: Option<Vec<String>>,
+    cors_headers: Option<Vec<String>>,
+    log_level: Option<String>,
+    static_dir: Option<PathBuf>,
+    validation: Option<ValidationPolicy>,
+}
+
+/// 最終的な起動設定
+#[derive(Debug, Clone, Serialize)]
+pub struct Settings {
+    pub bind: Vec<String>,
+    pub port: u16,
+    pub config_path: String,
+    /// 空なら同一オリジンのみ
+    pub cors_origins: Vec<String>,
+    pub cors_methods: Vec<String>,
+    pub cors_headers: Vec<String>,
+    pub log_level: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub static_dir: Option<PathBuf>,
+    /// タイマーの検証ルール（設定ファイルの [validation] でのみ指定できる）
+    pub validation: ValidationPolicy,
+}
+
+pub enum Loaded {
+    Run(Settings),
+    /// --print-config: 表示して終了する
+    Print(Settings),
+    /// 管理コマンドを実行して終了する
+    Admin(Settings, UserCommand),
+}
+
+impl Settings {
+    /// 引数・環境変数・設定ファイルを読み、検証する
+    pub fn load() -> Result<Loaded, Vec<String>> {
+        let args = Args::parse();
+        let file = read_settings_file(args.settings.as_deref()).map_err(|e| vec![e])?;
+
+        let settings = Settings::merge(&args, file);
+        settings.validate()?;
+
+        Ok(match args.command {
+            Some(Command::User(command)) => Loaded::Admin(settings, command),
+            None if args.print_config => Loaded::Print(settings),
+            None => Loaded::Run(settings),
+        })
+    }
+
+    /// 引数（環境変数を含む）と設定ファイルを合わせる。どちらにもない項目は既定値
+    fn merge(args: &Args, file: SettingsFile) -> Settings {
+        Settings {
+            bind: non_empty(args.bind.clone())
+                .or(file.bind)
+                .unwrap_or_else(|| vec![DEFAULT_BIND.to_string()]),
+            port: args.port.or(file.port).unwrap_or(DEFAULT_PORT),
+            config_path: args
+                .config_path
+                .clone()
+                .or(file.config_path)
+                .unwrap_or_else(|| DEFAULT_CONFIG_PATH.to_string()),
+            cors_origins: non_empty(args.cors_origins.clone())
+                .or(file.cors_origins)
+                .unwrap_or_default(),
+            cors_methods: non_empty(args.cors_methods.clone())
+                .or(file.cors_methods)
+                .unwrap_or_else(|| to_strings(&cors::DEFAULT_METHODS))
+                .into_iter()
+                .map(|m| m.to_uppercase())
+                .collect(),
+            cors_headers: non_empty(args.cors_headers.clone())
+                .or(file.cors_headers)
+                .unwrap_or_else(|| to_strings(&cors::DEFAULT_HEADERS))
+                .into_iter()
+                .map(|h| h.to_lowercase())
+                .collect(),
+            log_level: args
+                .log_level
+                .clone()
+                .or(file.log_level)
+                .unwrap_or_else(|| DEFAULT_LOG_LEVEL.to_string())
+                .to_lowercase(),
+            static_dir: args.static_dir.clone().or(file.static_dir),
+            validation: file.validation.unwrap_or_default(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if self.bind.is_empty() {
+            errors.push("bind: アドレスを1つ以上指定してください".to_string());
+        }
+        for addr in &self.bind {
+            if addr.parse::<IpAddr>().is_err() {
+                errors.push(format!("bind: IP アドレスではありません: {}", addr));
+            }
+        }
+        if self.port == 0 {
+            errors.push("port: 1〜65535 を指定してください".to_string());
+        }
+        if self.config_path.trim().is_empty() {
+            errors.push("config_path: パスを指定してください".to_string());
+        }
+        for origin in &self.cors_origins {
+            if !is_valid_origin(origin) {
+                errors.push(format!(
+                    "cors_origins: オリジンは scheme://host[:port] か * で指定してください: {}",
+                    origin
+                ));
+            }
+        }
+        for method in &self.cors_methods {
+            if Method::from_bytes(method.as_bytes()).is_err() {
+                errors.push(format!("cors_methods: メソッド名が不正です: {}", method));
+            }
+        }
+        for name in &self.cors_headers {
+            if HeaderName::from_bytes(name.as_bytes()).is_err() {
+                errors.push(format!("cors_headers: ヘッダー名が不正です: {}", name));
+            }
+        }
+        if !LOG_LEVELS.contains(&self.log_level.as_str()) {
+            errors.push(format!(
+                "log_level: {} のいずれかを指定してください: {}",
+                LOG_LEVELS.join(" / "),
+                self.log_level
+            ));
+        }
+        if let Some(dir) = &self.static_dir {
+            if !dir.is_dir() {
+                errors.push(format!("static_dir: ディレクトリがありません: {}", dir.display()));
+            }
+        }
+        errors.extend(self.validation.check().into_iter().map(|e| format!("validation.{}", e)));
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn socket_addrs(&self) -> Vec<SocketAddr> {
+        self.bind
+            .iter()
+            .filter_map(|addr| addr.parse::<IpAddr>().ok())
+            .map(|ip| SocketAddr::new(ip, self.port))
+            .collect()
+    }
+
+    pub fn cors_policy(&self) -> CorsPolicy {
+        CorsPolicy {
+            origins: self.cors_origins.clone(),
+            methods: self.cors_methods.clone(),
+            headers: self.cors_headers.clone(),
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).unwrap_or_default()
+    }
+}
+
+fn non_empty(values: Vec<String>) -> Option<Vec<String>> {
+    let values: Vec<String> = values
+        .into_iter()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect();
+    (!values.is_empty()).then_some(values)
+}
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+/// "http://192.168.1.10:14990" のようなオリジン（パスなし）か "*" か
+fn is_valid_origin(origin: &str) -> bool {
+    if origin == cors::ANY_ORIGIN {
+        return true;
+    }
+    let Some((scheme, host)) = origin.split_once("://") else {
+        return false;
+    };
+    matches!(scheme, "http" | "https") && !host.is_empty() && !host.contains('/')
+}
+
+/// 指定された設定ファイルを読む。未指定なら nekotimer.toml があれば読む
+fn read_settings_file(path: Option<&Path>) -> Result<SettingsFile, String> {
+    let path = match path {
+        Some(path) => path,
+        None if Path::new(DEFAULT_SETTINGS_FILE).exists() => Path::new(DEFAULT_SETTINGS_FILE),
+        None => return Ok(SettingsFile::default()),
+    };
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("設定ファイルを読めません ({}): {}", path.display(), e))?;
+    toml::from_str(&contents).map_err(|e| format!("設定ファイルが不正です ({}): {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(argv: &[&str]) -> Args {
+        Args::try_parse_from(std::iter::once("nekotimer-backend").chain(argv.iter().copied())).unwrap()
+    }
+
+    fn file(toml: &str) -> SettingsFile {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn valid() -> Settings {
+        Settings::merge(&args(&[]), SettingsFile::default())
+    }
+
+    fn errors(settings: Settings) -> Vec<String> {
+        settings.validate().err().unwrap_or_default()
+    }
+
+    /// 環境変数はプロセス全体で共有されるため、環境変数を使う確認はこのテストにまとめる。
+    /// 他のテストは NEKOTIMER_PORT / NEKOTIMER_LOG_LEVEL の値に依存しないこと
+    #[test]
+    fn merge_prefers_argv_then_env_then_file_then_default() {
+        let toml = || file("port = 3000\nlog_level = \"debug\"\nbind = [\"0.0.0.0\"]");
+
+        let defaults = Settings::merge(&args(&[]), SettingsFile::default());
+        assert_eq!(defaults.port, DEFAULT_PORT);
+        assert_eq!(defaults.bind, [DEFAULT_BIND]);
+        assert_eq!(defaults.log_level, DEFAULT_LOG_LEVEL);
+        assert_eq!(defaults.config_path, DEFAULT_CONFIG_PATH);
+        assert!(defaults.cors_origins.is_empty());
+
+        let from_file = Settings::merge(&args(&[]), toml());
+        assert_eq!((from_file.port, from_file.log_level.as_str()), (3000, "debug"));
+        assert_eq!(from_file.bind, ["0.0.0.0"]);
+
+        std::env::set_var("NEKOTIMER_PORT", "4000");
+        std::env::set_var("NEKOTIMER_LOG_LEVEL", "WARN");
+        let from_env = Settings::merge(&args(&[]), toml());
+        let from_argv = Settings::merge(&args(&["--port", "5000", "--bind", "::1,127.0.0.1"]), toml());
+        std::env::remove_var("NEKOTIMER_PORT");
+        std::env::remove_var("NEKOTIMER_LOG_LEVEL");
+
+        assert_eq!((from_env.port, from_env.log_level.as_str()), (4000, "warn"));
+        assert_eq!(from_env.bind, ["0.0.0.0"]);
+        assert_eq!((from_argv.port, from_argv.log_level.as_str()), (5000, "warn"));
+        assert_eq!(from_argv.bind, ["::1", "127.0.0.1"]);
+    }
+
+    #[test]
+    fn merge_normalizes_cors_lists() {
+        let settings = Settings::merge(
+            &args(&["--cors-method", "get, post", "--cors-header", "X-Token"]),
+            file("cors_origins = [\"http://localhost:8080\"]"),
+        );
+        assert_eq!(settings.cors_methods, ["GET", "POST"]);
+        assert_eq!(settings.cors_headers, ["x-token"]);
+        assert_eq!(settings.cors_origins, ["http://localhost:8080"]);
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(valid().validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_bad_bind_addresses() {
+        let mut settings = valid();
+        settings.bind = vec!["localhost".into(), "127.0.0.1".into(), "10.0.0.256".into()];
+        let bind_errors = errors(settings);
+        assert_eq!(bind_errors.len(), 2);
+        assert!(bind_errors.iter().all(|e| e.starts_with("bind:")));
+
+        let mut settings = valid();
+        settings.bind.clear();
+        assert_eq!(errors(settings).len(), 1);
+    }
+
+    #[test]
+    fn validate_rejects_port_zero() {
+        let mut settings = valid();
+        settings.port = 0;
+        let errors = errors(settings);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("port:"));
+    }
+
+    #[test]
+    fn validate_rejects_bad_origins() {
+        let mut settings = valid();
+        settings.cors_origins = ["*", "https://example.com", "http://[::1]:8080"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(settings.validate(), Ok(()));
+
+        settings.cors_origins = ["example.com", "ftp://example.com", "https://", "http://example.com/app"]
+            .map(String::from)
+            .to_vec();
+        let errors = errors(settings);
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().all(|e| e.starts_with("cors_origins:")));
+    }
+
+    #[test]
+    fn validate_rejects_unknown_log_levels() {
+        let mut settings = valid();
+        settings.log_level = "verbose".into();
+        let errors = errors(settings);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("log_level:"));
+
+        // 大文字は読み込み時に小文字へそろえる
+        let settings = Settings::merge(&args(&["--log-level", "DEBUG"]), SettingsFile::default());
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn settings_file_rejects_unknown_keys() {
+        assert!(toml::from_str::<SettingsFile>("prot = 80").is_err());
+    }
+}
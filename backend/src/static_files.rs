@@ -0,0 +1,171 @@
+//! フロントエンド（Trunk の dist）の配信。
+//! 指定ディレクトリ、または `embed-frontend` フィーチャーでバイナリに埋め込んだファイルを返す。
+//! /api 以外で見つからないパス（拡張子なし）は index.html を返し、画面の切り替えはフロント側に任せる。
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::http::Method;
+use actix_web::{web, HttpRequest, HttpResponse};
+use nekotimer_shared::{ApiResponse, ErrorCode, Resource, ValidationError};
+
+use crate::AppState;
+
+const INDEX: &str = "index.html";
+
+#[cfg(feature = "embed-frontend")]
+#[derive(rust_embed::RustEmbed)]
+#[folder = "../frontend/dist"]
+struct EmbeddedFrontend;
+
+/// 配信元
+#[derive(Clone, Debug)]
+pub enum StaticSource {
+    Dir(PathBuf),
+    #[cfg(feature = "embed-frontend")]
+    Embedded,
+}
+
+struct StaticFile {
+    body: Cow<'static, [u8]>,
+    etag: String,
+}
+
+impl StaticSource {
+    /// ディレクトリ指定を優先し、なければ埋め込み（ビルド時に有効な場合）を使う
+    pub fn resolve(dir: Option<PathBuf>) -> Option<Self> {
+        match dir {
+            Some(dir) => Some(StaticSource::Dir(dir)),
+            #[cfg(feature = "embed-frontend")]
+            None => Some(StaticSource::Embedded),
+            #[cfg(not(feature = "embed-frontend"))]
+            None => None,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            StaticSource::Dir(dir) => dir.display().to_string(),
+            #[cfg(feature = "embed-frontend")]
+            StaticSource::Embedded => "埋め込み".to_string(),
+        }
+    }
+
+    async fn load(&self, path: &str) -> Option<StaticFile> {
+        match self {
+            StaticSource::Dir(dir) => {
+                let full = dir.join(path);
+                let meta = tokio::fs::metadata(&full).await.ok()?;
+                if !meta.is_file() {
+                    return None;
+                }
+                let mtime = meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_secs());
+                let body = tokio::fs::read(&full).await.ok()?;
+                Some(StaticFile {
+                    etag: format!("\"{:x}-{:x}\"", meta.len(), mtime),
+                    body: Cow::Owned(body),
+                })
+            }
+            #[cfg(feature = "embed-frontend")]
+            StaticSource::Embedded => {
+                let file = <EmbeddedFrontend as rust_embed::RustEmbed>::get(path)?;
+                Some(StaticFile {
+                    etag: format!("\"{}\"", hex::encode(file.metadata.sha256_hash())),
+                    body: file.data,
+                })
+            }
+        }
+    }
+}
+
+/// URL のパスを配信元内の相対パスにする。`..` などを含む場合は None
+fn relative_path(request_path: &str) -> Option<String> {
+    let trimmed = request_path.trim_start_matches('/');
+    if trimmed.is_empty() {
+        return Some(INDEX.to_string());
+    }
+    let ok = trimmed
+        .split('/')
+        .all(|seg| !seg.is_empty() && seg != "." && seg != ".." && !seg.contains('\\'));
+    ok.then(|| trimmed.to_string())
+}
+
+/// Trunk が付けるハッシュ（16桁の16進数）入りのファイル名か
+fn is_hashed(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.split(['-', '_', '.'])
+        .any(|part| part.len() == 16 && part.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// index.html は毎回確認、ハッシュ付きのファイルは長期キャッシュ、その他（効果音など）は1時間
+fn cache_control(path: &str) -> &'static str {
+    if path == INDEX {
+        "no-cache"
+    } else if is_hashed(path) {
+        "public, max-age=31536000, immutable"
+    } else {
+        "public, max-age=3600"
+    }
+}
+
+fn api_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ApiResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new(
+            "path",
+            ErrorCode::NotFound { resource: Resource::Api },
+        )]),
+    })
+}
+
+/// ルートに一致しなかったリクエストの処理（default_service）
+pub async fn serve(req: HttpRequest, data: web::Data<AppState>) -> HttpResponse {
+    if req.path() == "/api" || req.path().starts_with("/api/") {
+        return api_not_found();
+    }
+    let Some(source) = &data.static_files else {
+        return HttpResponse::NotFound().finish();
+    };
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return HttpResponse::MethodNotAllowed().finish();
+    }
+    let Some(path) = relative_path(req.path()) else {
+        return HttpResponse::NotFound().finish();
+    };
+
+    let (path, file) = match source.load(&path).await {
+        Some(file) => (path, file),
+        // 拡張子のないパスは画面の URL とみなして index.html を返す
+        None if Path::new(&path).extension().is_none() => match source.load(INDEX).await {
+            Some(file) => (INDEX.to_string(), file),
+            None => return HttpResponse::NotFound().finish(),
+        },
+        None => return HttpResponse::NotFound().finish(),
+    };
+
+    let not_modified = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|v| v.as_bytes() == file.etag.as_bytes());
+    let mut resp = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    resp.insert_header((header::CACHE_CONTROL, cache_control(&path)));
+    if let Ok(etag) = HeaderValue::from_str(&file.etag) {
+        resp.insert_header((header::ETAG, etag));
+    }
+    if not_modified {
+        return resp.finish();
+    }
+    let mime = mime_guess::from_path(&path).first_or_octet_stream();
+    resp.content_type(mime.as_ref()).body(file.body.into_owned())
+}
@@ -0,0 +1,50 @@
+//! テスト用の状態と利用者
+
+use actix_web::{web, HttpMessage};
+use nekotimer_shared::{TimerConfig, UserInfo, ValidationPolicy};
+use uuid::Uuid;
+
+use crate::AppState;
+
+/// 一時ディレクトリに置いた空の状態
+pub fn state() -> web::Data<AppState> {
+    let dir = std::env::temp_dir().join(format!("nekotimer-test-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config_path = dir.join("test.config").to_string_lossy().into_owned();
+    web::Data::new(AppState::load(config_path, None, ValidationPolicy::default()))
+}
+
+pub fn user(id: &str) -> UserInfo {
+    UserInfo {
+        id: id.into(),
+        username: id.into(),
+        is_admin: false,
+    }
+}
+
+pub fn admin(id: &str) -> UserInfo {
+    UserInfo {
+        is_admin: true,
+        ..user(id)
+    }
+}
+
+/// owner の個人タイマー
+pub fn timer(id: &str, owner: &str) -> TimerConfig {
+    TimerConfig {
+        id: id.into(),
+        name: id.into(),
+        blocks: Vec::new(),
+        owner: Some(owner.into()),
+        workspace_id: None,
+        sound_profile: None,
+        sounds: Default::default(),
+        tick_under_secs: None,
+    }
+}
+
+/// 認証を通ったものとして user を付ける（require_auth の代わり）。`TestRequest::to_request()` に使う
+pub fn request_as<R: HttpMessage>(req: R, user: &UserInfo) -> R {
+    req.extensions_mut().insert(user.clone());
+    req
+}
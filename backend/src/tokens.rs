@@ -0,0 +1,124 @@
+//! スクリプト用の個人 API トークン。
+//! トークン本体は作成時にだけ返し、`<設定ファイル名>.tokens.json` には SHA-256 ハッシュを保存する。
+//! スコープを超える操作とトークン・ログインの管理 API は拒否する。
+
+use actix_web::http::Method;
+use nekotimer_shared::{ApiToken, TokenScope, UserInfo, API_TOKEN_PREFIX};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::auth::hash_token;
+use crate::persistence;
+use crate::runs::now_unix;
+use crate::AppState;
+
+/// 最終使用時刻をファイルに書く間隔（リクエストごとに保存しない）
+const LAST_USED_SAVE_INTERVAL_SECS: u64 = 60;
+/// 一覧に表示するトークンの先頭の長さ
+const DISPLAY_PREFIX_LEN: usize = 12;
+/// API トークンでは使えないパス（セッションでのみ操作する）
+const SESSION_ONLY_PATHS: [&str; 2] = ["/api/tokens", "/api/auth/"];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredApiToken {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub scope: TokenScope,
+    pub token_hash: String,
+    pub prefix: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub last_used_at: Option<u64>,
+}
+
+impl StoredApiToken {
+    pub fn info(&self) -> ApiToken {
+        ApiToken {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            scope: self.scope,
+            prefix: self.prefix.clone(),
+            created_at: self.created_at,
+            last_used_at: self.last_used_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApiTokenFile {
+    pub tokens: Vec<StoredApiToken>,
+}
+
+pub enum TokenError {
+    /// 存在しない・失効した・ユーザーが削除された
+    Invalid,
+    /// スコープ外の操作
+    Forbidden,
+}
+
+pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(API_TOKEN_PREFIX)
+}
+
+/// 新しいトークンを発行する。戻り値はトークン本体と保存用のレコード
+pub fn issue(user_id: &str, name: &str, scope: TokenScope) -> (String, StoredApiToken) {
+    let token = format!(
+        "{}{}{}",
+        API_TOKEN_PREFIX,
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    );
+    let record = StoredApiToken {
+        id: Uuid::new_v4().to_string(),
+        user_id: user_id.to_string(),
+        name: name.trim().to_string(),
+        scope,
+        token_hash: hash_token(&token),
+        prefix: token.chars().take(DISPLAY_PREFIX_LEN).collect(),
+        created_at: now_unix(),
+        last_used_at: None,
+    };
+    (token, record)
+}
+
+/// スコープでそのリクエストが許されるか
+fn allows(scope: TokenScope, method: &Method, path: &str) -> bool {
+    if SESSION_ONLY_PATHS.iter().any(|p| path.starts_with(p)) {
+        return false;
+    }
+    let is_read = method == Method::GET || method == Method::HEAD;
+    let is_run = method == Method::POST && (path == "/api/runs" || path.starts_with("/api/runs/"));
+    match scope {
+        TokenScope::Read => is_read,
+        TokenScope::Run => is_read || is_run,
+        TokenScope::Manage => true,
+    }
+}
+
+/// API トークンを検証し、持ち主のユーザーを返す。最終使用時刻も更新する
+pub fn authenticate(data: &AppState, token: &str, method: &Method, path: &str) -> Result<UserInfo, TokenError> {
+    let token_hash = hash_token(token);
+    let mut tokens = data.api_tokens.lock().unwrap();
+    let record = tokens
+        .tokens
+        .iter_mut()
+        .find(|t| t.token_hash == token_hash)
+        .ok_or(TokenError::Invalid)?;
+    let user = data.users.find_by_id(&record.user_id).ok_or(TokenError::Invalid)?;
+    if !allows(record.scope, method, path) {
+        return Err(TokenError::Forbidden);
+    }
+
+    let now = now_unix();
+    let save = record
+        .last_used_at
+        .is_none_or(|t| now >= t + LAST_USED_SAVE_INTERVAL_SECS);
+    if save {
+        record.last_used_at = Some(now);
+        if let Err(e) = persistence::save_json(&data.api_tokens_path, &*tokens) {
+            log::error!("API トークンの保存に失敗しました: {}", e);
+        }
+    }
+    Ok(user.info())
+}
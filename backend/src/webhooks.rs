@@ -0,0 +1,464 @@
+//! Webhook 通知: 実行セッションのイベントを登録 URL に署名付き JSON で POST する。
+//! Webhook ごとに送信タスクを1つ持ち、イベントの順序を保ったまま送る。
+//! 失敗時（タイムアウト・2xx 以外）は間隔を倍にしながら再試行し、結果を送信ログに残す。
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use actix_web::web;
+use hmac::{Hmac, Mac};
+use nekotimer_shared::{
+    RunEvent, RunUpdate, TimerConfig, Webhook, WebhookDelivery, WebhookEvent, WebhookPayload,
+};
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::runs::now_unix;
+use crate::workspaces::timer_role;
+use crate::AppState;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const RETRY: Retry = Retry {
+    max_attempts: 3,
+    base_delay: Duration::from_secs(1),
+};
+/// 送信ログに残す件数
+pub const DELIVERY_LOG_CAPACITY: usize = 200;
+
+pub const SIGNATURE_HEADER: &str = "X-Nekotimer-Signature";
+pub const EVENT_HEADER: &str = "X-Nekotimer-Event";
+pub const DELIVERY_HEADER: &str = "X-Nekotimer-Delivery";
+
+type Job = (Webhook, WebhookPayload);
+
+/// 再試行の回数と最初の待ち時間（以降は倍にしていく）
+struct Retry {
+    max_attempts: u32,
+    base_delay: Duration,
+}
+
+pub fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// 署名用の秘密鍵（64桁の16進数）
+pub fn generate_secret() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn payload(update: &RunUpdate, event: WebhookEvent, block_ix: Option<usize>) -> WebhookPayload {
+    let session = &update.session;
+    let block_ix = block_ix.or(Some(session.progress.block_ix));
+    let block = block_ix.and_then(|ix| session.timer.blocks.get(ix));
+    let (repetition, repetitions) = match session.progress.countdown_run {
+        Some((cur, tot)) if block_ix == Some(session.progress.block_ix) => (Some(cur), Some(tot)),
+        _ => (None, None),
+    };
+    WebhookPayload {
+        delivery_id: Uuid::new_v4().to_string(),
+        event,
+        timestamp: now_unix(),
+        session_id: session.id.clone(),
+        timer_id: session.timer.id.clone(),
+        timer_name: session.timer.name.clone(),
+        block_index: block.and(block_ix),
+        block_name: block.map(|b| b.name().to_string()),
+        repetition,
+        repetitions,
+    }
+}
+
+/// 更新に含まれる実行イベントを Webhook の通知内容に変換する
+pub fn payloads_for(update: &RunUpdate) -> Vec<WebhookPayload> {
+    update
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            RunEvent::Started => Some(payload(update, WebhookEvent::RunStarted, None)),
+            RunEvent::BlockStarted { block_ix } => {
+                Some(payload(update, WebhookEvent::BlockStarted, Some(*block_ix)))
+            }
+            RunEvent::RepetitionEnded { block_ix, run, total } => {
+                let mut p = payload(update, WebhookEvent::RepetitionEnded, Some(*block_ix));
+                p.repetition = Some(*run);
+                p.repetitions = Some(*total);
+                Some(p)
+            }
+            RunEvent::BlockEnded { block_ix, .. } => {
+                Some(payload(update, WebhookEvent::BlockEnded, Some(*block_ix)))
+            }
+            RunEvent::Completed => {
+                let mut p = payload(update, WebhookEvent::Completed, None);
+                p.block_index = None;
+                p.block_name = None;
+                p.repetition = None;
+                p.repetitions = None;
+                Some(p)
+            }
+            RunEvent::Aborted { block_ix } => {
+                Some(payload(update, WebhookEvent::Aborted, Some(*block_ix)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// 1件送信する。成功するか RETRY.max_attempts 回失敗するまで再試行する。
+pub async fn deliver(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    payload: &WebhookPayload,
+) -> WebhookDelivery {
+    deliver_with(client, webhook, payload, &RETRY).await
+}
+
+async fn deliver_with(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    payload: &WebhookPayload,
+    retry: &Retry,
+) -> WebhookDelivery {
+    let mut delivery = WebhookDelivery {
+        id: payload.delivery_id.clone(),
+        webhook_id: webhook.id.clone(),
+        url: webhook.url.clone(),
+        event: payload.event,
+        timestamp: payload.timestamp,
+        attempts: 0,
+        success: false,
+        status: None,
+        error: None,
+    };
+    let body = match serde_json::to_vec(payload) {
+        Ok(body) => body,
+        Err(e) => {
+            delivery.error = Some(e.to_string());
+            return delivery;
+        }
+    };
+    let signature = sign(&webhook.secret, &body);
+    let event_name = serde_json::to_value(payload.event)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    let mut delay = retry.base_delay;
+    while delivery.attempts < retry.max_attempts {
+        if delivery.attempts > 0 {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+        delivery.attempts += 1;
+        let result = client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, &signature)
+            .header(EVENT_HEADER, &event_name)
+            .header(DELIVERY_HEADER, &payload.delivery_id)
+            .body(body.clone())
+            .send()
+            .await;
+        match result {
+            Ok(resp) => {
+                delivery.status = Some(resp.status().as_u16());
+                if resp.status().is_success() {
+                    delivery.success = true;
+                    delivery.error = None;
+                    break;
+                }
+                delivery.error = Some(format!("HTTP {}", resp.status()));
+            }
+            Err(e) => {
+                delivery.status = None;
+                delivery.error = Some(e.to_string());
+            }
+        }
+    }
+    delivery
+}
+
+/// 送信ログに追加（古いものから捨てる）
+pub fn log_delivery(data: &AppState, delivery: WebhookDelivery) {
+    push_delivery(&mut data.webhook_deliveries.lock().unwrap(), delivery);
+}
+
+fn push_delivery(log: &mut VecDeque<WebhookDelivery>, delivery: WebhookDelivery) {
+    if log.len() >= DELIVERY_LOG_CAPACITY {
+        log.pop_front();
+    }
+    log.push_back(delivery);
+}
+
+/// 登録したユーザーがそのタイマーを見られるか。
+/// 登録後にワークスペースを抜けた・管理者でなくなった場合は、見えなくなったタイマーのイベントを送らない
+fn owner_can_see(data: &AppState, webhook: &Webhook, timer: &TimerConfig) -> bool {
+    // 認証導入前の Webhook は管理者だけが管理できるものとして扱う
+    let Some(owner) = &webhook.owner else {
+        return true;
+    };
+    data.users
+        .find_by_id(owner)
+        .is_some_and(|user| timer_role(data, &user.info(), timer).is_some())
+}
+
+async fn delivery_worker(
+    data: web::Data<AppState>,
+    client: reqwest::Client,
+    mut jobs: mpsc::UnboundedReceiver<Job>,
+) {
+    while let Some((webhook, payload)) = jobs.recv().await {
+        let delivery = deliver(&client, &webhook, &payload).await;
+        if !delivery.success {
+            log::warn!(
+                "Webhook の送信に失敗しました ({}): {}",
+                webhook.url,
+                delivery.error.as_deref().unwrap_or("")
+            );
+        }
+        log_delivery(&data, delivery);
+    }
+}
+
+/// 実行セッションの更新を監視し、該当する Webhook の送信タスクに振り分け続ける
+pub async fn dispatch_webhooks(data: web::Data<AppState>, mut updates: mpsc::UnboundedReceiver<RunUpdate>) {
+    let client = http_client();
+    let mut workers: HashMap<String, mpsc::UnboundedSender<Job>> = HashMap::new();
+
+    while let Some(update) = updates.recv().await {
+        let payloads = payloads_for(&update);
+        if payloads.is_empty() {
+            continue;
+        }
+
+        let webhooks = data.webhooks.lock().unwrap().webhooks.clone();
+        // 削除された Webhook の送信タスクは送信側を捨てて終了させる
+        workers.retain(|id, _| webhooks.iter().any(|w| w.id == *id));
+
+        for payload in payloads {
+            for webhook in webhooks
+                .iter()
+                .filter(|w| w.accepts(payload.event, &update.session.timer.id))
+                .filter(|w| owner_can_see(&data, w, &update.session.timer))
+            {
+                let sender = workers.entry(webhook.id.clone()).or_insert_with(|| {
+                    let (tx, rx) = mpsc::unbounded_channel();
+                    tokio::spawn(delivery_worker(data.clone(), client.clone(), rx));
+                    tx
+                });
+                let mut payload = payload.clone();
+                payload.delivery_id = Uuid::new_v4().to_string();
+                let _ = sender.send((webhook.clone(), payload));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// 受け取ったリクエスト（届いた時刻・ヘッダー・本文）
+    struct Received {
+        at: Instant,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    }
+
+    /// statuses の順に応答する HTTP サーバー。None なら応答せずに待たせる
+    async fn serve(statuses: Vec<Option<u16>>) -> (String, mpsc::UnboundedReceiver<Received>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let received = read_request(&mut socket).await;
+                let _ = tx.send(received);
+                match status {
+                    Some(status) => {
+                        let response =
+                            format!("HTTP/1.1 {} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+                        socket.write_all(response.as_bytes()).await.unwrap();
+                    }
+                    // 接続を保ったまま応答しない（クライアントのタイムアウト待ち）
+                    None => {
+                        tokio::spawn(async move {
+                            tokio::time::sleep(Duration::from_secs(10)).await;
+                            drop(socket);
+                        });
+                    }
+                }
+            }
+        });
+        (url, rx)
+    }
+
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> Received {
+        let at = Instant::now();
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            let n = socket.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            if let Some(ix) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break ix + 4;
+            }
+        };
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+        let headers: HashMap<String, String> = head
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+            .collect();
+        let len: usize = headers.get("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+        while buf.len() < header_end + len {
+            let n = socket.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        Received {
+            at,
+            headers,
+            body: buf[header_end..header_end + len].to_vec(),
+        }
+    }
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook {
+            id: "w1".into(),
+            url: url.into(),
+            timer_id: None,
+            events: Vec::new(),
+            secret: "s3cret".into(),
+            enabled: true,
+            owner: None,
+        }
+    }
+
+    fn test_payload() -> WebhookPayload {
+        WebhookPayload {
+            delivery_id: "d1".into(),
+            event: WebhookEvent::Test,
+            timestamp: 1,
+            session_id: String::new(),
+            timer_id: String::new(),
+            timer_name: String::new(),
+            block_index: None,
+            block_name: None,
+            repetition: None,
+            repetitions: None,
+        }
+    }
+
+    fn verify(secret: &str, body: &[u8], signature: &str) -> bool {
+        let Some(hex_mac) = signature.strip_prefix("sha256=") else {
+            return false;
+        };
+        let Ok(expected) = hex::decode(hex_mac) else {
+            return false;
+        };
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        mac.verify_slice(&expected).is_ok()
+    }
+
+    #[test]
+    fn signature_matches_hmac_of_body() {
+        let body = br#"{"event":"Test"}"#;
+        let signature = sign("s3cret", body);
+        assert!(verify("s3cret", body, &signature));
+        assert!(!verify("other", body, &signature));
+        assert!(!verify("s3cret", br#"{"event":"Completed"}"#, &signature));
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_body_with_headers() {
+        let (url, mut received) = serve(vec![Some(200)]).await;
+        let delivery = deliver(&http_client(), &webhook(&url), &test_payload()).await;
+        assert!(delivery.success);
+        assert_eq!((delivery.attempts, delivery.status), (1, Some(200)));
+
+        let request = received.recv().await.unwrap();
+        let signature = &request.headers[&SIGNATURE_HEADER.to_ascii_lowercase()];
+        assert!(verify("s3cret", &request.body, signature));
+        assert_eq!(request.headers[&EVENT_HEADER.to_ascii_lowercase()], "Test");
+        assert_eq!(request.headers[&DELIVERY_HEADER.to_ascii_lowercase()], "d1");
+        let sent: WebhookPayload = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(sent.delivery_id, "d1");
+    }
+
+    #[tokio::test]
+    async fn retries_with_doubling_delay() {
+        let (url, mut received) = serve(vec![Some(500), Some(503), Some(204)]).await;
+        let retry = Retry {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+        };
+        let delivery = deliver_with(&http_client(), &webhook(&url), &test_payload(), &retry).await;
+        assert!(delivery.success);
+        assert_eq!((delivery.attempts, delivery.status), (3, Some(204)));
+
+        let mut at = Vec::new();
+        while let Ok(request) = received.try_recv() {
+            at.push(request.at);
+        }
+        assert_eq!(at.len(), 3);
+        assert!(at[1] - at[0] >= Duration::from_millis(100));
+        assert!(at[2] - at[1] >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_timeouts() {
+        let (url, _received) = serve(vec![None, None]).await;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let retry = Retry {
+            max_attempts: 2,
+            base_delay: Duration::from_millis(10),
+        };
+        let delivery = deliver_with(&client, &webhook(&url), &test_payload(), &retry).await;
+        assert!(!delivery.success);
+        assert_eq!((delivery.attempts, delivery.status), (2, None));
+        assert!(delivery.error.is_some());
+    }
+
+    #[test]
+    fn delivery_log_keeps_latest_entries() {
+        let mut log = VecDeque::new();
+        for i in 0..DELIVERY_LOG_CAPACITY + 5 {
+            let delivery = WebhookDelivery {
+                id: i.to_string(),
+                webhook_id: "w1".into(),
+                url: String::new(),
+                event: WebhookEvent::Test,
+                timestamp: 0,
+                attempts: 1,
+                success: true,
+                status: Some(200),
+                error: None,
+            };
+            push_delivery(&mut log, delivery);
+        }
+        assert_eq!(log.len(), DELIVERY_LOG_CAPACITY);
+        assert_eq!(log.front().unwrap().id, "5");
+        assert_eq!(log.back().unwrap().id, (DELIVERY_LOG_CAPACITY + 4).to_string());
+    }
+}
@@ -0,0 +1,157 @@
+//! チームで共有するワークスペース。`<設定ファイル名>.workspaces.json` に保存する。
+//! タイマーに対する権限はここで決める（個人タイマーは持ち主、ワークスペースのタイマーはメンバーの役割）。
+
+use std::collections::HashSet;
+
+use actix_web::HttpResponse;
+use nekotimer_shared::{
+    ApiResponse, ErrorCode, RunRecord, TimerConfig, UserInfo, ValidationError, Workspace,
+    WorkspaceMember, WorkspaceRole,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::UserStore;
+use crate::AppState;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredMember {
+    pub user_id: String,
+    pub role: WorkspaceRole,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredWorkspace {
+    pub id: String,
+    pub name: String,
+    pub members: Vec<StoredMember>,
+    pub created_at: u64,
+}
+
+impl StoredWorkspace {
+    pub fn member_role(&self, user_id: &str) -> Option<WorkspaceRole> {
+        self.members.iter().find(|m| m.user_id == user_id).map(|m| m.role)
+    }
+
+    pub fn owner_count(&self) -> usize {
+        self.members.iter().filter(|m| m.role == WorkspaceRole::Owner).count()
+    }
+
+    /// API で返す形にする。role は user の役割（管理者でメンバーでなければオーナー扱い）
+    pub fn info(&self, user: &UserInfo, users: &UserStore) -> Workspace {
+        let members = self
+            .members
+            .iter()
+            .map(|m| WorkspaceMember {
+                user_id: m.user_id.clone(),
+                username: users
+                    .find_by_id(&m.user_id)
+                    .map(|u| u.username)
+                    .unwrap_or_else(|| "(削除されたユーザー)".to_string()),
+                role: m.role,
+            })
+            .collect();
+        Workspace {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            role: self.member_role(&user.id).unwrap_or(WorkspaceRole::Owner),
+            members,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct WorkspaceFile {
+    pub workspaces: Vec<StoredWorkspace>,
+}
+
+impl WorkspaceFile {
+    /// ワークスペースでのユーザーの役割。管理者はすべてのワークスペースでオーナー扱い
+    pub fn role_in(&self, user: &UserInfo, workspace_id: &str) -> Option<WorkspaceRole> {
+        let workspace = self.workspaces.iter().find(|w| w.id == workspace_id)?;
+        if user.is_admin {
+            return Some(WorkspaceRole::Owner);
+        }
+        workspace.member_role(&user.id)
+    }
+
+    /// タイマーに対するユーザーの役割。None ならそのタイマーは見えない
+    pub fn timer_role(&self, user: &UserInfo, timer: &TimerConfig) -> Option<WorkspaceRole> {
+        if user.is_admin {
+            return Some(WorkspaceRole::Owner);
+        }
+        match &timer.workspace_id {
+            Some(workspace_id) => self.role_in(user, workspace_id),
+            None => (timer.owner.as_deref() == Some(user.id.as_str())).then_some(WorkspaceRole::Owner),
+        }
+    }
+}
+
+/// タイマーに対するユーザーの役割（config のロック中に呼んでよい。ロックは config → workspaces の順に取る）
+pub fn timer_role(data: &AppState, user: &UserInfo, timer: &TimerConfig) -> Option<WorkspaceRole> {
+    data.workspaces.lock().unwrap().timer_role(user, timer)
+}
+
+/// ID のタイマーに対するユーザーの役割。タイマーがない・見えないなら None
+pub fn timer_role_by_id(data: &AppState, user: &UserInfo, timer_id: &str) -> Option<WorkspaceRole> {
+    let config = data.config.lock().unwrap();
+    let timer = config.timers.iter().find(|t| t.id == timer_id)?;
+    timer_role(data, user, timer)
+}
+
+/// ユーザーに見えるタイマーの ID（履歴・スケジュールなどの絞り込み用）
+pub fn visible_timer_ids(data: &AppState, user: &UserInfo) -> HashSet<String> {
+    timer_ids_where(data, user, |_| true)
+}
+
+/// 実行記録が見えるかを判定する関数。タイマーが残っていれば今の所有者・ワークスペースで、
+/// 削除済みなら記録に残した実行時点のタイマー設定で判定する（削除しても元の持ち主・メンバーには履歴が残る）
+pub fn run_record_filter(data: &AppState, user: &UserInfo) -> impl Fn(&RunRecord) -> bool {
+    let (live, visible) = {
+        let config = data.config.lock().unwrap();
+        let workspaces = data.workspaces.lock().unwrap();
+        let mut live = HashSet::new();
+        let mut visible = HashSet::new();
+        for timer in &config.timers {
+            live.insert(timer.id.clone());
+            if workspaces.timer_role(user, timer).is_some() {
+                visible.insert(timer.id.clone());
+            }
+        }
+        (live, visible)
+    };
+    let workspaces = data.workspaces.lock().unwrap().clone();
+    let user = user.clone();
+    move |record| {
+        if live.contains(&record.timer_id) {
+            visible.contains(&record.timer_id)
+        } else {
+            workspaces.timer_role(&user, &record.timer).is_some()
+        }
+    }
+}
+
+/// 編集者以上として扱えるタイマーの ID
+pub fn editable_timer_ids(data: &AppState, user: &UserInfo) -> HashSet<String> {
+    timer_ids_where(data, user, WorkspaceRole::can_edit)
+}
+
+fn timer_ids_where(data: &AppState, user: &UserInfo, f: impl Fn(WorkspaceRole) -> bool) -> HashSet<String> {
+    let config = data.config.lock().unwrap();
+    let workspaces = data.workspaces.lock().unwrap();
+    config
+        .timers
+        .iter()
+        .filter(|t| workspaces.timer_role(user, t).is_some_and(&f))
+        .map(|t| t.id.clone())
+        .collect()
+}
+
+/// 見えるが役割が足りない操作
+pub fn permission_denied() -> HttpResponse {
+    HttpResponse::Forbidden().json(ApiResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ValidationError::new("role", ErrorCode::PermissionDenied)]),
+    })
+}
@@ -0,0 +1,187 @@
+//! バックエンドの REST API クライアント
+
+use std::fmt;
+use std::time::Duration;
+
+use nekotimer_shared::{
+    ApiResponse, ErrorCode, Locale, LoginRequest, LoginResponse, Resource, TimerConfig,
+    ValidationError, ValidationPolicy,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use ureq::http::Response;
+use ureq::{Agent, Body, RequestBuilder};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub enum CliError {
+    /// 接続できない・応答が読めない
+    Http(String),
+    /// サーバーが返した検証エラー
+    Api(Vec<ValidationError>),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// 引数などの誤り
+    Usage(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Http(e) => write!(f, "サーバーと通信できません: {}", e),
+            CliError::Api(errors) => {
+                let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+                write!(f, "{}", messages.join("\n"))
+            }
+            CliError::Io(e) => write!(f, "{}", e),
+            CliError::Json(e) => write!(f, "JSON が不正です: {}", e),
+            CliError::Usage(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<ureq::Error> for CliError {
+    fn from(e: ureq::Error) -> Self {
+        CliError::Http(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(e: std::io::Error) -> Self {
+        CliError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(e: serde_json::Error) -> Self {
+        CliError::Json(e)
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    agent: Agent,
+    /// ログインで得たトークン（Authorization: Bearer で送る）
+    token: Option<String>,
+    /// エラーメッセージの言語（Accept-Language で送る）。None ならサーバーの既定
+    locale: Option<Locale>,
+}
+
+/// 環境変数のロケール（LC_ALL > LC_MESSAGES > LANG、例: "en_US.UTF-8"）から表示言語を選ぶ。
+/// 最初に値のある変数だけを見る。C / POSIX など対応していない言語なら None
+fn locale_from_env() -> Option<Locale> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| Locale::from_tag(&value))
+}
+
+impl Client {
+    pub fn new(server: &str, token: Option<String>) -> Self {
+        let agent: Agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(REQUEST_TIMEOUT))
+            .build()
+            .into();
+        Self {
+            base_url: format!("{}/api", server.trim_end_matches('/')),
+            agent,
+            token,
+            locale: locale_from_env(),
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// トークンと表示言語のヘッダーを付ける
+    fn prepare<B>(&self, mut req: RequestBuilder<B>) -> RequestBuilder<B> {
+        if let Some(locale) = self.locale {
+            req = req.header("Accept-Language", locale.tag());
+        }
+        match &self.token {
+            Some(token) => req.header("Authorization", format!("Bearer {}", token)),
+            None => req,
+        }
+    }
+
+    /// ApiResponse を取り出す。success でなければ errors を CliError::Api にする。
+    fn parse<T: DeserializeOwned>(mut resp: Response<Body>) -> Result<Option<T>, CliError> {
+        let status = resp.status();
+        let api_resp: ApiResponse<T> = resp
+            .body_mut()
+            .read_json()
+            .map_err(|e| CliError::Http(format!("HTTP {}: {}", status, e)))?;
+        if api_resp.success {
+            Ok(api_resp.data)
+        } else {
+            Err(CliError::Api(api_resp.errors.unwrap_or_default()))
+        }
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>, CliError> {
+        let req = self.prepare(self.agent.get(&self.url(path)));
+        Self::parse(req.call()?)
+    }
+
+    fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<Option<T>, CliError> {
+        let req = self.prepare(self.agent.post(&self.url(path)));
+        Self::parse(req.send_json(body)?)
+    }
+
+    fn delete(&self, path: &str) -> Result<(), CliError> {
+        let req = self.prepare(self.agent.delete(&self.url(path)));
+        Self::parse::<()>(req.call()?).map(|_| ())
+    }
+
+    pub fn login(&self, req: &LoginRequest) -> Result<LoginResponse, CliError> {
+        self.post("/auth/login", req)?
+            .ok_or_else(|| CliError::Http("応答にトークンが含まれていません".into()))
+    }
+
+    pub fn list_timers(&self) -> Result<Vec<TimerConfig>, CliError> {
+        Ok(self.get("/timers")?.unwrap_or_default())
+    }
+
+    pub fn create_timer(&self, timer: &TimerConfig) -> Result<TimerConfig, CliError> {
+        self.post("/timers", timer)?
+            .ok_or_else(|| CliError::Http("応答にタイマーが含まれていません".into()))
+    }
+
+    pub fn delete_timer(&self, id: &str) -> Result<(), CliError> {
+        self.delete(&format!("/timers/{}", id))
+    }
+
+    /// サーバーのタイマーの検証ルール
+    pub fn validation_policy(&self) -> Result<ValidationPolicy, CliError> {
+        Ok(self.get("/validation-policy")?.unwrap_or_default())
+    }
+
+    /// 手元で検証したエラーを、サーバーの応答と同じ言語の文言にする
+    pub fn localized(&self, mut errors: Vec<ValidationError>) -> CliError {
+        let locale = self.locale.unwrap_or_default();
+        for e in &mut errors {
+            e.message = e.render(locale);
+        }
+        CliError::Api(errors)
+    }
+
+    /// ID または名前でタイマーを探す
+    pub fn find_timer(&self, key: &str) -> Result<TimerConfig, CliError> {
+        let timers = self.list_timers()?;
+        timers
+            .iter()
+            .find(|t| t.id == key)
+            .or_else(|| timers.iter().find(|t| t.name == key))
+            .cloned()
+            .ok_or_else(|| {
+                let mut error =
+                    ValidationError::new("id", ErrorCode::NotFound { resource: Resource::Timer });
+                error.message = format!("{}: {}", error.render(self.locale.unwrap_or_default()), key);
+                CliError::Api(vec![error])
+            })
+    }
+}
@@ -0,0 +1,74 @@
+//! 画面なしでタイマーを実行し、実行イベントを1行1 JSON（NDJSON）で標準出力に書く。
+//! イベントの形式は API の RunEvent と同じ（`type` で種類を区別）に、経過秒数と時刻を加えたもの。
+//! SIGINT / SIGTERM を受けたら Aborted を書いて終了する。
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use nekotimer_shared::{RunEngine, RunEvent, RunStatus, TimerConfig};
+use serde::Serialize;
+
+const TICK: Duration = Duration::from_secs(1);
+/// 中断要求を確認する間隔
+const POLL: Duration = Duration::from_millis(100);
+
+#[derive(Serialize)]
+struct EventLine<'a> {
+    #[serde(flatten)]
+    event: &'a RunEvent,
+    /// 実行開始からの経過秒数
+    elapsed_secs: u64,
+    /// 出力時刻（UNIX ミリ秒）
+    timestamp_ms: u128,
+}
+
+fn emit(out: &mut impl Write, engine: &RunEngine, events: &[RunEvent]) -> io::Result<()> {
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    for event in events {
+        let line = EventLine {
+            event,
+            elapsed_secs: engine.progress().elapsed_secs,
+            timestamp_ms,
+        };
+        serde_json::to_writer(&mut *out, &line)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+/// タイマーを最後まで（または中断されるまで）実行し、終了時の状態を返す
+pub fn run(timer: TimerConfig) -> io::Result<RunStatus> {
+    let interrupted = Arc::new(AtomicBool::new(false));
+    for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+        signal_hook::flag::register(signal, Arc::clone(&interrupted))?;
+    }
+
+    let mut out = io::stdout().lock();
+    let mut engine = RunEngine::new(timer);
+    let events = engine.start();
+    emit(&mut out, &engine, &events)?;
+    let mut next_tick = Instant::now() + TICK;
+
+    while !engine.is_finished() {
+        if interrupted.load(Ordering::Relaxed) {
+            let events = engine.abort();
+            emit(&mut out, &engine, &events)?;
+            break;
+        }
+        let now = Instant::now();
+        if now < next_tick {
+            thread::sleep(POLL.min(next_tick - now));
+            continue;
+        }
+        let events = engine.tick();
+        next_tick += TICK;
+        emit(&mut out, &engine, &events)?;
+    }
+    Ok(engine.status())
+}
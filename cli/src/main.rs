@@ -0,0 +1,353 @@
+//! nekotimer のコマンドラインクライアント。バックエンドの REST API を操作する。
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use nekotimer_shared::{LoginRequest, RunStatus, TimerBlock, TimerConfig, TimerConfigFile};
+use serde::Serialize;
+
+mod client;
+mod headless;
+mod table;
+mod tui;
+
+use client::{CliError, Client};
+use table::{format_duration, Table};
+
+const DEFAULT_SERVER: &str = "http://127.0.0.1:14990";
+/// run で中止（q キー・SIGINT など）したときの終了コード
+const EXIT_ABORTED: u8 = 2;
+
+#[derive(Parser)]
+#[command(name = "nekotimer", version, about = "nekotimer のタイマーを端末から操作します")]
+struct Cli {
+    /// バックエンドの URL
+    #[arg(long, global = true, env = "NEKOTIMER_SERVER", default_value = DEFAULT_SERVER)]
+    server: String,
+    /// API トークン（画面の「トークン」で作成）または `nekotimer login` で得たトークン
+    #[arg(long, global = true, env = "NEKOTIMER_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+    /// 表ではなく JSON で出力する
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// ログインしてトークンを表示する（NEKOTIMER_TOKEN に設定して使う）
+    Login {
+        username: String,
+        /// パスワードを標準入力の1行目から読む
+        #[arg(long)]
+        password_stdin: bool,
+    },
+    /// タイマーの一覧
+    List,
+    /// タイマーの詳細（ブロック構成）
+    Show {
+        /// タイマー名または ID
+        timer: String,
+    },
+    /// JSON ファイルからタイマーを作成する（export の出力もそのまま読める）
+    Create {
+        /// JSON ファイル。`-` で標準入力
+        #[arg(short, long)]
+        file: PathBuf,
+    },
+    /// タイマーを削除する
+    Delete {
+        /// タイマー名または ID
+        timer: String,
+        /// 確認せずに削除する
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// タイマーを JSON で書き出す
+    Export {
+        /// 書き出すタイマー名または ID。省略時はすべて
+        timer: Option<String>,
+        /// 出力先ファイル。省略時は標準出力
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// タイマーを端末で実行する（サーバーには実行を記録しない）
+    #[command(after_help = "終了コード: 0 = 完了, 1 = エラー, 2 = 中止")]
+    Run {
+        /// タイマー名または ID。--file 指定時はファイル内の名前
+        timer: Option<String>,
+        /// サーバーではなく JSON ファイルからタイマーを読む
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        /// 画面を出さず、実行イベントを NDJSON で標準出力に書く
+        #[arg(long)]
+        headless: bool,
+    },
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), CliError> {
+    writeln!(io::stdout(), "{}", serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+fn block_kind(block: &TimerBlock) -> &'static str {
+    match block {
+        TimerBlock::Wait(_) => "待機",
+        TimerBlock::Countdown(_) => "カウントダウン",
+    }
+}
+
+/// 例: 「1分30秒」「30秒 × 3回（間隔 10秒）」
+fn block_detail(block: &TimerBlock) -> String {
+    let mmss = |m: u32, s: u32| match (m, s) {
+        (0, s) => format!("{}秒", s),
+        (m, 0) => format!("{}分", m),
+        (m, s) => format!("{}分{}秒", m, s),
+    };
+    match block {
+        TimerBlock::Wait(w) => mmss(w.minutes, w.seconds),
+        TimerBlock::Countdown(c) => {
+            let mut detail = format!("{} × {}回", mmss(c.minutes, c.seconds), c.repeat_count.max(1));
+            if c.repeat_count > 1 {
+                detail.push_str(&format!("（間隔 {}）", mmss(c.interval_minutes, c.interval_seconds)));
+            }
+            detail
+        }
+    }
+}
+
+fn login(client: &Client, username: &str, password_stdin: bool, json: bool) -> Result<(), CliError> {
+    let password = if password_stdin {
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+        line.trim_end_matches(['\r', '\n']).to_string()
+    } else {
+        rpassword::prompt_password("パスワード: ")?
+    };
+    let resp = client.login(&LoginRequest {
+        username: username.to_string(),
+        password,
+    })?;
+    if json {
+        return print_json(&resp);
+    }
+    writeln!(io::stdout(), "{}", resp.token)?;
+    Ok(())
+}
+
+fn list(client: &Client, json: bool) -> Result<(), CliError> {
+    let timers = client.list_timers()?;
+    if json {
+        return print_json(&timers);
+    }
+    if timers.is_empty() {
+        println!("タイマーはありません");
+        return Ok(());
+    }
+    let mut table = Table::new(&["名前", "ブロック", "所要時間", "ID"]);
+    for t in &timers {
+        table.row(vec![
+            t.name.clone(),
+            t.blocks.len().to_string(),
+            format_duration(t.total_secs()),
+            t.id.clone(),
+        ]);
+    }
+    table.print()?;
+    Ok(())
+}
+
+fn show(client: &Client, key: &str, json: bool) -> Result<(), CliError> {
+    let timer = client.find_timer(key)?;
+    if json {
+        return print_json(&timer);
+    }
+    println!("{}  ({})", timer.name, timer.id);
+    println!("所要時間: {}", format_duration(timer.total_secs()));
+    println!();
+    let mut table = Table::new(&["#", "種類", "名前", "内容"]);
+    for (ix, block) in timer.blocks.iter().enumerate() {
+        table.row(vec![
+            (ix + 1).to_string(),
+            block_kind(block).to_string(),
+            block.name().to_string(),
+            block_detail(block),
+        ]);
+    }
+    table.print()?;
+    Ok(())
+}
+
+/// 1件のタイマー・タイマーの配列・設定ファイル形式（{"timers": [...]}) のいずれかを読む
+fn read_timers(file: &PathBuf) -> Result<Vec<TimerConfig>, CliError> {
+    let contents = if file.as_os_str() == "-" {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        fs::read_to_string(file)?
+    };
+    let value: serde_json::Value = serde_json::from_str(&contents)?;
+    let timers = if value.get("timers").is_some() {
+        serde_json::from_value::<TimerConfigFile>(value)?.timers
+    } else if value.is_array() {
+        serde_json::from_value(value)?
+    } else {
+        vec![serde_json::from_value(value)?]
+    };
+    Ok(timers)
+}
+
+fn create(client: &Client, file: &PathBuf, json: bool) -> Result<(), CliError> {
+    let timers = read_timers(file)?;
+    let mut created = Vec::new();
+    for timer in timers {
+        match client.create_timer(&timer) {
+            Ok(t) => {
+                if !json {
+                    println!("作成しました: {} ({})", t.name, t.id);
+                }
+                created.push(t);
+            }
+            Err(CliError::Api(errors)) => {
+                // どのタイマーで失敗したか分かるように名前を添える
+                let errors = errors
+                    .into_iter()
+                    .map(|mut e| {
+                        e.message = format!("「{}」: {}", timer.name, e.message);
+                        e
+                    })
+                    .collect();
+                return Err(CliError::Api(errors));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if json {
+        print_json(&created)?;
+    }
+    Ok(())
+}
+
+fn confirm(prompt: &str) -> Result<bool, CliError> {
+    print!("{} [y/N]: ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn delete(client: &Client, key: &str, yes: bool, json: bool) -> Result<(), CliError> {
+    let timer = client.find_timer(key)?;
+    if !yes && !confirm(&format!("タイマー「{}」を削除しますか?", timer.name))? {
+        return Err(CliError::Usage("中止しました".into()));
+    }
+    client.delete_timer(&timer.id)?;
+    if json {
+        print_json(&timer)
+    } else {
+        println!("削除しました: {} ({})", timer.name, timer.id);
+        Ok(())
+    }
+}
+
+fn export(client: &Client, key: Option<&str>, output: Option<&PathBuf>) -> Result<(), CliError> {
+    let timers = match key {
+        Some(key) => vec![client.find_timer(key)?],
+        None => client.list_timers()?,
+    };
+    let json = serde_json::to_string_pretty(&TimerConfigFile { timers })?;
+    match output {
+        Some(path) => fs::write(path, json + "\n")?,
+        None => writeln!(io::stdout(), "{}", json)?,
+    }
+    Ok(())
+}
+
+/// 実行するタイマーをサーバーまたはファイルから決める
+fn load_run_timer(client: &Client, key: Option<&str>, file: Option<&PathBuf>) -> Result<TimerConfig, CliError> {
+    let Some(file) = file else {
+        let key = key.ok_or_else(|| CliError::Usage("タイマー名または --file を指定してください".into()))?;
+        return client.find_timer(key);
+    };
+    let timers = read_timers(file)?;
+    let timer = match key {
+        Some(key) => timers.into_iter().find(|t| t.id == key || t.name == key).ok_or_else(|| {
+            CliError::Usage(format!("ファイルにタイマーがありません: {}", key))
+        })?,
+        None if timers.len() == 1 => timers.into_iter().next().unwrap(),
+        None => {
+            return Err(CliError::Usage(
+                "ファイルに複数のタイマーがあります。タイマー名を指定してください".into(),
+            ))
+        }
+    };
+    // ファイルからの実行はオフラインでもできるよう、サーバーに繋がらなければ既定のルールで検証する
+    let policy = client.validation_policy().unwrap_or_default();
+    timer.validate(&policy).map_err(|errors| client.localized(errors))?;
+    Ok(timer)
+}
+
+fn run(
+    client: &Client,
+    key: Option<&str>,
+    file: Option<&PathBuf>,
+    headless: bool,
+) -> Result<ExitCode, CliError> {
+    let timer = load_run_timer(client, key, file)?;
+    let name = timer.name.clone();
+    let status = if headless {
+        headless::run(timer)?
+    } else {
+        let status = tui::run(timer)?;
+        match status {
+            RunStatus::Completed => println!("完了しました: {}", name),
+            _ => println!("中止しました: {}", name),
+        }
+        status
+    };
+    Ok(match status {
+        RunStatus::Completed => ExitCode::SUCCESS,
+        _ => ExitCode::from(EXIT_ABORTED),
+    })
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let client = Client::new(&cli.server, cli.token.clone());
+
+    let result = match &cli.command {
+        Command::Login {
+            username,
+            password_stdin,
+        } => login(&client, username, *password_stdin, cli.json).map(|()| ExitCode::SUCCESS),
+        Command::List => list(&client, cli.json).map(|()| ExitCode::SUCCESS),
+        Command::Show { timer } => show(&client, timer, cli.json).map(|()| ExitCode::SUCCESS),
+        Command::Create { file } => create(&client, file, cli.json).map(|()| ExitCode::SUCCESS),
+        Command::Delete { timer, yes } => {
+            delete(&client, timer, *yes, cli.json).map(|()| ExitCode::SUCCESS)
+        }
+        Command::Export { timer, output } => {
+            export(&client, timer.as_deref(), output.as_ref()).map(|()| ExitCode::SUCCESS)
+        }
+        Command::Run { timer, file, headless } => {
+            run(&client, timer.as_deref(), file.as_ref(), *headless)
+        }
+    };
+
+    match result {
+        Ok(code) => code,
+        // `| head` などで出力先が閉じられた場合は正常終了扱い
+        Err(CliError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            for line in e.to_string().lines() {
+                eprintln!("エラー: {}", line);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! 端末向けの簡易テーブル表示（全角文字の幅を考慮して揃える）
+
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    pub fn print(&self) -> io::Result<()> {
+        let mut out = io::stdout().lock();
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|col| {
+                std::iter::once(&self.headers)
+                    .chain(self.rows.iter())
+                    .filter_map(|row| row.get(col))
+                    .map(|cell| cell.width())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let line = |cells: &[String]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, w)| format!("{}{}", cell, " ".repeat(w - cell.width())))
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        };
+
+        writeln!(out, "{}", line(&self.headers))?;
+        let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        writeln!(out, "{}", rule.join("  "))?;
+        for row in &self.rows {
+            writeln!(out, "{}", line(row))?;
+        }
+        Ok(())
+    }
+}
+
+/// 所要時間: 1時間未満は「x分y秒」、それ以上は「x時間y分」
+pub fn format_duration(secs: u64) -> String {
+    let hours = secs / 3600;
+    let minutes = secs % 3600 / 60;
+    if hours > 0 {
+        format!("{}時間{}分", hours, minutes)
+    } else {
+        format!("{}分{}秒", minutes, secs % 60)
+    }
+}
@@ -0,0 +1,213 @@
+//! 端末上でタイマーを実行する。進行は shared の RunEngine（Web 版と同じ規則）で、
+//! 効果音の代わりに端末ベルを鳴らす。
+
+use std::io::{self, Stdout, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, queue};
+use nekotimer_shared::{
+    sound_events, CountdownPhase, RunEngine, RunEvent, RunStatus, SoundEvent, TimerConfig,
+};
+
+use crate::table::format_duration;
+
+const TICK: Duration = Duration::from_secs(1);
+
+/// 大きな数字のフォント（5行）。DIGITS[0..=9] と ':'
+const FONT_HEIGHT: usize = 5;
+const DIGITS: [[&str; FONT_HEIGHT]; 10] = [
+    ["█████", "█   █", "█   █", "█   █", "█████"],
+    ["    █", "    █", "    █", "    █", "    █"],
+    ["█████", "    █", "█████", "█    ", "█████"],
+    ["█████", "    █", "█████", "    █", "█████"],
+    ["█   █", "█   █", "█████", "    █", "    █"],
+    ["█████", "█    ", "█████", "    █", "█████"],
+    ["█████", "█    ", "█████", "█   █", "█████"],
+    ["█████", "    █", "    █", "    █", "    █"],
+    ["█████", "█   █", "█████", "█   █", "█████"],
+    ["█████", "█   █", "█████", "    █", "█████"],
+];
+const COLON: [&str; FONT_HEIGHT] = ["   ", " █ ", "   ", " █ ", "   "];
+
+/// 端末を raw モード・代替画面にし、drop 時に必ず元に戻す
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter(out: &mut Stdout) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        queue!(out, EnterAlternateScreen, cursor::Hide)?;
+        out.flush()?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let mut out = io::stdout();
+        let _ = queue!(out, cursor::Show, LeaveAlternateScreen);
+        let _ = out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// "MM:SS"（1時間以上は "H:MM:SS"）を大きな数字の5行にする
+fn big_time(secs: u32) -> Vec<String> {
+    let text = if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    };
+    (0..FONT_HEIGHT)
+        .map(|row| {
+            text.chars()
+                .map(|ch| match ch.to_digit(10) {
+                    Some(d) => DIGITS[d as usize][row],
+                    None => COLON[row],
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+fn phase_line(engine: &RunEngine) -> String {
+    let progress = engine.progress();
+    match (progress.countdown_phase, progress.countdown_run) {
+        (Some(CountdownPhase::Countdown), Some((cur, tot))) => format!("カウントダウン  {}/{}回目", cur, tot),
+        (Some(CountdownPhase::Interval), Some((cur, tot))) => format!("インターバル  {}/{}回目の後", cur, tot),
+        _ => "待機".to_string(),
+    }
+}
+
+fn status_line(engine: &RunEngine) -> &'static str {
+    match engine.status() {
+        RunStatus::Running => "",
+        RunStatus::Paused => "［一時停止中］",
+        RunStatus::Completed => "［完了］",
+        RunStatus::Aborted => "［中止］",
+    }
+}
+
+fn render(out: &mut Stdout, engine: &RunEngine) -> io::Result<()> {
+    let timer = engine.timer();
+    let progress = engine.progress();
+    let mut lines: Vec<(String, bool)> = vec![
+        (format!("{}  {}", timer.name, status_line(engine)), true),
+        (String::new(), false),
+    ];
+    lines.extend(big_time(progress.remaining_secs).into_iter().map(|l| (format!("  {}", l), false)));
+    lines.push((String::new(), false));
+    lines.push((format!("  {}", phase_line(engine)), true));
+    lines.push((String::new(), false));
+    for (ix, block) in timer.blocks.iter().enumerate() {
+        let current = ix == progress.block_ix && !engine.is_finished();
+        let done = ix < progress.block_ix || engine.status() == RunStatus::Completed;
+        let mark = if current { "▶" } else if done { "✓" } else { " " };
+        lines.push((
+            format!(" {} {:>2}. {}  ({})", mark, ix + 1, block.name(), format_duration(block.total_secs())),
+            current,
+        ));
+    }
+    lines.push((String::new(), false));
+    lines.push((
+        if engine.status() == RunStatus::Completed {
+            "何かキーを押すと終了します".to_string()
+        } else {
+            "space: 一時停止/再開   s: スキップ   q: 中止".to_string()
+        },
+        false,
+    ));
+
+    queue!(out, Clear(ClearType::All))?;
+    for (row, (text, bold)) in lines.iter().enumerate() {
+        queue!(out, cursor::MoveTo(0, row as u16))?;
+        if *bold {
+            queue!(out, SetAttribute(Attribute::Bold), Print(text), SetAttribute(Attribute::Reset))?;
+        } else {
+            queue!(out, Print(text))?;
+        }
+    }
+    out.flush()
+}
+
+/// Web 版で効果音を鳴らす場面で端末ベルを鳴らす。
+/// ブロックの開始は鳴らさない（前のブロックの終了と同時で、最初のブロックは自分で始めたところなので）
+fn ring(out: &mut Stdout, timer: &TimerConfig, events: &[RunEvent]) -> io::Result<()> {
+    let sounds = sound_events(events, timer.tick_under_secs());
+    if sounds.iter().any(|(_, event)| *event != SoundEvent::BlockStart) {
+        queue!(out, Print('\x07'))?;
+    }
+    Ok(())
+}
+
+fn is_quit(key: &KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+        || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+/// 端末 UI でタイマーを実行し、終了時の状態を返す
+pub fn run(timer: TimerConfig) -> io::Result<RunStatus> {
+    let mut out = io::stdout();
+    let _guard = TerminalGuard::enter(&mut out)?;
+
+    let mut engine = RunEngine::new(timer);
+    let events = engine.start();
+    ring(&mut out, engine.timer(), &events)?;
+    render(&mut out, &engine)?;
+    let mut next_tick = Instant::now() + TICK;
+
+    while !engine.is_finished() {
+        let wait = next_tick.saturating_duration_since(Instant::now());
+        if event::poll(wait)? {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let events = if is_quit(&key) {
+                engine.abort()
+            } else {
+                match key.code {
+                    KeyCode::Char(' ') | KeyCode::Char('p') => match engine.status() {
+                        RunStatus::Paused => {
+                            next_tick = Instant::now() + TICK;
+                            engine.resume()
+                        }
+                        _ => engine.pause(),
+                    },
+                    KeyCode::Char('s') | KeyCode::Right => {
+                        next_tick = Instant::now() + TICK;
+                        engine.skip()
+                    }
+                    _ => continue,
+                }
+            };
+            ring(&mut out, engine.timer(), &events)?;
+            render(&mut out, &engine)?;
+            continue;
+        }
+
+        if engine.status() == RunStatus::Paused {
+            next_tick = Instant::now() + TICK;
+            continue;
+        }
+        let events = engine.tick();
+        next_tick += TICK;
+        ring(&mut out, engine.timer(), &events)?;
+        render(&mut out, &engine)?;
+    }
+
+    // 完了時は結果を見せてからキー入力で閉じる
+    while engine.status() == RunStatus::Completed {
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                break;
+            }
+        }
+    }
+    Ok(engine.status())
+}
@@ -2,20 +2,26 @@ use yew::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
 use crate::components::content_pane::ContentPane;
+use crate::components::login_view::LoginView;
 use crate::components::sidebar::Sidebar;
 use crate::components::running_timer_modal::RunningTimerModal;
 use crate::components::unsaved_changes_modal::UnsavedChangesModal;
 use crate::services::api;
+use crate::services::locale::{apply_document_lang, current_locale, LocaleContext};
+use crate::services::run_events;
+use crate::services::sound_preferences;
 use crate::services::timer_runner;
-use crate::state::{AppAction, AppState, AppStateContext};
+use crate::state::{AppAction, AppState, AppStateContext, ViewMode};
 
 #[function_component(App)]
 pub fn app() -> Html {
     let state = use_reducer(AppState::default);
+    let locale = use_state(current_locale);
 
     {
-        use_effect_with((), |_| {
-            timer_runner::init_audio_cache();
+        let locale = *locale;
+        use_effect_with((), move |_| {
+            apply_document_lang(locale);
             || ()
         });
     }
@@ -23,30 +29,97 @@ pub fn app() -> Html {
         let state = state.clone();
         use_effect_with((), move |_| {
             spawn_local(async move {
-                match api::fetch_timers().await {
-                    Ok(timers) => state.dispatch(AppAction::SetTimers(timers)),
-                    Err(e) => log::error!("Failed to fetch timers: {}", e),
-                }
+                state.dispatch(AppAction::SetUser(api::fetch_me().await.ok()));
             });
             || ()
         });
     }
 
+    // ログインしたら検証ルール・ワークスペース・タイマー一覧の取得と実行イベントの購読を始める。
+    // 効果音の音量の設定も利用者ごとのものに切り替える
+    let user_id = state.user.as_ref().map(|u| u.id.clone());
+    {
+        let state = state.clone();
+        use_effect_with(user_id, move |user_id| {
+            sound_preferences::set_user(user_id.clone());
+            let unsubscribe = user_id.as_ref().and_then(|_| {
+                let state_for_fetch = state.clone();
+                spawn_local(async move {
+                    match api::fetch_sound_profiles().await {
+                        Ok(profiles) => state_for_fetch.dispatch(AppAction::SetSoundProfiles(profiles)),
+                        Err(e) => log::error!("Failed to fetch sound profiles: {}", e),
+                    }
+                    match api::fetch_custom_sounds().await {
+                        Ok(sounds) => state_for_fetch.dispatch(AppAction::SetCustomSounds(sounds)),
+                        Err(e) => log::error!("Failed to fetch custom sounds: {}", e),
+                    }
+                    match api::fetch_validation_policy().await {
+                        Ok(policy) => state_for_fetch.dispatch(AppAction::SetValidationPolicy(policy)),
+                        Err(e) => log::error!("Failed to fetch validation policy: {}", e),
+                    }
+                    match api::fetch_workspaces().await {
+                        Ok(workspaces) => state_for_fetch.dispatch(AppAction::SetWorkspaces(workspaces)),
+                        Err(e) => log::error!("Failed to fetch workspaces: {}", e),
+                    }
+                    match api::fetch_timers().await {
+                        Ok(timers) => state_for_fetch.dispatch(AppAction::SetTimers(timers)),
+                        Err(e) => log::error!("Failed to fetch timers: {}", e),
+                    }
+                });
+                run_events::subscribe(state)
+            });
+            move || {
+                if let Some(unsubscribe) = unsubscribe {
+                    unsubscribe();
+                }
+            }
+        });
+    }
+
+    // 選択中のタイマー（編集・表示）で鳴る効果音だけをプリロードする
+    let selected_timer = match &state.view {
+        ViewMode::ViewTimer(id) => state.timers.iter().find(|t| t.id == *id).cloned(),
+        ViewMode::Builder => state.editing_timer.clone(),
+        _ => None,
+    };
+    use_effect_with((selected_timer, state.sound_profiles.clone()), |(timer, profiles)| {
+        timer_runner::set_sound_profiles(profiles.clone());
+        if let Some(timer) = timer {
+            timer_runner::init_audio_cache(timer);
+        }
+        || ()
+    });
+
+    if !state.auth_checked {
+        return html! {};
+    }
+    if state.user.is_none() {
+        return html! {
+            <ContextProvider<LocaleContext> context={locale}>
+                <ContextProvider<AppStateContext> context={state}>
+                    <LoginView />
+                </ContextProvider<AppStateContext>>
+            </ContextProvider<LocaleContext>>
+        };
+    }
+
     let show_unsaved_modal = state.pending_navigation.is_some();
     let show_running_modal = state.running.is_some();
 
     html! {
-        <ContextProvider<AppStateContext> context={state}>
-            <div class="app-container">
-                <Sidebar />
-                <ContentPane />
-                if show_unsaved_modal {
-                    <UnsavedChangesModal />
-                }
-                if show_running_modal {
-                    <RunningTimerModal />
-                }
-            </div>
-        </ContextProvider<AppStateContext>>
+        <ContextProvider<LocaleContext> context={locale}>
+            <ContextProvider<AppStateContext> context={state}>
+                <div class="app-container">
+                    <Sidebar />
+                    <ContentPane />
+                    if show_unsaved_modal {
+                        <UnsavedChangesModal />
+                    }
+                    if show_running_modal {
+                        <RunningTimerModal />
+                    }
+                </div>
+            </ContextProvider<AppStateContext>>
+        </ContextProvider<LocaleContext>>
     }
 }
@@ -1,16 +1,26 @@
 use web_sys::DragEvent;
 use yew::prelude::*;
 
-use nekotimer_shared::{CountdownBlock, TimerBlock, WaitBlock};
+use nekotimer_shared::{CountdownBlock, SoundSet, TimerBlock, ValidationError, WaitBlock};
 
 use crate::components::block_drop_zone::BlockDropZone;
 use crate::components::countdown_block::CountdownBlockEditor;
 use crate::components::wait_block::WaitBlockEditor;
+use crate::i18n::t;
+use crate::services::locale::use_locale;
 use crate::state::{AppAction, AppStateContext};
 
+#[derive(Properties, PartialEq)]
+pub struct BlockCanvasProps {
+    /// 入力中のエラー（ブロックの項目のものを各エディタに渡す）
+    #[prop_or_default]
+    pub errors: Vec<ValidationError>,
+}
+
 #[function_component(BlockCanvas)]
-pub fn block_canvas() -> Html {
+pub fn block_canvas(props: &BlockCanvasProps) -> Html {
     let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
 
     let blocks = match &state.editing_timer {
         Some(t) => t.blocks.clone(),
@@ -35,7 +45,7 @@ pub fn block_canvas() -> Html {
 
     html! {
         <div class="block-canvas">
-            <h3 class="canvas-title">{"ブロック"}</h3>
+            <h3 class="canvas-title">{ t(locale, "canvas.title") }</h3>
 
             <div class="block-list">
                 <BlockDropZone index={0} />
@@ -69,6 +79,16 @@ pub fn block_canvas() -> Html {
                     };
 
                     let can_remove = blocks.len() > 1;
+                    let errors: Vec<ValidationError> = props
+                        .errors
+                        .iter()
+                        .filter(|e| e.block_field().is_some_and(|(b, _)| b == i))
+                        .cloned()
+                        .collect();
+                    let block_class = classes!(
+                        block_class,
+                        (!errors.is_empty()).then_some("has-errors")
+                    );
 
                     html! {
                         <>
@@ -93,10 +113,12 @@ pub fn block_canvas() -> Html {
                                 </div>
                                 { match block {
                                     TimerBlock::Wait(w) => html! {
-                                        <WaitBlockEditor block={w.clone()} on_change={on_update.clone()} />
+                                        <WaitBlockEditor block={w.clone()} index={i} errors={errors.clone()}
+                                            on_change={on_update.clone()} />
                                     },
                                     TimerBlock::Countdown(c) => html! {
-                                        <CountdownBlockEditor block={c.clone()} on_change={on_update.clone()} />
+                                        <CountdownBlockEditor block={c.clone()} index={i} errors={errors.clone()}
+                                            on_change={on_update.clone()} />
                                     },
                                 }}
                             </div>
@@ -108,10 +130,10 @@ pub fn block_canvas() -> Html {
 
             <div class="block-palette">
                 <div class="palette-item" draggable="true" ondragstart={on_palette_drag_wait}>
-                    {"+ 待機ブロック"}
+                    { t(locale, "canvas.add_wait") }
                 </div>
                 <div class="palette-item" draggable="true" ondragstart={on_palette_drag_countdown}>
-                    {"+ カウントダウンブロック"}
+                    { t(locale, "canvas.add_countdown") }
                 </div>
             </div>
         </div>
@@ -123,6 +145,8 @@ pub fn default_wait_block() -> TimerBlock {
         name: String::new(),
         minutes: 0,
         seconds: 10,
+        sounds: SoundSet::default(),
+        announcement: None,
     })
 }
 
@@ -134,5 +158,7 @@ pub fn default_countdown_block() -> TimerBlock {
         repeat_count: 1,
         interval_minutes: 0,
         interval_seconds: 10,
+        sounds: SoundSet::default(),
+        announcement: None,
     })
 }
@@ -1,7 +1,14 @@
 use yew::prelude::*;
+use crate::components::history_view::HistoryView;
 use crate::components::no_content::NoContent;
+use crate::components::sound_library_view::SoundLibraryView;
+use crate::components::sound_profile_view::SoundProfileView;
+use crate::components::speech_settings_view::SpeechSettingsView;
 use crate::components::timer_builder::TimerBuilder;
 use crate::components::timer_view::TimerView;
+use crate::components::token_view::TokenView;
+use crate::components::webhook_view::WebhookView;
+use crate::components::workspace_view::WorkspaceView;
 use crate::state::{AppStateContext, ViewMode};
 
 #[function_component(ContentPane)]
@@ -18,6 +25,13 @@ pub fn content_pane() -> Html {
                 html! { <NoContent /> }
             }
         }
+        ViewMode::History => html! { <HistoryView /> },
+        ViewMode::Webhooks => html! { <WebhookView /> },
+        ViewMode::Tokens => html! { <TokenView /> },
+        ViewMode::Workspaces => html! { <WorkspaceView /> },
+        ViewMode::SoundProfiles => html! { <SoundProfileView /> },
+        ViewMode::SoundLibrary => html! { <SoundLibraryView /> },
+        ViewMode::Speech => html! { <SpeechSettingsView /> },
     };
 
     html! {
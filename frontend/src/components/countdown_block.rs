@@ -1,11 +1,25 @@
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use nekotimer_shared::{CountdownBlock, TimerBlock};
+use nekotimer_shared::{
+    CountdownBlock, SoundEvent, SoundSet, TimerBlock, ValidationError, ANNOUNCEMENT_MAX_LEN,
+};
+
+use crate::components::sound_set_editor::SoundSetEditor;
+
+use crate::i18n::t;
+use crate::services::locale::use_locale;
+use crate::services::validation::field_message;
+use crate::state::AppStateContext;
 
 #[derive(Properties, PartialEq)]
 pub struct CountdownBlockProps {
     pub block: CountdownBlock,
+    /// ブロックの位置（エラーの項目名 `blocks[i].seconds` に使う）
+    pub index: usize,
+    /// 入力中のエラー
+    #[prop_or_default]
+    pub errors: Vec<ValidationError>,
     pub on_change: Callback<TimerBlock>,
 }
 
@@ -13,6 +27,9 @@ pub struct CountdownBlockProps {
 pub fn countdown_block_editor(props: &CountdownBlockProps) -> Html {
     let block = props.block.clone();
     let on_change = props.on_change.clone();
+    let locale = use_locale();
+    let state = use_context::<AppStateContext>().expect("no context found");
+    let policy = &state.validation_policy;
 
     let make_handler = |field: &'static str| {
         let block = block.clone();
@@ -33,42 +50,84 @@ pub fn countdown_block_editor(props: &CountdownBlockProps) -> Html {
         })
     };
 
+    let on_sounds = {
+        let block = block.clone();
+        let on_change = on_change.clone();
+        Callback::from(move |sounds: SoundSet| {
+            let mut b = block.clone();
+            b.sounds = sounds;
+            on_change.emit(TimerBlock::Countdown(b));
+        })
+    };
+
+    // 空欄なら読み上げの設定のテンプレートを使う
+    let on_announcement = {
+        let block = block.clone();
+        let on_change = on_change.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut b = block.clone();
+            let value = input.value();
+            b.announcement = (!value.trim().is_empty()).then_some(value);
+            on_change.emit(TimerBlock::Countdown(b));
+        })
+    };
+
+    // 数値の入力欄。エラーがあれば赤枠にしてツールチップで理由を出す
+    let number_input = |name: &'static str, value: u32, min: u32, max: u32| {
+        let field = format!("blocks[{}].{}", props.index, name);
+        let error = field_message(&props.errors, &field);
+        html! {
+            <input type="number" value={value.to_string()} oninput={make_handler(name)}
+                min={min.to_string()} max={max.to_string()} data-field={field}
+                class={classes!(error.is_some().then_some("field-invalid"))}
+                title={error.clone()} />
+        }
+    };
+
     html! {
         <div class="block-fields">
             <div class="block-field">
-                <label>{"名前"}</label>
+                <label>{ t(locale, "block.name") }</label>
                 <input type="text" value={block.name.clone()} oninput={make_handler("name")}
-                    placeholder="ブロック名" />
+                    placeholder={t(locale, "block.name_placeholder")} />
             </div>
             <div class="block-field-row">
                 <div class="block-field">
-                    <label>{"分"}</label>
-                    <input type="number" value={block.minutes.to_string()} oninput={make_handler("minutes")}
-                        min="0" max="1440" />
+                    <label>{ t(locale, "block.minutes") }</label>
+                    { number_input("minutes", block.minutes, 0, policy.max_minutes) }
                 </div>
                 <div class="block-field">
-                    <label>{"秒"}</label>
-                    <input type="number" value={block.seconds.to_string()} oninput={make_handler("seconds")}
-                        min="1" max="59" />
+                    <label>{ t(locale, "block.seconds") }</label>
+                    { number_input("seconds", block.seconds, 1, 59) }
                 </div>
             </div>
             <div class="block-field">
-                <label>{"繰り返し回数"}</label>
-                <input type="number" value={block.repeat_count.to_string()} oninput={make_handler("repeat_count")}
-                    min="0" max="100" />
+                <label>{ t(locale, "block.repeat_count") }</label>
+                { number_input("repeat_count", block.repeat_count, 0, policy.max_repeat_count) }
             </div>
             <div class="block-field-row">
                 <div class="block-field">
-                    <label>{"インターバル分"}</label>
-                    <input type="number" value={block.interval_minutes.to_string()} oninput={make_handler("interval_minutes")}
-                        min="0" max="1440" />
+                    <label>{ t(locale, "block.interval_minutes") }</label>
+                    { number_input("interval_minutes", block.interval_minutes, 0, policy.max_minutes) }
                 </div>
                 <div class="block-field">
-                    <label>{"インターバル秒"}</label>
-                    <input type="number" value={block.interval_seconds.to_string()} oninput={make_handler("interval_seconds")}
-                        min="0" max="59" />
+                    <label>{ t(locale, "block.interval_seconds") }</label>
+                    { number_input("interval_seconds", block.interval_seconds, 0, 59) }
                 </div>
             </div>
+            <details class="block-sounds">
+                <summary>{ t(locale, "sound.block_summary") }</summary>
+                <SoundSetEditor sounds={block.sounds.clone()} events={SoundEvent::BLOCK.to_vec()}
+                    on_change={on_sounds} />
+                <div class="block-field block-announcement">
+                    <label>{ t(locale, "block.announcement") }</label>
+                    <input type="text" value={block.announcement.clone().unwrap_or_default()}
+                        oninput={on_announcement} maxlength={ANNOUNCEMENT_MAX_LEN.to_string()}
+                        data-field={format!("blocks[{}].announcement", props.index)}
+                        placeholder={t(locale, "block.announcement_placeholder")} />
+                </div>
+            </details>
         </div>
     }
 }
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use nekotimer_shared::{Locale, PeriodRunStats, RunOutcome, RunRecord, RunStats, TimerBlock};
+
+use crate::i18n::{duration, t, tf};
+use crate::services::api;
+use crate::services::datetime::{
+    browser_time_zone, date_key, days_ago, format_datetime, weekday_from_monday,
+};
+use crate::services::locale::use_locale;
+use crate::state::AppStateContext;
+
+/// カレンダーに表示する週数
+const HEATMAP_WEEKS: u32 = 26;
+/// 週ごとのグラフに表示する週数
+const CHART_WEEKS: u32 = 12;
+const RECENT_RUNS_LIMIT: usize = 50;
+
+const CHART_WIDTH: f64 = 480.0;
+const CHART_HEIGHT: f64 = 140.0;
+
+fn completion_rate(completed: u32, runs: u32) -> u32 {
+    (completed * 100).checked_div(runs).unwrap_or(0)
+}
+
+fn heat_level(runs: u32) -> &'static str {
+    match runs {
+        0 => "heat-cell level-0",
+        1 => "heat-cell level-1",
+        2 => "heat-cell level-2",
+        3..=4 => "heat-cell level-3",
+        _ => "heat-cell level-4",
+    }
+}
+
+fn block_label(locale: Locale, record: &RunRecord, block_ix: usize) -> String {
+    match record.timer.blocks.get(block_ix) {
+        Some(TimerBlock::Wait(w)) if !w.name.is_empty() => w.name.clone(),
+        Some(TimerBlock::Countdown(c)) if !c.name.is_empty() => c.name.clone(),
+        _ => tf(locale, "history.block_n", &[&(block_ix + 1)]),
+    }
+}
+
+/// 中止時は「中止（ブロック名 2/3回目）」のように中止位置を添える
+fn outcome_label(locale: Locale, record: &RunRecord) -> String {
+    match record.outcome {
+        RunOutcome::Completed => t(locale, "history.completed").to_string(),
+        RunOutcome::Aborted => match record.aborted_block_ix {
+            Some(ix) => {
+                let block = block_label(locale, record, ix);
+                match record.aborted_countdown_run {
+                    Some((cur, tot)) => tf(locale, "history.aborted_at_run", &[&block, &cur, &tot]),
+                    None => tf(locale, "history.aborted_at", &[&block]),
+                }
+            }
+            None => t(locale, "history.aborted").to_string(),
+        },
+    }
+}
+
+/// 直近 HEATMAP_WEEKS 週を「月曜始まり・縦7マス×週数」で並べたカレンダー
+fn render_heatmap(locale: Locale, per_day: &[PeriodRunStats]) -> Html {
+    let counts: HashMap<&str, u32> = per_day.iter().map(|d| (d.start.as_str(), d.runs)).collect();
+    let today_offset = weekday_from_monday(&days_ago(0));
+    let total_days = (HEATMAP_WEEKS - 1) * 7 + today_offset + 1;
+
+    html! {
+        <div class="heatmap">
+            { for (0..total_days).rev().map(|ago| {
+                let date = days_ago(ago);
+                let key = date_key(&date);
+                let runs = counts.get(key.as_str()).copied().unwrap_or(0);
+                let title = tf(locale, "history.day_runs", &[&key, &runs]);
+                html! { <div class={heat_level(runs)} {title} /> }
+            })}
+        </div>
+    }
+}
+
+/// 直近 CHART_WEEKS 週の週ごとの実行回数（完了分を濃く表示）
+fn render_weekly_chart(locale: Locale, per_week: &[PeriodRunStats]) -> Html {
+    let by_week: HashMap<&str, &PeriodRunStats> =
+        per_week.iter().map(|w| (w.start.as_str(), w)).collect();
+    let this_monday = weekday_from_monday(&days_ago(0));
+    let weeks: Vec<(String, u32, u32)> = (0..CHART_WEEKS)
+        .rev()
+        .map(|w| {
+            let key = date_key(&days_ago(this_monday + w * 7));
+            let (runs, completed) = by_week
+                .get(key.as_str())
+                .map_or((0, 0), |s| (s.runs, s.completed));
+            (key, runs, completed)
+        })
+        .collect();
+    let max_runs = weeks.iter().map(|(_, r, _)| *r).max().unwrap_or(0).max(1) as f64;
+    let slot = CHART_WIDTH / CHART_WEEKS as f64;
+    let bar_width = slot * 0.7;
+
+    html! {
+        <svg class="weekly-chart" viewBox={format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT + 20.0)}>
+            { for weeks.iter().enumerate().map(|(i, (key, runs, completed))| {
+                let x = i as f64 * slot + (slot - bar_width) / 2.0;
+                let h = *runs as f64 / max_runs * CHART_HEIGHT;
+                let hc = *completed as f64 / max_runs * CHART_HEIGHT;
+                let label = key.get(5..).unwrap_or(key).replace('-', "/");
+                html! {
+                    <g>
+                        <title>{ tf(locale, "history.week_runs", &[key, runs, completed]) }</title>
+                        <rect class="bar-runs" x={x.to_string()} y={(CHART_HEIGHT - h).to_string()}
+                            width={bar_width.to_string()} height={h.to_string()} />
+                        <rect class="bar-completed" x={x.to_string()} y={(CHART_HEIGHT - hc).to_string()}
+                            width={bar_width.to_string()} height={hc.to_string()} />
+                        <text class="bar-label" x={(x + bar_width / 2.0).to_string()} y={(CHART_HEIGHT + 14.0).to_string()}>
+                            { label }
+                        </text>
+                    </g>
+                }
+            })}
+        </svg>
+    }
+}
+
+#[function_component(HistoryView)]
+pub fn history_view() -> Html {
+    let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
+    let selected_timer = use_state(|| None::<String>);
+    let stats = use_state(|| None::<RunStats>);
+    let runs = use_state(Vec::<RunRecord>::new);
+    let error = use_state(|| None::<String>);
+
+    {
+        let stats = stats.clone();
+        let runs = runs.clone();
+        let error = error.clone();
+        use_effect_with((*selected_timer).clone(), move |timer_id| {
+            let timer_id = timer_id.clone();
+            spawn_local(async move {
+                let tz = browser_time_zone();
+                let result = async {
+                    let s = api::fetch_run_stats(timer_id.as_deref(), &tz).await?;
+                    let r = api::fetch_history(timer_id.as_deref(), RECENT_RUNS_LIMIT).await?;
+                    Ok::<_, String>((s, r))
+                }
+                .await;
+                match result {
+                    Ok((s, r)) => {
+                        stats.set(Some(s));
+                        runs.set(r);
+                        error.set(None);
+                    }
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_select_timer = {
+        let selected_timer = selected_timer.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            selected_timer.set(if value.is_empty() { None } else { Some(value) });
+        })
+    };
+
+    let body = match &*stats {
+        None => html! {},
+        Some(stats) => html! {
+            <>
+                <div class="history-summary">
+                    <div class="history-card">
+                        <span class="history-card-label">{ t(locale, "history.total_runs") }</span>
+                        <span class="history-card-value">{ tf(locale, "history.runs_value", &[&stats.total_runs]) }</span>
+                    </div>
+                    <div class="history-card">
+                        <span class="history-card-label">{ t(locale, "history.completion_rate") }</span>
+                        <span class="history-card-value">
+                            { completion_rate(stats.completed_runs, stats.total_runs) }{"%"}
+                        </span>
+                    </div>
+                    <div class="history-card">
+                        <span class="history-card-label">{ t(locale, "history.total_time") }</span>
+                        <span class="history-card-value">{ duration(locale, stats.total_active_secs) }</span>
+                    </div>
+                    <div class="history-card">
+                        <span class="history-card-label">{ t(locale, "history.streak") }</span>
+                        <span class="history-card-value">
+                            { tf(locale, "history.streak_days", &[&stats.current_streak_days]) }
+                            <small>{ tf(locale, "history.longest_streak", &[&stats.longest_streak_days]) }</small>
+                        </span>
+                    </div>
+                </div>
+
+                <h3 class="history-section-title">{ t(locale, "history.calendar") }</h3>
+                { render_heatmap(locale, &stats.per_day) }
+
+                <h3 class="history-section-title">{ t(locale, "history.weekly") }</h3>
+                { render_weekly_chart(locale, &stats.per_week) }
+
+                if selected_timer.is_none() && !stats.per_timer.is_empty() {
+                    <h3 class="history-section-title">{ t(locale, "history.per_timer") }</h3>
+                    <table class="history-table">
+                        <thead>
+                            <tr>
+                                <th>{ t(locale, "history.timer") }</th>
+                                <th>{ t(locale, "history.total_runs") }</th>
+                                <th>{ t(locale, "history.completion_rate") }</th>
+                                <th>{ t(locale, "history.total_time") }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for stats.per_timer.iter().map(|timer| {
+                                let rate = completion_rate(timer.completed, timer.runs);
+                                html! {
+                                    <tr>
+                                        <td>{ &timer.timer_name }</td>
+                                        <td>{ timer.runs }</td>
+                                        <td>
+                                            <div class="rate-bar">
+                                                <div class="rate-bar-fill" style={format!("width: {}%", rate)} />
+                                            </div>
+                                            <span class="rate-value">{ rate }{"%"}</span>
+                                        </td>
+                                        <td>{ duration(locale, timer.total_active_secs) }</td>
+                                    </tr>
+                                }
+                            })}
+                        </tbody>
+                    </table>
+                }
+            </>
+        },
+    };
+
+    html! {
+        <div class="history-view">
+            <h2 class="history-title">{ t(locale, "history.title") }</h2>
+            <div class="history-filter">
+                <label for="history-timer">{ t(locale, "history.timer") }</label>
+                <select id="history-timer" onchange={on_select_timer}>
+                    <option value="" selected={selected_timer.is_none()}>{ t(locale, "history.all") }</option>
+                    { for state.timers.iter().map(|timer| html! {
+                        <option value={timer.id.clone()} selected={selected_timer.as_deref() == Some(timer.id.as_str())}>
+                            { &timer.name }
+                        </option>
+                    })}
+                </select>
+            </div>
+
+            if let Some(e) = &*error {
+                <div class="validation-errors">{ e }</div>
+            }
+
+            { body }
+
+            <h3 class="history-section-title">{ t(locale, "history.recent") }</h3>
+            if runs.is_empty() {
+                <p class="history-empty">{ t(locale, "history.empty") }</p>
+            } else {
+                <ul class="history-run-list">
+                    { for runs.iter().map(|r| {
+                        let class = match r.outcome {
+                            RunOutcome::Completed => "history-run-item completed",
+                            RunOutcome::Aborted => "history-run-item aborted",
+                        };
+                        html! {
+                            <li {class}>
+                                <span class="history-run-date">{ format_datetime(r.started_at) }</span>
+                                <span class="history-run-name">{ &r.timer.name }</span>
+                                <span class="history-run-status">{ outcome_label(locale, r) }</span>
+                                <span class="history-run-duration">{ duration(locale, r.active_secs) }</span>
+                            </li>
+                        }
+                    })}
+                </ul>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,87 @@
+use gloo_timers::callback::Interval;
+use yew::prelude::*;
+
+use nekotimer_shared::{RunEngine, RunEvent, TimerConfig};
+
+use crate::components::running_timer_modal::RunningTimerPanel;
+use crate::services::timer_runner;
+use crate::state::RunningInfo;
+
+fn play_sounds(engine: &RunEngine, events: &[RunEvent]) {
+    let (timer, status, progress) = (engine.timer(), engine.status(), engine.progress());
+    timer_runner::play_run_sounds(timer_runner::LOCAL_RUN, timer, &status, &progress, events);
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LocalRunModalProps {
+    pub timer: TimerConfig,
+    pub on_close: Callback<()>,
+}
+
+/// サーバーを使わずブラウザ内でタイマーを実行する（共有リンクの「この端末で実行」用）。
+/// 履歴・Webhook には残らない。
+#[function_component(LocalRunModal)]
+pub fn local_run_modal(props: &LocalRunModalProps) -> Html {
+    let engine = {
+        let timer = props.timer.clone();
+        use_mut_ref(move || RunEngine::new(timer))
+    };
+    let running = {
+        let engine = engine.clone();
+        use_state(move || RunningInfo::from_engine(&engine.borrow()))
+    };
+
+    {
+        let engine = engine.clone();
+        let running = running.clone();
+        use_effect_with((), move |_| {
+            let events = engine.borrow_mut().start();
+            play_sounds(&engine.borrow(), &events);
+            running.set(RunningInfo::from_engine(&engine.borrow()));
+
+            let interval = Interval::new(1000, move || {
+                let mut engine = engine.borrow_mut();
+                if engine.is_finished() {
+                    return;
+                }
+                let events = engine.tick();
+                play_sounds(&engine, &events);
+                running.set(RunningInfo::from_engine(&engine));
+            });
+            move || {
+                drop(interval);
+                timer_runner::cancel_scheduled_sounds();
+            }
+        });
+    }
+
+    let make_control = |action: fn(&mut RunEngine) -> Vec<RunEvent>| {
+        let engine = engine.clone();
+        let running = running.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut engine = engine.borrow_mut();
+            let events = action(&mut engine);
+            play_sounds(&engine, &events);
+            running.set(RunningInfo::from_engine(&engine));
+        })
+    };
+
+    let on_close = {
+        let engine = engine.clone();
+        let on_close = props.on_close.clone();
+        Callback::from(move |_: MouseEvent| {
+            engine.borrow_mut().abort();
+            on_close.emit(());
+        })
+    };
+
+    html! {
+        <RunningTimerPanel
+            running={(*running).clone()}
+            on_pause={make_control(RunEngine::pause)}
+            on_resume={make_control(RunEngine::resume)}
+            on_skip={make_control(RunEngine::skip)}
+            {on_close}
+        />
+    }
+}
@@ -0,0 +1,42 @@
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use nekotimer_shared::Locale;
+
+use crate::i18n::t;
+use crate::services::locale::{set_locale, LocaleContext};
+
+/// 言語名はどの言語で表示していても読めるようその言語自身で書く
+pub fn locale_name(locale: Locale) -> &'static str {
+    match locale {
+        Locale::Ja => "日本語",
+        Locale::En => "English",
+    }
+}
+
+/// 表示言語の切り替え
+#[function_component(LocaleSwitch)]
+pub fn locale_switch() -> Html {
+    let Some(context) = use_context::<LocaleContext>() else {
+        return html! {};
+    };
+    let current = *context;
+
+    let on_change = {
+        let context = context.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(locale) = Locale::from_tag(&select.value()) {
+                set_locale(&context, locale);
+            }
+        })
+    };
+
+    html! {
+        <select class="locale-switch" onchange={on_change} title={t(current, "locale.select")}>
+            { for Locale::ALL.iter().map(|&locale| html! {
+                <option value={locale.tag()} selected={locale == current}>{ locale_name(locale) }</option>
+            })}
+        </select>
+    }
+}
@@ -0,0 +1,86 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::components::locale_switch::LocaleSwitch;
+use crate::i18n::t;
+use crate::services::api;
+use crate::services::locale::use_locale;
+use crate::state::{AppAction, AppStateContext};
+
+/// ログイン画面（ユーザーはサーバーの管理コマンドで作成する）
+#[function_component(LoginView)]
+pub fn login_view() -> Html {
+    let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
+    let username = use_state(String::new);
+    let password = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    let submitting = use_state(|| false);
+
+    let on_username = {
+        let username = username.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            username.set(input.value());
+        })
+    };
+
+    let on_password = {
+        let password = password.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            password.set(input.value());
+        })
+    };
+
+    let on_submit = {
+        let username = username.clone();
+        let password = password.clone();
+        let error = error.clone();
+        let submitting = submitting.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let username = (*username).trim().to_string();
+            let password = (*password).clone();
+            let state = state.clone();
+            let error = error.clone();
+            let submitting = submitting.clone();
+            submitting.set(true);
+            spawn_local(async move {
+                match api::login(&username, &password).await {
+                    Ok(user) => state.dispatch(AppAction::SetUser(Some(user))),
+                    Err(e) => {
+                        error.set(Some(e));
+                        submitting.set(false);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="login-container">
+            <form class="login-form" onsubmit={on_submit}>
+                <h2>{"nekotimer"}</h2>
+                <label class="login-field">
+                    <span>{ t(locale, "login.username") }</span>
+                    <input type="text" autocomplete="username" value={(*username).clone()}
+                        oninput={on_username} />
+                </label>
+                <label class="login-field">
+                    <span>{ t(locale, "login.password") }</span>
+                    <input type="password" autocomplete="current-password" value={(*password).clone()}
+                        oninput={on_password} />
+                </label>
+                if let Some(message) = &*error {
+                    <div class="validation-errors">
+                        <ul><li>{ message }</li></ul>
+                    </div>
+                }
+                <button type="submit" class="btn btn-primary" disabled={*submitting}>{ t(locale, "login.submit") }</button>
+                <LocaleSwitch />
+            </form>
+        </div>
+    }
+}
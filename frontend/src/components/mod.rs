@@ -1,10 +1,26 @@
+pub mod login_view;
+pub mod locale_switch;
 pub mod sidebar;
 pub mod content_pane;
 pub mod no_content;
 pub mod timer_view;
+pub mod timer_transfer;
+pub mod history_view;
+pub mod schedule_editor;
+pub mod share_links;
+pub mod shared_timer_page;
+pub mod webhook_view;
+pub mod token_view;
+pub mod workspace_view;
+pub mod sound_profile_view;
+pub mod sound_library_view;
+pub mod speech_settings_view;
+pub mod sound_set_editor;
+pub mod volume_control;
 pub mod timer_builder;
 pub mod unsaved_changes_modal;
 pub mod running_timer_modal;
+pub mod local_run_modal;
 pub mod block_canvas;
 pub mod block_drop_zone;
 pub mod wait_block;
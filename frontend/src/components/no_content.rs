@@ -1,10 +1,14 @@
 use yew::prelude::*;
 
+use crate::i18n::t;
+use crate::services::locale::use_locale;
+
 #[function_component(NoContent)]
 pub fn no_content() -> Html {
+    let locale = use_locale();
     html! {
         <div class="no-content">
-            <p>{"サイドバーの「Add」ボタンを押してタイマーを作成してください"}</p>
+            <p>{ t(locale, "no_content.hint") }</p>
         </div>
     }
 }
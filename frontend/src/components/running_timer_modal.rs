@@ -1,15 +1,14 @@
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
-use nekotimer_shared::TimerBlock;
+use nekotimer_shared::{CountdownPhase, Locale, TimerBlock};
 
-use crate::state::{AppAction, AppStateContext, CountdownPhase};
-
-fn block_type_name(block: &TimerBlock) -> &'static str {
-    match block {
-        TimerBlock::Wait(_) => "待機",
-        TimerBlock::Countdown(_) => "カウントダウン",
-    }
-}
+use crate::components::timer_view::block_type_name;
+use crate::components::volume_control::VolumeControl;
+use crate::i18n::{min_sec, t, tf};
+use crate::services::locale::use_locale;
+use crate::services::{api, run_events};
+use crate::state::{AppAction, AppStateContext, RunningInfo};
 
 fn block_name(block: &TimerBlock) -> &str {
     match block {
@@ -19,12 +18,17 @@ fn block_name(block: &TimerBlock) -> &str {
 }
 
 /// ブロックの設定データ表示用。ウェイト: xx分yy秒 ウェイト。カウントダウン: xx分yy秒 カウントダウン (繰り返しz回, インターバル aa分yy秒)
-fn block_config_line(block: &TimerBlock) -> String {
+fn block_config_line(locale: Locale, block: &TimerBlock) -> String {
     match block {
-        TimerBlock::Wait(w) => format!("{}分{}秒 ウェイト", w.minutes, w.seconds),
-        TimerBlock::Countdown(c) => format!(
-            "{}分{}秒 カウントダウン (繰り返し{}回, インターバル {}分{}秒)",
-            c.minutes, c.seconds, c.repeat_count, c.interval_minutes, c.interval_seconds
+        TimerBlock::Wait(w) => tf(locale, "running.wait_line", &[&min_sec(locale, w.minutes, w.seconds)]),
+        TimerBlock::Countdown(c) => tf(
+            locale,
+            "running.countdown_line",
+            &[
+                &min_sec(locale, c.minutes, c.seconds),
+                &c.repeat_count,
+                &min_sec(locale, c.interval_minutes, c.interval_seconds),
+            ],
         ),
     }
 }
@@ -42,34 +46,89 @@ pub fn running_timer_modal() -> Html {
         None => return html! {},
     };
 
+    let session_id = running.session_id.clone();
+    let is_complete = running.is_complete;
+
     let on_close = {
         let state = state.clone();
+        let session_id = session_id.clone();
         Callback::from(move |_: MouseEvent| {
+            run_events::dismiss(&session_id);
+            if !is_complete {
+                let session_id = session_id.clone();
+                spawn_local(async move {
+                    if let Err(e) = api::control_run(&session_id, "stop").await {
+                        log::error!("Failed to stop timer: {}", e);
+                    }
+                });
+            }
             state.dispatch(AppAction::CloseRunningModal);
         })
     };
 
+    // 一時停止・再開・スキップはサーバーに送るだけ。結果は SSE で全画面に反映される。
+    let make_control = |action: &'static str| {
+        let session_id = session_id.clone();
+        Callback::from(move |_: MouseEvent| {
+            let session_id = session_id.clone();
+            spawn_local(async move {
+                if let Err(e) = api::control_run(&session_id, action).await {
+                    log::error!("Failed to {} timer: {}", action, e);
+                }
+            });
+        })
+    };
+
+    html! {
+        <RunningTimerPanel
+            {running}
+            on_pause={make_control("pause")}
+            on_resume={make_control("resume")}
+            on_skip={make_control("skip")}
+            {on_close}
+        />
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RunningTimerPanelProps {
+    pub running: RunningInfo,
+    pub on_pause: Callback<MouseEvent>,
+    pub on_resume: Callback<MouseEvent>,
+    pub on_skip: Callback<MouseEvent>,
+    /// 完了後は「閉じる」、実行中は「中止」
+    pub on_close: Callback<MouseEvent>,
+}
+
+/// 実行中モーダルの表示部分。サーバーの実行セッションとブラウザ内の実行（共有リンク）で共用する
+#[function_component(RunningTimerPanel)]
+pub fn running_timer_panel(props: &RunningTimerPanelProps) -> Html {
+    let locale = use_locale();
+    let running = &props.running;
+    let is_complete = running.is_complete;
     let current_ix = running.current_block_index;
     let remaining = running.remaining_secs;
-    let is_complete = running.is_complete;
+    let is_paused = running.is_paused;
     let countdown_run = running.countdown_run;
     let countdown_phase = running.countdown_phase.clone();
     let current_block_is_countdown = running
         .timer
         .blocks
         .get(current_ix)
-        .map_or(false, is_countdown_block);
+        .is_some_and(is_countdown_block);
 
     html! {
         <div class="modal-overlay running-timer-modal-overlay" role="dialog" aria-modal="true">
             <div class="modal-box running-timer-modal">
                 <div class="running-timer-modal-header">
-                    <h2 class="modal-title">{"タイマー実行中"}</h2>
+                    <h2 class="modal-title">
+                        { if is_paused { t(locale, "running.paused") } else { t(locale, "running.title") } }
+                    </h2>
                     <p class="running-timer-name">{ &running.timer.name }</p>
                 </div>
 
                 if is_complete {
-                    <div class="running-timer-complete">{"完了！"}</div>
+                    <div class="running-timer-complete">{ t(locale, "running.complete") }</div>
                 } else {
                     <ul class="running-timer-block-list">
                         { for running.timer.blocks.iter().enumerate().map(|(i, block)| {
@@ -85,28 +144,28 @@ pub fn running_timer_modal() -> Html {
                             let show_countdown_phase = is_current && current_block_is_countdown && countdown_run.is_some();
                             let phase_countdown_active = countdown_phase.as_ref() == Some(&CountdownPhase::Countdown);
                             let phase_interval_active = countdown_phase.as_ref() == Some(&CountdownPhase::Interval);
-                            let config_line = block_config_line(block);
+                            let config_line = block_config_line(locale, block);
                             html! {
                                 <li class={class}>
-                                    <span class="block-type">{ block_type_name(block) }</span>
+                                    <span class="block-type">{ block_type_name(locale, block) }</span>
                                     <span class="block-name">{ block_name(block) }</span>
                                     <div class="block-config">{ &config_line }</div>
                                     if is_current {
                                         if show_countdown_phase {
                                             if let Some((cur, tot)) = countdown_run {
-                                                <div class="block-countdown-run">{"現在 "}{ cur }{ " / " }{ tot }{ " 回" }</div>
+                                                <div class="block-countdown-run">{ tf(locale, "running.repetition", &[&cur, &tot]) }</div>
                                             }
                                             <div class="block-phase-row">
                                                 <span class={if phase_countdown_active { "block-phase active" } else { "block-phase" }}>
-                                                    {"カウントダウン"}
+                                                    { t(locale, "running.phase_countdown") }
                                                 </span>
                                                 <span class={if phase_interval_active { "block-phase active" } else { "block-phase" }}>
-                                                    {"インターバル"}
+                                                    { t(locale, "running.phase_interval") }
                                                 </span>
                                             </div>
                                         }
                                         <div class="block-remaining">
-                                            {"残り "}{ remaining }{ " 秒"}
+                                            { tf(locale, "running.remaining", &[&remaining]) }
                                         </div>
                                     }
                                 </li>
@@ -115,9 +174,25 @@ pub fn running_timer_modal() -> Html {
                     </ul>
                 }
 
+                <VolumeControl />
+
                 <div class="modal-actions running-timer-modal-actions">
-                    <button type="button" class="btn btn-primary" onclick={on_close}>
-                        { if is_complete { "閉じる" } else { "中止" } }
+                    if !is_complete {
+                        if is_paused {
+                            <button type="button" class="btn btn-secondary" onclick={props.on_resume.clone()}>
+                                { t(locale, "running.resume") }
+                            </button>
+                        } else {
+                            <button type="button" class="btn btn-secondary" onclick={props.on_pause.clone()}>
+                                { t(locale, "running.pause") }
+                            </button>
+                        }
+                        <button type="button" class="btn btn-secondary" onclick={props.on_skip.clone()}>
+                            { t(locale, "running.skip") }
+                        </button>
+                    }
+                    <button type="button" class="btn btn-primary" onclick={props.on_close.clone()}>
+                        { if is_complete { t(locale, "running.close") } else { t(locale, "running.stop") } }
                     </button>
                 </div>
             </div>
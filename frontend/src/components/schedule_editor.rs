@@ -0,0 +1,290 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use nekotimer_shared::{Locale, Schedule, ScheduleDay, ScheduleRule};
+
+use crate::i18n::{t, tf};
+use crate::services::api;
+use crate::services::datetime::{browser_time_zone, format_datetime};
+use crate::services::locale::use_locale;
+
+#[derive(Properties, PartialEq)]
+pub struct ScheduleEditorProps {
+    pub timer_id: String,
+}
+
+fn day_label(locale: Locale, day: ScheduleDay) -> &'static str {
+    let key = match day {
+        ScheduleDay::Mon => "day.mon",
+        ScheduleDay::Tue => "day.tue",
+        ScheduleDay::Wed => "day.wed",
+        ScheduleDay::Thu => "day.thu",
+        ScheduleDay::Fri => "day.fri",
+        ScheduleDay::Sat => "day.sat",
+        ScheduleDay::Sun => "day.sun",
+    };
+    t(locale, key)
+}
+
+/// 例: 「月・水・金 10:00」「cron: 0 10 * * MON-FRI」
+fn rule_line(locale: Locale, rule: &ScheduleRule) -> String {
+    match rule {
+        ScheduleRule::Weekly { days, hour, minute } => {
+            let days: Vec<&str> = ScheduleDay::ALL
+                .iter()
+                .filter(|d| days.contains(d))
+                .map(|d| day_label(locale, *d))
+                .collect();
+            format!("{} {:02}:{:02}", days.join(t(locale, "common.list_sep")), hour, minute)
+        }
+        ScheduleRule::Cron { expression } => format!("cron: {}", expression),
+    }
+}
+
+/// "HH:MM" → (時, 分)
+fn parse_time(value: &str) -> Option<(u32, u32)> {
+    let (h, m) = value.split_once(':')?;
+    Some((h.parse().ok()?, m.parse().ok()?))
+}
+
+/// タイマーに紐づく定期実行スケジュールの一覧と追加フォーム
+#[function_component(ScheduleEditor)]
+pub fn schedule_editor(props: &ScheduleEditorProps) -> Html {
+    let locale = use_locale();
+    let schedules = use_state(Vec::<Schedule>::new);
+    let errors = use_state(Vec::<String>::new);
+    let use_cron = use_state(|| false);
+    let days = use_state(|| vec![ScheduleDay::Mon, ScheduleDay::Tue, ScheduleDay::Wed, ScheduleDay::Thu, ScheduleDay::Fri]);
+    let time = use_state(|| "10:00".to_string());
+    let expression = use_state(String::new);
+    let timezone = use_state(browser_time_zone);
+
+    let reload = {
+        let schedules = schedules.clone();
+        let errors = errors.clone();
+        let timer_id = props.timer_id.clone();
+        Callback::from(move |_: ()| {
+            let schedules = schedules.clone();
+            let errors = errors.clone();
+            let timer_id = timer_id.clone();
+            spawn_local(async move {
+                match api::fetch_schedules(&timer_id).await {
+                    Ok(list) => schedules.set(list),
+                    Err(e) => errors.set(vec![e]),
+                }
+            });
+        })
+    };
+
+    {
+        let reload = reload.clone();
+        use_effect_with(props.timer_id.clone(), move |_| {
+            reload.emit(());
+            || ()
+        });
+    }
+
+    let on_kind = {
+        let use_cron = use_cron.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            use_cron.set(select.value() == "cron");
+        })
+    };
+
+    let on_time = {
+        let time = time.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            time.set(input.value());
+        })
+    };
+
+    let on_expression = {
+        let expression = expression.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            expression.set(input.value());
+        })
+    };
+
+    let on_timezone = {
+        let timezone = timezone.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            timezone.set(input.value());
+        })
+    };
+
+    let on_add = {
+        let timer_id = props.timer_id.clone();
+        let use_cron = use_cron.clone();
+        let days = days.clone();
+        let time = time.clone();
+        let expression = expression.clone();
+        let timezone = timezone.clone();
+        let errors = errors.clone();
+        let reload = reload.clone();
+        Callback::from(move |_: MouseEvent| {
+            let rule = if *use_cron {
+                ScheduleRule::Cron {
+                    expression: (*expression).clone(),
+                }
+            } else {
+                let Some((hour, minute)) = parse_time(&time) else {
+                    errors.set(vec![t(locale, "schedule.time_required").to_string()]);
+                    return;
+                };
+                ScheduleRule::Weekly {
+                    days: (*days).clone(),
+                    hour,
+                    minute,
+                }
+            };
+            let schedule = Schedule {
+                id: String::new(),
+                timer_id: timer_id.clone(),
+                rule,
+                timezone: (*timezone).clone(),
+                enabled: true,
+                next_run_at: None,
+                last_run_at: None,
+            };
+            if let Err(errs) = schedule.validate() {
+                errors.set(errs.iter().map(|e| e.render(locale)).collect());
+                return;
+            }
+            let errors = errors.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                match api::create_schedule(&schedule).await {
+                    Ok(_) => {
+                        errors.set(Vec::new());
+                        reload.emit(());
+                    }
+                    Err(errs) => errors.set(errs),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="schedule-editor">
+            <h3 class="schedule-title">{ t(locale, "schedule.title") }</h3>
+            if schedules.is_empty() {
+                <p class="schedule-empty">{ t(locale, "schedule.empty") }</p>
+            } else {
+                <ul class="schedule-list">
+                    { for schedules.iter().map(|s| {
+                        let on_toggle = {
+                            let mut toggled = s.clone();
+                            toggled.enabled = !toggled.enabled;
+                            let errors = errors.clone();
+                            let reload = reload.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                let toggled = toggled.clone();
+                                let errors = errors.clone();
+                                let reload = reload.clone();
+                                spawn_local(async move {
+                                    match api::update_schedule(&toggled).await {
+                                        Ok(_) => reload.emit(()),
+                                        Err(errs) => errors.set(errs),
+                                    }
+                                });
+                            })
+                        };
+                        let on_delete = {
+                            let id = s.id.clone();
+                            let errors = errors.clone();
+                            let reload = reload.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                let id = id.clone();
+                                let errors = errors.clone();
+                                let reload = reload.clone();
+                                spawn_local(async move {
+                                    match api::delete_schedule(&id).await {
+                                        Ok(()) => reload.emit(()),
+                                        Err(e) => errors.set(vec![e]),
+                                    }
+                                });
+                            })
+                        };
+                        let class = if s.enabled { "schedule-item" } else { "schedule-item disabled" };
+                        html! {
+                            <li {class}>
+                                <div class="schedule-rule">
+                                    { rule_line(locale, &s.rule) }
+                                    <span class="schedule-tz">{ format!(" ({})", s.timezone) }</span>
+                                </div>
+                                <div class="schedule-next">
+                                    { match s.next_run_at {
+                                        Some(at) => tf(locale, "schedule.next", &[&format_datetime(at)]),
+                                        None => tf(locale, "schedule.next", &[&"-"]),
+                                    }}
+                                </div>
+                                <div class="schedule-item-actions">
+                                    <button type="button" class="btn btn-secondary btn-small" onclick={on_toggle}>
+                                        { if s.enabled { t(locale, "common.disable") } else { t(locale, "common.enable") } }
+                                    </button>
+                                    <button type="button" class="btn btn-outline-danger btn-small" onclick={on_delete}>
+                                        { t(locale, "common.delete") }
+                                    </button>
+                                </div>
+                            </li>
+                        }
+                    })}
+                </ul>
+            }
+
+            <div class="schedule-form">
+                <select onchange={on_kind}>
+                    <option value="weekly" selected={!*use_cron}>{ t(locale, "schedule.weekly") }</option>
+                    <option value="cron" selected={*use_cron}>{ t(locale, "schedule.cron") }</option>
+                </select>
+                if *use_cron {
+                    <input type="text" class="schedule-cron-input" value={(*expression).clone()}
+                        oninput={on_expression} placeholder="0 10 * * MON-FRI" />
+                } else {
+                    <div class="schedule-days">
+                        { for ScheduleDay::ALL.iter().map(|day| {
+                            let day = *day;
+                            let active = days.contains(&day);
+                            let on_day = {
+                                let days = days.clone();
+                                Callback::from(move |_: MouseEvent| {
+                                    let mut next = (*days).clone();
+                                    if let Some(pos) = next.iter().position(|d| *d == day) {
+                                        next.remove(pos);
+                                    } else {
+                                        next.push(day);
+                                    }
+                                    days.set(next);
+                                })
+                            };
+                            html! {
+                                <button type="button"
+                                    class={if active { "schedule-day active" } else { "schedule-day" }}
+                                    onclick={on_day}>
+                                    { day_label(locale, day) }
+                                </button>
+                            }
+                        })}
+                    </div>
+                    <input type="time" value={(*time).clone()} oninput={on_time} />
+                }
+                <input type="text" class="schedule-tz-input" value={(*timezone).clone()}
+                    oninput={on_timezone} title={t(locale, "schedule.timezone")} />
+                <button type="button" class="btn btn-primary btn-small" onclick={on_add}>{ t(locale, "common.add") }</button>
+            </div>
+
+            if !errors.is_empty() {
+                <div class="validation-errors">
+                    <ul>
+                        { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                    </ul>
+                </div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,180 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use nekotimer_shared::{CreateShareLinkRequest, ShareLink};
+
+use crate::i18n::{t, tf};
+use crate::services::api;
+use crate::services::datetime::format_datetime;
+use crate::services::locale::use_locale;
+
+#[derive(Properties, PartialEq)]
+pub struct ShareLinksProps {
+    pub timer_id: String,
+}
+
+/// 有効期限の選択肢（日数。None は無期限）と表示用のメッセージキー
+const EXPIRY_OPTIONS: [(Option<u32>, &str); 4] = [
+    (None, "share.expiry_none"),
+    (Some(1), "share.expiry_1d"),
+    (Some(7), "share.expiry_7d"),
+    (Some(30), "share.expiry_30d"),
+];
+
+/// 共有リンクの完全な URL（このページと同じオリジン）
+fn share_url(link: &ShareLink) -> String {
+    let origin = web_sys::window()
+        .and_then(|w| w.location().origin().ok())
+        .unwrap_or_default();
+    format!("{}{}", origin, link.path())
+}
+
+/// タイマーの共有リンクの一覧・作成・取り消し
+#[function_component(ShareLinks)]
+pub fn share_links(props: &ShareLinksProps) -> Html {
+    let locale = use_locale();
+    let links = use_state(Vec::<ShareLink>::new);
+    let errors = use_state(Vec::<String>::new);
+    let allow_run = use_state(|| false);
+    let expires_in_days = use_state(|| None::<u32>);
+
+    let reload = {
+        let links = links.clone();
+        let errors = errors.clone();
+        let timer_id = props.timer_id.clone();
+        Callback::from(move |_: ()| {
+            let links = links.clone();
+            let errors = errors.clone();
+            let timer_id = timer_id.clone();
+            spawn_local(async move {
+                match api::fetch_shares(&timer_id).await {
+                    Ok(list) => links.set(list),
+                    Err(e) => errors.set(vec![e]),
+                }
+            });
+        })
+    };
+
+    {
+        let reload = reload.clone();
+        use_effect_with(props.timer_id.clone(), move |_| {
+            reload.emit(());
+            || ()
+        });
+    }
+
+    let on_allow_run = {
+        let allow_run = allow_run.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            allow_run.set(input.checked());
+        })
+    };
+
+    let on_expiry = {
+        let expires_in_days = expires_in_days.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            expires_in_days.set(select.value().parse().ok());
+        })
+    };
+
+    let on_create = {
+        let timer_id = props.timer_id.clone();
+        let allow_run = allow_run.clone();
+        let expires_in_days = expires_in_days.clone();
+        let errors = errors.clone();
+        let reload = reload.clone();
+        Callback::from(move |_: MouseEvent| {
+            let req = CreateShareLinkRequest {
+                timer_id: timer_id.clone(),
+                allow_run: *allow_run,
+                expires_in_days: *expires_in_days,
+            };
+            let errors = errors.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                match api::create_share(&req).await {
+                    Ok(_) => {
+                        errors.set(Vec::new());
+                        reload.emit(());
+                    }
+                    Err(errs) => errors.set(errs),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="share-links">
+            <h3 class="schedule-title">{ t(locale, "share.title") }</h3>
+            if links.is_empty() {
+                <p class="schedule-empty">{ t(locale, "share.empty") }</p>
+            } else {
+                <ul class="share-list">
+                    { for links.iter().map(|link| {
+                        let on_delete = {
+                            let id = link.id.clone();
+                            let errors = errors.clone();
+                            let reload = reload.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                let id = id.clone();
+                                let errors = errors.clone();
+                                let reload = reload.clone();
+                                spawn_local(async move {
+                                    match api::delete_share(&id).await {
+                                        Ok(()) => reload.emit(()),
+                                        Err(e) => errors.set(vec![e]),
+                                    }
+                                });
+                            })
+                        };
+                        let url = share_url(link);
+                        html! {
+                            <li class="share-item">
+                                <a class="share-url" href={url.clone()} target="_blank">{ url }</a>
+                                <div class="share-meta">
+                                    if link.allow_run {
+                                        <span class="share-badge">{ t(locale, "share.runnable") }</span>
+                                    }
+                                    { match link.expires_at {
+                                        Some(at) => tf(locale, "share.expires", &[&format_datetime(at)]),
+                                        None => tf(locale, "share.expires", &[&t(locale, "share.expiry_none")]),
+                                    }}
+                                </div>
+                                <button type="button" class="btn btn-outline-danger btn-small" onclick={on_delete}>
+                                    { t(locale, "share.revoke") }
+                                </button>
+                            </li>
+                        }
+                    })}
+                </ul>
+            }
+
+            <div class="schedule-form">
+                <label class="share-allow-run">
+                    <input type="checkbox" checked={*allow_run} onchange={on_allow_run} />
+                    { t(locale, "share.allow_run") }
+                </label>
+                <select onchange={on_expiry} title={t(locale, "share.expiry")}>
+                    { for EXPIRY_OPTIONS.iter().map(|(days, label)| html! {
+                        <option value={days.map(|d| d.to_string()).unwrap_or_default()}
+                            selected={*days == *expires_in_days}>
+                            { t(locale, label) }
+                        </option>
+                    })}
+                </select>
+                <button type="button" class="btn btn-primary btn-small" onclick={on_create}>{ t(locale, "share.create") }</button>
+            </div>
+
+            if !errors.is_empty() {
+                <div class="validation-errors">
+                    <ul>
+                        { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                    </ul>
+                </div>
+            }
+        </div>
+    }
+}
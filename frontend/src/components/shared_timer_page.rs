@@ -0,0 +1,88 @@
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use nekotimer_shared::SharedTimer;
+
+use crate::components::local_run_modal::LocalRunModal;
+use crate::components::timer_view::TimerView;
+use crate::i18n::{t, tf};
+use crate::services::api;
+use crate::services::datetime::format_datetime;
+use crate::services::locale::use_locale;
+use crate::services::timer_runner;
+
+#[derive(Properties, PartialEq)]
+pub struct SharedTimerPageProps {
+    pub token: String,
+}
+
+/// 共有リンク `/s/<token>` の画面。ログインなしで閲覧でき、許可されていればブラウザ内で実行できる
+#[function_component(SharedTimerPage)]
+pub fn shared_timer_page(props: &SharedTimerPageProps) -> Html {
+    let shared = use_state(|| None::<SharedTimer>);
+    let error = use_state(|| None::<String>);
+    let running = use_state(|| false);
+    let locale = use_locale();
+
+    {
+        let shared = shared.clone();
+        let error = error.clone();
+        let token = props.token.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match api::fetch_shared(&token).await {
+                    Ok(s) => {
+                        timer_runner::set_sound_profiles(s.sound_profile.clone().into_iter().collect());
+                        timer_runner::init_audio_cache(&s.timer);
+                        shared.set(Some(s));
+                    }
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_run = {
+        let running = running.clone();
+        Callback::from(move |_: MouseEvent| {
+            timer_runner::unlock_audio_for_ios();
+            running.set(true);
+        })
+    };
+
+    let on_close = {
+        let running = running.clone();
+        Callback::from(move |_: ()| running.set(false))
+    };
+
+    html! {
+        <div class="shared-page">
+            <header class="shared-header">{"nekotimer"}</header>
+            <main class="shared-main">
+                if let Some(e) = &*error {
+                    <div class="validation-errors">
+                        <ul><li>{ e }</li></ul>
+                    </div>
+                } else if let Some(s) = &*shared {
+                    <TimerView timer={s.timer.clone()} read_only=true />
+                    <div class="shared-footer">
+                        if s.allow_run {
+                            <button type="button" class="btn btn-primary" onclick={on_run}>
+                                { t(locale, "shared.run_here") }
+                            </button>
+                        }
+                        if let Some(expires_at) = s.expires_at {
+                            <span class="shared-expiry">{ tf(locale, "shared.expires", &[&format_datetime(expires_at)]) }</span>
+                        }
+                    </div>
+                    if *running {
+                        <LocalRunModal timer={s.timer.clone()} {on_close} />
+                    }
+                } else {
+                    <p class="history-empty">{ t(locale, "common.loading") }</p>
+                }
+            </main>
+        </div>
+    }
+}
@@ -1,14 +1,17 @@
-use std::cell::Cell;
-use std::rc::Rc;
 use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlSelectElement;
 use yew::prelude::*;
 
-use crate::services::timer_runner;
+use crate::components::locale_switch::LocaleSwitch;
+use crate::i18n::t;
+use crate::services::locale::use_locale;
+use crate::services::{api, run_events, timer_runner};
 use crate::state::{AppAction, AppStateContext};
 
 #[function_component(Sidebar)]
 pub fn sidebar() -> Html {
     let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
 
     let on_add = {
         let state = state.clone();
@@ -17,18 +20,107 @@ pub fn sidebar() -> Html {
         })
     };
 
+    let on_history = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(AppAction::ShowHistory);
+        })
+    };
+
+    let on_webhooks = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(AppAction::ShowWebhooks);
+        })
+    };
+
+    let on_tokens = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(AppAction::ShowTokens);
+        })
+    };
+
+    let on_sounds = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(AppAction::ShowSoundProfiles);
+        })
+    };
+
+    let on_sound_library = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(AppAction::ShowSoundLibrary);
+        })
+    };
+
+    let on_speech = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(AppAction::ShowSpeech);
+        })
+    };
+
+    let on_workspaces = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(AppAction::ShowWorkspaces);
+        })
+    };
+
+    let on_switch = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            state.dispatch(AppAction::SelectWorkspace((!value.is_empty()).then_some(value)));
+        })
+    };
+
+    let on_logout = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            let state = state.clone();
+            spawn_local(async move {
+                if let Err(e) = api::logout().await {
+                    log::error!("Failed to log out: {}", e);
+                }
+                state.dispatch(AppAction::SetUser(None));
+            });
+        })
+    };
+
+    // 閲覧者・実行者のワークスペースにはタイマーを追加できない
+    let can_add = state
+        .current_workspace
+        .as_ref()
+        .is_none_or(|id| state.workspaces.iter().any(|w| w.id == *id && w.role.can_edit()));
+
     html! {
         <div class="sidebar">
             <h2>{"nekotimer"}</h2>
+            if !state.workspaces.is_empty() {
+                <select class="sidebar-workspace-select" onchange={on_switch} title={t(locale, "sidebar.workspace_select")}>
+                    <option value="" selected={state.current_workspace.is_none()}>{ t(locale, "sidebar.personal") }</option>
+                    { for state.workspaces.iter().map(|w| html! {
+                        <option value={w.id.clone()} selected={state.current_workspace.as_deref() == Some(w.id.as_str())}>
+                            { &w.name }
+                        </option>
+                    })}
+                </select>
+            }
             <ul class="sidebar-timer-list">
-                { for state.timers.iter().map(|timer| {
+                { for state.current_timers().map(|timer| {
                     let id = timer.id.clone();
                     let timer_name = timer.name.clone();
                     let is_active = state.editing_timer
                         .as_ref()
                         .map(|t| t.id == timer.id)
                         .unwrap_or(false);
-                    let can_execute = !timer.id.is_empty();
+                    let role = state.role_for(timer);
+                    let can_execute = !timer.id.is_empty() && role.can_run();
+                    let can_edit = role.can_edit();
                     let class = if is_active {
                         "sidebar-timer-item active"
                     } else {
@@ -38,23 +130,34 @@ pub fn sidebar() -> Html {
                     let state_for_enter = state.clone();
                     let id_for_select = id.clone();
                     let id_for_preview = id.clone();
+                    // 編集できないタイマーはクリックしても表示だけ
                     let on_select = Callback::from(move |_: MouseEvent| {
-                        state_for_select.dispatch(AppAction::EditTimer(id_for_select.clone()));
+                        if can_edit {
+                            state_for_select.dispatch(AppAction::EditTimer(id_for_select.clone()));
+                        } else {
+                            state_for_select.dispatch(AppAction::PreviewTimer(Some(id_for_select.clone())));
+                        }
                     });
                     let on_mouse_enter = Callback::from(move |_: MouseEvent| {
                         state_for_enter.dispatch(AppAction::PreviewTimer(Some(id_for_preview.clone())));
                     });
+                    let id_to_run = id.clone();
                     let timer_to_run = timer.clone();
                     let state_for_runner = state.clone();
                     let on_execute = Callback::from(move |e: MouseEvent| {
                         e.stop_propagation();
                         timer_runner::unlock_audio_for_ios();
-                        let config = timer_to_run.clone();
+                        timer_runner::init_audio_cache(&timer_to_run);
+                        let timer_id = id_to_run.clone();
                         let state = state_for_runner.clone();
-                        let cancel_token = Rc::new(Cell::new(false));
-                        state.dispatch(AppAction::StartTimerExecution(config.clone(), cancel_token.clone()));
                         spawn_local(async move {
-                            timer_runner::run_timer(config, cancel_token, state).await;
+                            match api::start_run(&timer_id).await {
+                                Ok(session) => {
+                                    run_events::show(&session);
+                                    state.dispatch(AppAction::StartTimerExecution(session));
+                                }
+                                Err(e) => log::error!("Failed to start timer: {}", e),
+                            }
                         });
                     });
                     html! {
@@ -68,9 +171,9 @@ pub fn sidebar() -> Html {
                                 <button
                                     class="btn btn-success btn-run"
                                     onclick={on_execute}
-                                    title="実行"
+                                    title={t(locale, "sidebar.run")}
                                 >
-                                    {"実行"}
+                                    { t(locale, "sidebar.run") }
                                 </button>
                             }
                         </li>
@@ -78,8 +181,29 @@ pub fn sidebar() -> Html {
                 })}
             </ul>
             <div class="sidebar-actions">
-                <button class="btn btn-add" onclick={on_add}>{"Add"}</button>
+                if can_add {
+                    <button class="btn btn-add" onclick={on_add}>{ t(locale, "sidebar.add") }</button>
+                }
+                <button class="btn btn-add" onclick={on_history}>{ t(locale, "sidebar.history") }</button>
+                <button class="btn btn-add" onclick={on_webhooks}>{ t(locale, "sidebar.webhooks") }</button>
+                <button class="btn btn-add" onclick={on_tokens}>{ t(locale, "sidebar.tokens") }</button>
+                <button class="btn btn-add" onclick={on_sounds}>{ t(locale, "sidebar.sounds") }</button>
+                <button class="btn btn-add" onclick={on_sound_library}>{ t(locale, "sidebar.sound_library") }</button>
+                <button class="btn btn-add" onclick={on_speech}>{ t(locale, "sidebar.speech") }</button>
+                <button class="btn btn-add" onclick={on_workspaces}>{ t(locale, "sidebar.workspaces") }</button>
             </div>
+            if let Some(user) = &state.user {
+                <div class="sidebar-user">
+                    <span class="sidebar-user-name">
+                        { &user.username }
+                        if user.is_admin {
+                            <span class="sidebar-user-role">{ t(locale, "sidebar.admin") }</span>
+                        }
+                    </span>
+                    <button class="btn btn-secondary btn-small" onclick={on_logout}>{ t(locale, "sidebar.logout") }</button>
+                </div>
+            }
+            <LocaleSwitch />
         </div>
     }
 }
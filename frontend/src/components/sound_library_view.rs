@@ -0,0 +1,294 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{File, HtmlInputElement};
+use yew::prelude::*;
+
+use nekotimer_shared::{
+    validate_custom_sound_name, validate_custom_sound_upload, CustomSound, CUSTOM_SOUND_MAX_BYTES,
+};
+
+use crate::i18n::{t, tf};
+use crate::services::api;
+use crate::services::datetime::format_datetime;
+use crate::services::locale::use_locale;
+use crate::services::timer_runner;
+use crate::state::{AppAction, AppStateContext};
+
+/// 効果音の一覧を取り直して AppState に反映する。
+/// 削除したときはタイマーと効果音プロファイルもサーバー側で書き換わるので取り直す
+fn reload_sounds(state: AppStateContext, errors: UseStateHandle<Vec<String>>, with_references: bool) {
+    spawn_local(async move {
+        match api::fetch_custom_sounds().await {
+            Ok(list) => state.dispatch(AppAction::SetCustomSounds(list)),
+            Err(e) => errors.set(vec![e]),
+        }
+        if !with_references {
+            return;
+        }
+        match api::fetch_timers().await {
+            Ok(timers) => state.dispatch(AppAction::SetTimers(timers)),
+            Err(e) => errors.set(vec![e]),
+        }
+        match api::fetch_sound_profiles().await {
+            Ok(profiles) => state.dispatch(AppAction::SetSoundProfiles(profiles)),
+            Err(e) => errors.set(vec![e]),
+        }
+    });
+}
+
+fn format_size(bytes: usize) -> String {
+    format!("{:.1} KB", bytes as f64 / 1024.0)
+}
+
+/// アップロードした効果音（効果音ライブラリ）の追加・試聴・名前の変更・削除
+#[function_component(SoundLibraryView)]
+pub fn sound_library_view() -> Html {
+    let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
+    let errors = use_state(Vec::<String>::new);
+    let name = use_state(String::new);
+    let file = use_state(|| None::<File>);
+    let uploading = use_state(|| false);
+    // ファイル選択欄は送信後に空にするため、描画し直すキーを変える
+    let input_key = use_state(|| 0u32);
+
+    {
+        let state = state.clone();
+        let errors = errors.clone();
+        use_effect_with((), move |_| {
+            reload_sounds(state, errors, false);
+            || ()
+        });
+    }
+
+    let on_name = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    // 名前が空ならファイル名（拡張子なし）を入れておく
+    let on_file = {
+        let file = file.clone();
+        let name = name.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let selected = input.files().and_then(|list| list.get(0));
+            if let Some(f) = &selected {
+                if name.trim().is_empty() {
+                    let file_name = f.name();
+                    let stem = file_name.rsplit_once('.').map_or(file_name.as_str(), |(stem, _)| stem);
+                    name.set(stem.to_string());
+                }
+            }
+            file.set(selected);
+        })
+    };
+
+    let on_upload = {
+        let state = state.clone();
+        let name = name.clone();
+        let file = file.clone();
+        let errors = errors.clone();
+        let uploading = uploading.clone();
+        let input_key = input_key.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(selected) = (*file).clone() else {
+                errors.set(vec![t(locale, "sound_library.no_file").to_string()]);
+                return;
+            };
+            let size = selected.size() as usize;
+            if let Err(errs) = validate_custom_sound_upload(&name, &selected.type_(), size) {
+                errors.set(errs.iter().map(|e| e.render(locale)).collect());
+                return;
+            }
+            uploading.set(true);
+            let state = state.clone();
+            let name = name.clone();
+            let file = file.clone();
+            let errors = errors.clone();
+            let uploading = uploading.clone();
+            let input_key = input_key.clone();
+            spawn_local(async move {
+                match api::upload_custom_sound(name.trim(), &selected).await {
+                    Ok(_) => {
+                        name.set(String::new());
+                        file.set(None);
+                        input_key.set(*input_key + 1);
+                        errors.set(Vec::new());
+                        reload_sounds(state, errors, false);
+                    }
+                    Err(errs) => errors.set(errs),
+                }
+                uploading.set(false);
+            });
+        })
+    };
+
+    let on_changed = {
+        let state = state.clone();
+        let errors = errors.clone();
+        Callback::from(move |result: Result<bool, Vec<String>>| match result {
+            Ok(deleted) => {
+                errors.set(Vec::new());
+                reload_sounds(state.clone(), errors.clone(), deleted);
+            }
+            Err(errs) => errors.set(errs),
+        })
+    };
+
+    let user = state.user.clone();
+    let can_modify = |sound: &CustomSound| {
+        user.as_ref().is_some_and(|u| u.is_admin || u.id == sound.owner)
+    };
+
+    html! {
+        <div class="sound-library-view">
+            <h2 class="history-title">{ t(locale, "sound_library.title") }</h2>
+            <p class="webhook-help">
+                { tf(locale, "sound_library.help", &[&(CUSTOM_SOUND_MAX_BYTES / 1024)]) }
+            </p>
+
+            if state.custom_sounds.is_empty() {
+                <p class="history-empty">{ t(locale, "sound_library.empty") }</p>
+            } else {
+                <table class="history-table">
+                    <thead>
+                        <tr>
+                            <th>{ t(locale, "sound_library.name") }</th>
+                            <th>{ t(locale, "sound_library.size") }</th>
+                            <th>{ t(locale, "sound_library.uploaded_at") }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for state.custom_sounds.iter().map(|sound| html! {
+                            <SoundRow
+                                key={sound.id.clone()}
+                                sound={sound.clone()}
+                                editable={can_modify(sound)}
+                                on_changed={on_changed.clone()}
+                            />
+                        })}
+                    </tbody>
+                </table>
+            }
+
+            <div class="webhook-form token-form sound-upload-form">
+                <input type="file" key={*input_key} accept="audio/*" onchange={on_file} />
+                <input type="text" value={(*name).clone()} oninput={on_name}
+                    placeholder={t(locale, "sound_library.name_placeholder")} />
+                <button type="button" class="btn btn-primary btn-small" onclick={on_upload} disabled={*uploading}>
+                    { t(locale, "sound_library.upload") }
+                </button>
+            </div>
+
+            if !errors.is_empty() {
+                <div class="validation-errors">
+                    <ul>
+                        { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                    </ul>
+                </div>
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct SoundRowProps {
+    sound: CustomSound,
+    /// アップロードした人か管理者なら名前の変更・削除ができる
+    editable: bool,
+    /// 成功したら Ok(削除したか)
+    on_changed: Callback<Result<bool, Vec<String>>>,
+}
+
+#[function_component(SoundRow)]
+fn sound_row(props: &SoundRowProps) -> Html {
+    let locale = use_locale();
+    let name = {
+        let initial = props.sound.name.clone();
+        use_state(move || initial)
+    };
+    {
+        let name = name.clone();
+        use_effect_with(props.sound.name.clone(), move |initial| {
+            name.set(initial.clone());
+            || ()
+        });
+    }
+
+    let on_name = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    let on_preview = {
+        let id = props.sound.sound_id();
+        Callback::from(move |_: MouseEvent| timer_runner::play_sound(&id))
+    };
+
+    let on_rename = {
+        let id = props.sound.id.clone();
+        let name = name.clone();
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Err(errs) = validate_custom_sound_name(&name) {
+                on_changed.emit(Err(errs.iter().map(|e| e.render(locale)).collect()));
+                return;
+            }
+            let id = id.clone();
+            let name = (*name).clone();
+            let on_changed = on_changed.clone();
+            spawn_local(async move {
+                on_changed.emit(api::rename_custom_sound(&id, name.trim()).await.map(|_| false));
+            });
+        })
+    };
+
+    let on_delete = {
+        let id = props.sound.id.clone();
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |_: MouseEvent| {
+            let id = id.clone();
+            let on_changed = on_changed.clone();
+            spawn_local(async move {
+                on_changed.emit(api::delete_custom_sound(&id).await.map(|_| true).map_err(|e| vec![e]));
+            });
+        })
+    };
+
+    let dirty = *name != props.sound.name;
+
+    html! {
+        <tr>
+            <td>
+                if props.editable {
+                    <input type="text" class="sound-profile-name" value={(*name).clone()} oninput={on_name} />
+                } else {
+                    { &props.sound.name }
+                }
+            </td>
+            <td>{ format_size(props.sound.size) }</td>
+            <td>{ format_datetime(props.sound.uploaded_at) }</td>
+            <td class="schedule-item-actions">
+                <button type="button" class="btn btn-secondary btn-small" onclick={on_preview}
+                    title={t(locale, "sound.preview")}>
+                    {"▶"}
+                </button>
+                if props.editable {
+                    <button type="button" class="btn btn-primary btn-small" onclick={on_rename} disabled={!dirty}>
+                        { t(locale, "sound_library.rename") }
+                    </button>
+                    <button type="button" class="btn btn-outline-danger btn-small" onclick={on_delete}>
+                        { t(locale, "common.delete") }
+                    </button>
+                }
+            </td>
+        </tr>
+    }
+}
@@ -0,0 +1,231 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use nekotimer_shared::{SoundEvent, SoundProfile, SoundSet};
+
+use crate::components::sound_set_editor::SoundSetEditor;
+use crate::i18n::t;
+use crate::services::api;
+use crate::services::locale::use_locale;
+use crate::state::{AppAction, AppStateContext};
+
+/// プロファイルの一覧を取り直して AppState に反映する。
+/// 削除したプロファイルはサーバー側でタイマーからも外れるので、タイマーも取り直す
+fn reload_profiles(state: AppStateContext, errors: UseStateHandle<Vec<String>>) {
+    spawn_local(async move {
+        match api::fetch_sound_profiles().await {
+            Ok(list) => state.dispatch(AppAction::SetSoundProfiles(list)),
+            Err(e) => errors.set(vec![e]),
+        }
+        match api::fetch_timers().await {
+            Ok(timers) => state.dispatch(AppAction::SetTimers(timers)),
+            Err(e) => errors.set(vec![e]),
+        }
+    });
+}
+
+/// 効果音プロファイル（名前付きの効果音の組み合わせ）の作成と編集
+#[function_component(SoundProfileView)]
+pub fn sound_profile_view() -> Html {
+    let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
+    let errors = use_state(Vec::<String>::new);
+    let name = use_state(String::new);
+
+    {
+        let state = state.clone();
+        let errors = errors.clone();
+        use_effect_with((), move |_| {
+            reload_profiles(state, errors);
+            || ()
+        });
+    }
+
+    let on_name = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    let on_create = {
+        let state = state.clone();
+        let name = name.clone();
+        let errors = errors.clone();
+        Callback::from(move |_: MouseEvent| {
+            let profile = SoundProfile {
+                id: String::new(),
+                name: (*name).clone(),
+                owner: String::new(),
+                sounds: SoundSet::default(),
+            };
+            if let Err(errs) = profile.validate() {
+                errors.set(errs.iter().map(|e| e.render(locale)).collect());
+                return;
+            }
+            let state = state.clone();
+            let name = name.clone();
+            let errors = errors.clone();
+            spawn_local(async move {
+                match api::create_sound_profile(&profile).await {
+                    Ok(_) => {
+                        name.set(String::new());
+                        errors.set(Vec::new());
+                        reload_profiles(state, errors);
+                    }
+                    Err(errs) => errors.set(errs),
+                }
+            });
+        })
+    };
+
+    let on_changed = {
+        let state = state.clone();
+        let errors = errors.clone();
+        Callback::from(move |result: Result<(), Vec<String>>| match result {
+            Ok(()) => {
+                errors.set(Vec::new());
+                reload_profiles(state.clone(), errors.clone());
+            }
+            Err(errs) => errors.set(errs),
+        })
+    };
+
+    let user = state.user.clone();
+    let can_modify = |profile: &SoundProfile| {
+        user.as_ref().is_some_and(|u| u.is_admin || u.id == profile.owner)
+    };
+
+    html! {
+        <div class="sound-profile-view">
+            <h2 class="history-title">{ t(locale, "sound_profile.title") }</h2>
+            <p class="webhook-help">{ t(locale, "sound_profile.help") }</p>
+
+            if state.sound_profiles.is_empty() {
+                <p class="history-empty">{ t(locale, "sound_profile.empty") }</p>
+            }
+            { for state.sound_profiles.iter().map(|p| html! {
+                <SoundProfileCard
+                    key={p.id.clone()}
+                    profile={p.clone()}
+                    editable={can_modify(p)}
+                    on_changed={on_changed.clone()}
+                />
+            })}
+
+            <div class="webhook-form token-form">
+                <input type="text" value={(*name).clone()} oninput={on_name}
+                    placeholder={t(locale, "sound_profile.name_placeholder")} />
+                <button type="button" class="btn btn-primary btn-small" onclick={on_create}>
+                    { t(locale, "common.create") }
+                </button>
+            </div>
+
+            if !errors.is_empty() {
+                <div class="validation-errors">
+                    <ul>
+                        { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                    </ul>
+                </div>
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct SoundProfileCardProps {
+    profile: SoundProfile,
+    /// 作成者か管理者なら編集できる
+    editable: bool,
+    on_changed: Callback<Result<(), Vec<String>>>,
+}
+
+#[function_component(SoundProfileCard)]
+fn sound_profile_card(props: &SoundProfileCardProps) -> Html {
+    let locale = use_locale();
+    // 保存するまでの編集中の内容
+    let draft = {
+        let profile = props.profile.clone();
+        use_state(move || profile)
+    };
+    {
+        let draft = draft.clone();
+        use_effect_with(props.profile.clone(), move |profile| {
+            draft.set(profile.clone());
+            || ()
+        });
+    }
+
+    let on_name = {
+        let draft = draft.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = (*draft).clone();
+            next.name = input.value();
+            draft.set(next);
+        })
+    };
+
+    let on_sounds = {
+        let draft = draft.clone();
+        Callback::from(move |sounds: SoundSet| {
+            let mut next = (*draft).clone();
+            next.sounds = sounds;
+            draft.set(next);
+        })
+    };
+
+    let on_save = {
+        let draft = draft.clone();
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |_: MouseEvent| {
+            let profile = (*draft).clone();
+            let on_changed = on_changed.clone();
+            spawn_local(async move {
+                on_changed.emit(api::update_sound_profile(&profile).await.map(|_| ()));
+            });
+        })
+    };
+
+    let on_delete = {
+        let id = props.profile.id.clone();
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |_: MouseEvent| {
+            let id = id.clone();
+            let on_changed = on_changed.clone();
+            spawn_local(async move {
+                on_changed.emit(api::delete_sound_profile(&id).await.map_err(|e| vec![e]));
+            });
+        })
+    };
+
+    let dirty = *draft != props.profile;
+
+    html! {
+        <div class="workspace-card">
+            <div class="workspace-card-header">
+                if props.editable {
+                    <input type="text" class="sound-profile-name" value={draft.name.clone()} oninput={on_name} />
+                } else {
+                    <h3 class="schedule-title">{ &props.profile.name }</h3>
+                }
+            </div>
+            if props.editable {
+                <SoundSetEditor sounds={draft.sounds.clone()} events={SoundEvent::ALL.to_vec()} on_change={on_sounds} />
+                <div class="schedule-item-actions">
+                    <button type="button" class="btn btn-primary btn-small" onclick={on_save} disabled={!dirty}>
+                        { t(locale, "sound_profile.save") }
+                    </button>
+                    <button type="button" class="btn btn-outline-danger btn-small" onclick={on_delete}>
+                        { t(locale, "common.delete") }
+                    </button>
+                </div>
+            } else {
+                <SoundSetEditor sounds={props.profile.sounds.clone()} events={SoundEvent::ALL.to_vec()}
+                    disabled=true on_change={Callback::noop()} />
+            }
+        </div>
+    }
+}
@@ -0,0 +1,178 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use nekotimer_shared::{
+    BeepPattern, CustomSound, Locale, SoundEvent, SoundSet, BEEP_PRESETS, BUILTIN_SOUNDS,
+};
+
+use crate::i18n::{t, tf};
+use crate::services::locale::use_locale;
+use crate::services::timer_runner;
+use crate::state::AppStateContext;
+
+pub fn sound_event_label(locale: Locale, event: SoundEvent) -> &'static str {
+    let key = match event {
+        SoundEvent::BlockStart => "sound_event.block_start",
+        SoundEvent::BlockEnd => "sound_event.block_end",
+        SoundEvent::Tick => "sound_event.tick",
+        SoundEvent::Repetition => "sound_event.repetition",
+        SoundEvent::Finish => "sound_event.finish",
+    };
+    t(locale, key)
+}
+
+fn sound_label(locale: Locale, id: &str) -> String {
+    let key = match id {
+        "tick" => "sound.tick",
+        "block_end" => "sound.block_end",
+        "all_end" => "sound.all_end",
+        "countdown_blk_next" => "sound.countdown_blk_next",
+        "silent" => "sound.silent",
+        other => return other.to_string(),
+    };
+    t(locale, key).to_string()
+}
+
+fn beep_label(locale: Locale, beep: &BeepPattern) -> String {
+    tf(locale, "sound.beep", &[&beep.frequency_hz, &beep.duration_ms, &beep.count])
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SoundSetEditorProps {
+    pub sounds: SoundSet,
+    /// 選択肢に出すタイミング
+    pub events: Vec<SoundEvent>,
+    /// 表示のみ（変更できないプロファイルなど）
+    #[prop_or_default]
+    pub disabled: bool,
+    pub on_change: Callback<SoundSet>,
+}
+
+/// タイミングごとに効果音を選ぶ。「既定」は上位（タイマー・プロファイル）の設定に任せる。
+/// 組み込みの効果音のあとに電子音とアップロードした効果音を並べ、電子音は高さ・長さ・回数を変えられる
+#[function_component(SoundSetEditor)]
+pub fn sound_set_editor(props: &SoundSetEditorProps) -> Html {
+    let locale = use_locale();
+    let custom_sounds: Vec<CustomSound> = use_context::<AppStateContext>()
+        .map(|state| state.custom_sounds.clone())
+        .unwrap_or_default();
+
+    html! {
+        <div class="sound-set">
+            { for props.events.iter().map(|event| {
+                let event = *event;
+                let selected = props.sounds.get(event).map(str::to_string);
+                let on_select = {
+                    let sounds = props.sounds.clone();
+                    let on_change = props.on_change.clone();
+                    Callback::from(move |e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        let value = select.value();
+                        let mut next = sounds.clone();
+                        next.set(event, (!value.is_empty()).then_some(value));
+                        on_change.emit(next);
+                    })
+                };
+                let beep = selected.as_deref().and_then(BeepPattern::parse);
+                // 選択肢にない電子音（高さなどを変えたもの）も選択中として出す
+                let extra_beep = beep.filter(|b| !BEEP_PRESETS.contains(&b.id().as_str()));
+                let on_beep = {
+                    let sounds = props.sounds.clone();
+                    let on_change = props.on_change.clone();
+                    Callback::from(move |beep: BeepPattern| {
+                        let mut next = sounds.clone();
+                        next.set(event, Some(beep.id()));
+                        on_change.emit(next);
+                    })
+                };
+                let on_preview = {
+                    let sound = selected.clone().unwrap_or_else(|| event.default_sound().to_string());
+                    Callback::from(move |_: MouseEvent| timer_runner::play_sound(&sound))
+                };
+                html! {
+                    <div class="sound-row">
+                        <label>{ sound_event_label(locale, event) }</label>
+                        <select onchange={on_select} disabled={props.disabled}>
+                            <option value="" selected={selected.is_none()}>{ t(locale, "sound.inherit") }</option>
+                            { for BUILTIN_SOUNDS.iter().map(|id| html! {
+                                <option value={*id} selected={selected.as_deref() == Some(*id)}>
+                                    { sound_label(locale, id) }
+                                </option>
+                            })}
+                            <optgroup label={t(locale, "sound.beep_group")}>
+                                { for BEEP_PRESETS.iter().filter_map(|id| BeepPattern::parse(id)).map(|b| html! {
+                                    <option value={b.id()} selected={beep == Some(b)}>{ beep_label(locale, &b) }</option>
+                                })}
+                                if let Some(b) = extra_beep {
+                                    <option value={b.id()} selected=true>{ beep_label(locale, &b) }</option>
+                                }
+                            </optgroup>
+                            if !custom_sounds.is_empty() {
+                                <optgroup label={t(locale, "sound.custom_group")}>
+                                    { for custom_sounds.iter().map(|sound| {
+                                        let id = sound.sound_id();
+                                        let is_selected = selected.as_deref() == Some(id.as_str());
+                                        html! {
+                                            <option value={id} selected={is_selected}>
+                                                { &sound.name }
+                                            </option>
+                                        }
+                                    })}
+                                </optgroup>
+                            }
+                        </select>
+                        <button type="button" class="btn btn-secondary btn-small" onclick={on_preview}
+                            title={t(locale, "sound.preview")}>
+                            {"▶"}
+                        </button>
+                        if let Some(beep) = beep.filter(|_| !props.disabled) {
+                            <BeepEditor {beep} on_change={on_beep} />
+                        }
+                    </div>
+                }
+            })}
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct BeepEditorProps {
+    beep: BeepPattern,
+    on_change: Callback<BeepPattern>,
+}
+
+/// 電子音の高さ・長さ・回数・間隔
+#[function_component(BeepEditor)]
+fn beep_editor(props: &BeepEditorProps) -> Html {
+    let locale = use_locale();
+    let field = |label: &'static str, value: u32, (min, max): (u32, u32), apply: fn(&mut BeepPattern, u32)| {
+        let beep = props.beep;
+        let on_change = props.on_change.clone();
+        // 入力途中の値で範囲に丸めないよう、確定（フォーカスを外す・Enter）したときに反映する
+        let onchange = Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = input.value().parse::<u32>() {
+                let mut next = beep;
+                apply(&mut next, value);
+                on_change.emit(next.clamped());
+            }
+        });
+        html! {
+            <label>
+                { t(locale, label) }
+                <input type="number" min={min.to_string()} max={max.to_string()} value={value.to_string()} {onchange} />
+            </label>
+        }
+    };
+    let beep = props.beep;
+    html! {
+        <div class="sound-beep">
+            { field("sound.beep_frequency", beep.frequency_hz, BeepPattern::FREQUENCY_RANGE, |b, v| b.frequency_hz = v) }
+            { field("sound.beep_duration", beep.duration_ms, BeepPattern::DURATION_RANGE, |b, v| b.duration_ms = v) }
+            { field("sound.beep_count", beep.count, BeepPattern::COUNT_RANGE, |b, v| b.count = v) }
+            if beep.count > 1 {
+                { field("sound.beep_gap", beep.gap_ms, BeepPattern::GAP_RANGE, |b, v| b.gap_ms = v) }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,212 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use nekotimer_shared::{sample_announcement, Locale, SpeechEvent, SpeechSettings};
+
+use crate::components::locale_switch::locale_name;
+use crate::i18n::t;
+use crate::services::locale::use_locale;
+use crate::services::speech;
+
+fn event_label(locale: Locale, event: SpeechEvent) -> &'static str {
+    match event {
+        SpeechEvent::BlockStart => t(locale, "speech.event.block_start"),
+        SpeechEvent::Repetition => t(locale, "speech.event.repetition"),
+        SpeechEvent::Interval => t(locale, "speech.event.interval"),
+        SpeechEvent::Remaining => t(locale, "speech.event.remaining"),
+        SpeechEvent::Finish => t(locale, "speech.event.finish"),
+    }
+}
+
+/// 「30, 10」のような入力を秒数の一覧にする（0 や数でないものは除き、大きい順に並べる）
+fn parse_remaining_at(text: &str) -> Vec<u32> {
+    let mut secs: Vec<u32> = text
+        .split([',', '、'])
+        .filter_map(|s| s.trim().parse().ok())
+        .filter(|&s| s > 0)
+        .collect();
+    secs.sort_unstable_by(|a, b| b.cmp(a));
+    secs.dedup();
+    secs
+}
+
+fn remaining_at_text(secs: &[u32]) -> String {
+    secs.iter().map(u32::to_string).collect::<Vec<_>>().join(", ")
+}
+
+/// 変えた設定はすぐ保存する
+fn update(settings: &UseStateHandle<SpeechSettings>, f: impl FnOnce(&mut SpeechSettings)) {
+    let mut next = (**settings).clone();
+    f(&mut next);
+    speech::save_settings(&next);
+    settings.set(next);
+}
+
+/// 読み上げ（音声合成）の設定。端末ごとに保存する
+#[function_component(SpeechSettingsView)]
+pub fn speech_settings_view() -> Html {
+    let locale = use_locale();
+    let settings = use_state(speech::settings);
+    // 声の一覧は遅れて読み込まれることがあるので、読み込まれたら描き直す
+    let redraw = use_force_update();
+
+    use_effect_with((), move |_| speech::watch_voices(move || redraw.force_update()));
+
+    if !speech::is_supported() {
+        return html! {
+            <div class="speech-settings-view">
+                <h2 class="history-title">{ t(locale, "speech.title") }</h2>
+                <p class="history-empty">{ t(locale, "speech.unsupported") }</p>
+            </div>
+        };
+    }
+
+    let on_enabled = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            update(&settings, |s| s.enabled = input.checked());
+        })
+    };
+
+    // 言語を変えると声の候補も変わるので、声はブラウザの既定に戻す
+    let on_language = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(locale) = Locale::from_tag(&select.value()) {
+                update(&settings, |s| {
+                    s.locale = locale;
+                    s.voice = None;
+                });
+            }
+        })
+    };
+
+    let on_voice = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            update(&settings, |s| s.voice = (!value.is_empty()).then_some(value));
+        })
+    };
+
+    let on_rate = {
+        let settings = settings.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(rate) = input.value().parse::<f32>() {
+                update(&settings, |s| s.rate = rate);
+            }
+        })
+    };
+
+    let on_remaining_at = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let secs = parse_remaining_at(&input.value());
+            input.set_value(&remaining_at_text(&secs));
+            update(&settings, |s| s.remaining_at = secs);
+        })
+    };
+
+    let voices = speech::voices(settings.locale);
+    let (rate_min, rate_max) = SpeechSettings::RATE_RANGE;
+
+    html! {
+        <div class="speech-settings-view">
+            <h2 class="history-title">{ t(locale, "speech.title") }</h2>
+            <p class="webhook-help">{ t(locale, "speech.help") }</p>
+
+            <div class="speech-settings">
+                <label class="speech-enabled">
+                    <input type="checkbox" checked={settings.enabled} onchange={on_enabled} />
+                    { t(locale, "speech.enabled") }
+                </label>
+                <div class="block-field">
+                    <label>{ t(locale, "speech.language") }</label>
+                    <select onchange={on_language}>
+                        { for Locale::ALL.iter().map(|&l| html! {
+                            <option value={l.tag()} selected={l == settings.locale}>{ locale_name(l) }</option>
+                        })}
+                    </select>
+                </div>
+                <div class="block-field">
+                    <label>{ t(locale, "speech.voice") }</label>
+                    <select onchange={on_voice}>
+                        <option value="" selected={settings.voice.is_none()}>{ t(locale, "speech.voice_default") }</option>
+                        { for voices.iter().map(|voice| {
+                            let name = voice.name();
+                            let is_selected = settings.voice.as_deref() == Some(name.as_str());
+                            html! { <option value={name.clone()} selected={is_selected}>{ name }</option> }
+                        })}
+                    </select>
+                </div>
+                <div class="block-field">
+                    <label>{ t(locale, "speech.rate") }</label>
+                    <input type="range" min={rate_min.to_string()} max={rate_max.to_string()} step="0.1"
+                        value={settings.rate.to_string()} oninput={on_rate} />
+                    <span class="speech-rate">{ format!("{:.1}×", settings.rate) }</span>
+                </div>
+                <div class="block-field">
+                    <label>{ t(locale, "speech.remaining_at") }</label>
+                    <input type="text" value={remaining_at_text(&settings.remaining_at)} onchange={on_remaining_at}
+                        placeholder={t(locale, "speech.remaining_at_placeholder")} />
+                </div>
+            </div>
+
+            <h3 class="history-section-title">{ t(locale, "speech.templates") }</h3>
+            <p class="webhook-help">{ t(locale, "speech.templates_help") }</p>
+            <div class="speech-templates">
+                { for SpeechEvent::ALL.iter().map(|&event| html! {
+                    <TemplateRow event={event} settings={settings.clone()} />
+                })}
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct TemplateRowProps {
+    event: SpeechEvent,
+    settings: UseStateHandle<SpeechSettings>,
+}
+
+/// タイミングごとの読み上げる文。空欄なら既定の文（プレースホルダーに表示）
+#[function_component(TemplateRow)]
+fn template_row(props: &TemplateRowProps) -> Html {
+    let locale = use_locale();
+    let event = props.event;
+    let settings = props.settings.clone();
+
+    let on_input = {
+        let settings = settings.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            update(&settings, |s| s.templates.set(event, (!value.trim().is_empty()).then_some(value)));
+        })
+    };
+
+    let on_preview = {
+        let settings = settings.clone();
+        Callback::from(move |_: MouseEvent| {
+            speech::cancel();
+            speech::speak(&sample_announcement(&settings, event), &settings);
+        })
+    };
+
+    html! {
+        <div class="block-field speech-template">
+            <label>{ event_label(locale, event) }</label>
+            <input type="text" value={settings.templates.get(event).unwrap_or_default().to_string()}
+                oninput={on_input} placeholder={event.default_template(settings.locale)} />
+            <button type="button" class="btn btn-secondary btn-small" onclick={on_preview}
+                title={t(locale, "sound.preview")}>
+                {"▶"}
+            </button>
+        </div>
+    }
+}
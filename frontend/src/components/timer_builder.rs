@@ -1,15 +1,22 @@
 use wasm_bindgen_futures::spawn_local;
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
+use nekotimer_shared::{SoundEvent, SoundSet, TICK_UNDER_SECS, TICK_UNDER_SECS_MAX};
+
 use crate::components::block_canvas::BlockCanvas;
+use crate::components::sound_set_editor::SoundSetEditor;
 use crate::services::api;
+use crate::i18n::t;
+use crate::services::locale::use_locale;
+use crate::services::validation::{field_message, focus_field};
 use crate::state::{AppAction, AppStateContext};
 
 #[function_component(TimerBuilder)]
 pub fn timer_builder() -> Html {
     let state = use_context::<AppStateContext>().expect("no context found");
     let name_input_ref = use_node_ref();
+    let locale = use_locale();
 
     {
         let name_input_ref = name_input_ref.clone();
@@ -25,11 +32,30 @@ pub fn timer_builder() -> Html {
         });
     }
 
+    // 保存でサーバーがエラーを返したら最初のエラーの入力欄へ移動する
+    use_effect_with(state.validation_errors.clone(), |errors| {
+        if !errors.is_empty() {
+            focus_field(None);
+        }
+        || ()
+    });
+
     let timer = match &state.editing_timer {
         Some(t) => t.clone(),
         None => return html! {},
     };
 
+    // 入力のたびに検証する。手元で通ればサーバーが返したエラーを出す
+    let live_errors = timer.validate(&state.validation_policy).err().unwrap_or_default();
+    let can_save = live_errors.is_empty();
+    let errors = if can_save {
+        state.validation_errors.clone()
+    } else {
+        live_errors
+    };
+    let name_error = field_message(&errors, "name");
+    let tick_error = field_message(&errors, "tick_under_secs");
+
     let on_name_change = {
         let state = state.clone();
         Callback::from(move |e: InputEvent| {
@@ -38,20 +64,43 @@ pub fn timer_builder() -> Html {
         })
     };
 
+    let on_sound_profile = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            state.dispatch(AppAction::SetTimerSoundProfile((!value.is_empty()).then_some(value)));
+        })
+    };
+
+    let on_sounds = {
+        let state = state.clone();
+        Callback::from(move |sounds: SoundSet| state.dispatch(AppAction::SetTimerSounds(sounds)))
+    };
+
+    // 空欄なら既定の秒数（TICK_UNDER_SECS）
+    let on_tick_under_secs = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            let secs = (!value.trim().is_empty()).then(|| value.trim().parse().unwrap_or(0));
+            state.dispatch(AppAction::SetTimerTickUnderSecs(secs));
+        })
+    };
+
+    // 手元の検証で通らないうちは保存せず、最初のエラーの入力欄へ移動する
     let on_save = {
         let state = state.clone();
         let timer = timer.clone();
         Callback::from(move |_: MouseEvent| {
+            if !can_save {
+                focus_field(None);
+                return;
+            }
             let state = state.clone();
             let timer = timer.clone();
             spawn_local(async move {
-                if let Err(errors) = timer.validate() {
-                    let error_messages: Vec<String> =
-                        errors.iter().map(|e| e.message.clone()).collect();
-                    state.dispatch(AppAction::SetValidationErrors(error_messages));
-                    return;
-                }
-
                 let result = if timer.id.is_empty() {
                     api::create_timer(&timer).await
                 } else {
@@ -70,42 +119,82 @@ pub fn timer_builder() -> Html {
         })
     };
 
+    let on_error_click = |field: String| {
+        Callback::from(move |_: MouseEvent| focus_field(Some(&field)))
+    };
+
     html! {
         <div class="timer-builder">
             <h2 class="builder-title">
                 if timer.id.is_empty() {
-                    {"新規タイマー作成"}
+                    { t(locale, "builder.title_new") }
                 } else {
-                    {"タイマー編集"}
+                    { t(locale, "builder.title_edit") }
                 }
             </h2>
             <div class="builder-name-section">
-                <label for="timer-name">{"タイマー名"}</label>
+                <label for="timer-name">{ t(locale, "builder.name") }</label>
                 <input
                     ref={name_input_ref}
                     id="timer-name"
-                    class="timer-name-input"
                     type="text"
-                    placeholder="タイマー名を入力..."
+                    placeholder={t(locale, "builder.name_placeholder")}
                     value={timer.name.clone()}
                     oninput={on_name_change}
-                    maxlength="64"
+                    maxlength={state.validation_policy.name_max_chars.to_string()}
+                    data-field="name"
+                    class={classes!("timer-name-input", name_error.is_some().then_some("field-invalid"))}
+                    title={name_error.clone()}
                 />
             </div>
 
-            <BlockCanvas />
+            <details class="builder-sounds">
+                <summary>{ t(locale, "sound.timer_summary") }</summary>
+                <div class="sound-row">
+                    <label for="timer-sound-profile">{ t(locale, "sound.profile") }</label>
+                    <select id="timer-sound-profile" onchange={on_sound_profile}>
+                        <option value="" selected={timer.sound_profile.is_none()}>{ t(locale, "sound.no_profile") }</option>
+                        { for state.sound_profiles.iter().map(|p| html! {
+                            <option value={p.id.clone()} selected={timer.sound_profile.as_deref() == Some(p.id.as_str())}>
+                                { &p.name }
+                            </option>
+                        })}
+                    </select>
+                </div>
+                <div class="sound-row">
+                    <label for="timer-tick-under-secs">{ t(locale, "sound.tick_under_secs") }</label>
+                    <input id="timer-tick-under-secs" type="number" min="0" max={TICK_UNDER_SECS_MAX.to_string()}
+                        value={timer.tick_under_secs.map(|s| s.to_string()).unwrap_or_default()}
+                        placeholder={TICK_UNDER_SECS.to_string()} oninput={on_tick_under_secs}
+                        data-field="tick_under_secs"
+                        class={classes!("sound-tick-input", tick_error.is_some().then_some("field-invalid"))}
+                        title={tick_error.clone().unwrap_or_else(|| t(locale, "sound.tick_under_secs_help").to_string())} />
+                </div>
+                <SoundSetEditor sounds={timer.sounds.clone()} events={SoundEvent::ALL.to_vec()} on_change={on_sounds} />
+            </details>
 
-            if !state.validation_errors.is_empty() {
+            <BlockCanvas errors={errors.clone()} />
+
+            if !errors.is_empty() {
                 <div class="validation-errors">
                     <ul>
-                        { for state.validation_errors.iter().map(|e| html! {
-                            <li>{ e }</li>
+                        { for errors.iter().map(|e| html! {
+                            <li onclick={on_error_click(e.field.clone())}>{ e.render(locale) }</li>
                         })}
                     </ul>
                 </div>
             }
 
-            <button class="btn btn-primary save-btn" onclick={on_save}>{"Save"}</button>
+            <div class="save-row">
+                <button class="btn btn-primary save-btn" onclick={on_save} aria-disabled={(!can_save).to_string()}>
+                    { t(locale, "builder.save") }
+                </button>
+                if !can_save {
+                    <span class="save-hint" onclick={Callback::from(|_: MouseEvent| focus_field(None))}>
+                        { t(locale, "builder.fix_errors") }
+                    </span>
+                }
+            </div>
         </div>
     }
 }
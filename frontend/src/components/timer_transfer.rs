@@ -0,0 +1,128 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use nekotimer_shared::TimerConfig;
+
+use crate::i18n::t;
+use crate::services::api;
+use crate::services::locale::use_locale;
+use crate::state::{AppAction, AppStateContext};
+
+#[derive(Properties, PartialEq)]
+pub struct TimerTransferProps {
+    pub timer: TimerConfig,
+}
+
+/// select の値。個人タイマーは空文字
+fn destination_value(workspace_id: &Option<String>) -> String {
+    workspace_id.clone().unwrap_or_default()
+}
+
+/// タイマーを個人とワークスペースの間で移動・コピーする
+#[function_component(TimerTransfer)]
+pub fn timer_transfer(props: &TimerTransferProps) -> Html {
+    let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
+    let errors = use_state(Vec::<String>::new);
+    let message = use_state(|| None::<String>);
+
+    // 移動・コピー先は今の場所以外で、タイマーを置ける（編集者以上の）スペース
+    let mut destinations: Vec<(Option<String>, String)> = Vec::new();
+    if props.timer.workspace_id.is_some() {
+        destinations.push((None, t(locale, "sidebar.personal").to_string()));
+    }
+    for w in &state.workspaces {
+        if w.role.can_edit() && props.timer.workspace_id.as_deref() != Some(w.id.as_str()) {
+            destinations.push((Some(w.id.clone()), w.name.clone()));
+        }
+    }
+    let destination = use_state(|| None::<String>);
+    {
+        // タイマーが変わったら先頭の候補を選び直す
+        let destination = destination.clone();
+        let first = destinations.first().map(|(id, _)| destination_value(id));
+        use_effect_with((props.timer.id.clone(), first), move |(_, first)| {
+            destination.set(first.clone());
+            || ()
+        });
+    }
+
+    if destinations.is_empty() {
+        return html! {};
+    }
+    let can_move = state.role_for(&props.timer).can_edit();
+
+    let on_destination = {
+        let destination = destination.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            destination.set(Some(select.value()));
+        })
+    };
+
+    let make_transfer = |copy: bool| {
+        let state = state.clone();
+        let id = props.timer.id.clone();
+        let destination = destination.clone();
+        let errors = errors.clone();
+        let message = message.clone();
+        Callback::from(move |_: MouseEvent| {
+            let workspace_id = (*destination).clone().filter(|v| !v.is_empty());
+            let state = state.clone();
+            let id = id.clone();
+            let errors = errors.clone();
+            let message = message.clone();
+            spawn_local(async move {
+                match api::transfer_timer(&id, workspace_id.clone(), copy).await {
+                    Ok(timer) => {
+                        errors.set(Vec::new());
+                        if let Ok(timers) = api::fetch_timers().await {
+                            state.dispatch(AppAction::SetTimers(timers));
+                        }
+                        if copy {
+                            message.set(Some(t(locale, "transfer.copied").to_string()));
+                        } else {
+                            // 移動先のスペースに切り替えて表示し直す
+                            state.dispatch(AppAction::SelectWorkspace(workspace_id));
+                            state.dispatch(AppAction::PreviewTimer(Some(timer.id)));
+                        }
+                    }
+                    Err(e) => {
+                        message.set(None);
+                        errors.set(vec![e]);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="timer-transfer">
+            <h3 class="schedule-title">{ t(locale, "transfer.title") }</h3>
+            <div class="schedule-form">
+                <select onchange={on_destination}>
+                    { for destinations.iter().map(|(id, name)| {
+                        let value = destination_value(id);
+                        let selected = destination.as_deref() == Some(value.as_str());
+                        html! { <option {value} {selected}>{ name }</option> }
+                    })}
+                </select>
+                if can_move {
+                    <button type="button" class="btn btn-secondary btn-small" onclick={make_transfer(false)}>{ t(locale, "transfer.move") }</button>
+                }
+                <button type="button" class="btn btn-secondary btn-small" onclick={make_transfer(true)}>{ t(locale, "transfer.copy") }</button>
+                if let Some(m) = &*message {
+                    <span class="timer-transfer-message">{ m }</span>
+                }
+            </div>
+            if !errors.is_empty() {
+                <div class="validation-errors">
+                    <ul>
+                        { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                    </ul>
+                </div>
+            }
+        </div>
+    }
+}
@@ -1,31 +1,54 @@
 use yew::prelude::*;
-use nekotimer_shared::{CountdownBlock, TimerBlock, TimerConfig, WaitBlock};
+use nekotimer_shared::{CountdownBlock, Locale, TimerBlock, TimerConfig, WaitBlock};
+
+use crate::components::schedule_editor::ScheduleEditor;
+use crate::components::share_links::ShareLinks;
+use crate::components::timer_transfer::TimerTransfer;
+use crate::i18n::{min_sec, t, tf};
+use crate::services::locale::use_locale;
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct TimerViewProps {
     pub timer: TimerConfig,
+    /// 共有リンクでの閲覧。編集・スケジュール・共有・移動の操作を出さない
+    #[prop_or_default]
+    pub read_only: bool,
 }
 
-fn format_wait_line(w: &WaitBlock) -> String {
-    format!("{}分{}秒 待機", w.minutes, w.seconds)
+fn format_wait_line(locale: Locale, w: &WaitBlock) -> String {
+    tf(locale, "timer_view.wait_line", &[&min_sec(locale, w.minutes, w.seconds)])
 }
 
-fn format_countdown_line(c: &CountdownBlock) -> String {
-    format!(
-        "{}分{}秒 カウントダウン、{}回繰り返し (インターバル待機: {}分{}秒)",
-        c.minutes, c.seconds, c.repeat_count, c.interval_minutes, c.interval_seconds
+fn format_countdown_line(locale: Locale, c: &CountdownBlock) -> String {
+    tf(
+        locale,
+        "timer_view.countdown_line",
+        &[
+            &min_sec(locale, c.minutes, c.seconds),
+            &c.repeat_count,
+            &min_sec(locale, c.interval_minutes, c.interval_seconds),
+        ],
     )
 }
 
 #[function_component(TimerView)]
 pub fn timer_view(props: &TimerViewProps) -> Html {
-    let state = use_context::<crate::state::AppStateContext>().expect("no context found");
+    // 共有リンクの画面には AppState がない
+    let state = use_context::<crate::state::AppStateContext>();
+    let locale = use_locale();
+
+    // 編集・スケジュール・共有リンクはワークスペースの編集者以上
+    let can_edit = !props.read_only
+        && state
+            .as_ref()
+            .is_some_and(|s| s.role_for(&props.timer).can_edit());
 
     let on_edit = {
         let id = props.timer.id.clone();
-        let state = state.clone();
         Callback::from(move |_: MouseEvent| {
-            state.dispatch(crate::state::AppAction::EditTimer(id.clone()));
+            if let Some(state) = &state {
+                state.dispatch(crate::state::AppAction::EditTimer(id.clone()));
+            }
         })
     };
 
@@ -37,27 +60,36 @@ pub fn timer_view(props: &TimerViewProps) -> Html {
                     html! {
                         <div class="timer-view-block">
                             <div class="timer-view-block-type-row">
-                                <span class="timer-view-block-type">{ block_type_name(block) }</span>
+                                <span class="timer-view-block-type">{ block_type_name(locale, block) }</span>
                                 <span class="timer-view-block-name">{ block_name(block) }</span>
                             </div>
                             <div class="timer-view-block-detail">
-                                { block_detail_line(block) }
+                                { block_detail_line(locale, block) }
                             </div>
                         </div>
                     }
                 })}
             </div>
-            <div class="timer-view-actions">
-                <button class="btn btn-primary" onclick={on_edit}>{"編集する"}</button>
-            </div>
+            if can_edit {
+                <ScheduleEditor timer_id={props.timer.id.clone()} />
+                <ShareLinks timer_id={props.timer.id.clone()} />
+            }
+            if !props.read_only {
+                <TimerTransfer timer={props.timer.clone()} />
+            }
+            if can_edit {
+                <div class="timer-view-actions">
+                    <button class="btn btn-primary" onclick={on_edit}>{ t(locale, "timer_view.edit") }</button>
+                </div>
+            }
         </div>
     }
 }
 
-fn block_type_name(block: &TimerBlock) -> &'static str {
+pub fn block_type_name(locale: Locale, block: &TimerBlock) -> &'static str {
     match block {
-        TimerBlock::Wait(_) => "待機",
-        TimerBlock::Countdown(_) => "カウントダウン",
+        TimerBlock::Wait(_) => t(locale, "block_type.wait"),
+        TimerBlock::Countdown(_) => t(locale, "block_type.countdown"),
     }
 }
 
@@ -68,9 +100,9 @@ fn block_name(block: &TimerBlock) -> &str {
     }
 }
 
-fn block_detail_line(block: &TimerBlock) -> String {
+fn block_detail_line(locale: Locale, block: &TimerBlock) -> String {
     match block {
-        TimerBlock::Wait(w) => format_wait_line(w),
-        TimerBlock::Countdown(c) => format_countdown_line(c),
+        TimerBlock::Wait(w) => format_wait_line(locale, w),
+        TimerBlock::Countdown(c) => format_countdown_line(locale, c),
     }
 }
@@ -0,0 +1,192 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use nekotimer_shared::{ApiToken, Locale, TokenScope};
+
+use crate::i18n::t;
+use crate::services::api;
+use crate::services::datetime::format_datetime;
+use crate::services::locale::use_locale;
+
+fn scope_label(locale: Locale, scope: TokenScope) -> &'static str {
+    let key = match scope {
+        TokenScope::Read => "token.scope_read",
+        TokenScope::Run => "token.scope_run",
+        TokenScope::Manage => "token.scope_manage",
+    };
+    t(locale, key)
+}
+
+fn scope_value(scope: TokenScope) -> &'static str {
+    match scope {
+        TokenScope::Read => "Read",
+        TokenScope::Run => "Run",
+        TokenScope::Manage => "Manage",
+    }
+}
+
+/// スクリプト用の API トークンの作成・一覧・失効
+#[function_component(TokenView)]
+pub fn token_view() -> Html {
+    let locale = use_locale();
+    let tokens = use_state(Vec::<ApiToken>::new);
+    let errors = use_state(Vec::<String>::new);
+    let name = use_state(String::new);
+    let scope = use_state(|| TokenScope::Read);
+    // 作成直後のトークン本体（画面を離れると再表示できない）
+    let created = use_state(|| None::<String>);
+
+    let reload = {
+        let tokens = tokens.clone();
+        let errors = errors.clone();
+        Callback::from(move |_: ()| {
+            let tokens = tokens.clone();
+            let errors = errors.clone();
+            spawn_local(async move {
+                match api::fetch_tokens().await {
+                    Ok(list) => tokens.set(list),
+                    Err(e) => errors.set(vec![e]),
+                }
+            });
+        })
+    };
+
+    {
+        let reload = reload.clone();
+        use_effect_with((), move |_| {
+            reload.emit(());
+            || ()
+        });
+    }
+
+    let on_name = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    let on_scope = {
+        let scope = scope.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(s) = TokenScope::ALL.iter().find(|s| scope_value(**s) == select.value()) {
+                scope.set(*s);
+            }
+        })
+    };
+
+    let on_create = {
+        let name = name.clone();
+        let scope = scope.clone();
+        let errors = errors.clone();
+        let created = created.clone();
+        let reload = reload.clone();
+        Callback::from(move |_: MouseEvent| {
+            let token_name = (*name).clone();
+            let token_scope = *scope;
+            let name = name.clone();
+            let errors = errors.clone();
+            let created = created.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                match api::create_token(&token_name, token_scope).await {
+                    Ok(result) => {
+                        created.set(Some(result.token));
+                        name.set(String::new());
+                        errors.set(Vec::new());
+                        reload.emit(());
+                    }
+                    Err(errs) => errors.set(errs),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="token-view">
+            <h2 class="history-title">{ t(locale, "token.title") }</h2>
+            <p class="webhook-help">
+                { t(locale, "token.help") }
+            </p>
+
+            if let Some(token) = &*created {
+                <div class="token-created">
+                    <p>{ t(locale, "token.created") }</p>
+                    <code>{ token }</code>
+                </div>
+            }
+
+            if tokens.is_empty() {
+                <p class="history-empty">{ t(locale, "token.empty") }</p>
+            } else {
+                <table class="history-table">
+                    <thead>
+                        <tr>
+                            <th>{ t(locale, "token.name") }</th>
+                            <th>{ t(locale, "token.scope") }</th>
+                            <th>{ t(locale, "token.token") }</th>
+                            <th>{ t(locale, "token.created_at") }</th>
+                            <th>{ t(locale, "token.last_used") }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for tokens.iter().map(|token| {
+                            let on_revoke = {
+                                let id = token.id.clone();
+                                let errors = errors.clone();
+                                let reload = reload.clone();
+                                Callback::from(move |_: MouseEvent| {
+                                    let id = id.clone();
+                                    let errors = errors.clone();
+                                    let reload = reload.clone();
+                                    spawn_local(async move {
+                                        match api::revoke_token(&id).await {
+                                            Ok(()) => reload.emit(()),
+                                            Err(e) => errors.set(vec![e]),
+                                        }
+                                    });
+                                })
+                            };
+                            html! {
+                                <tr>
+                                    <td>{ &token.name }</td>
+                                    <td>{ scope_label(locale, token.scope) }</td>
+                                    <td><code>{ format!("{}…", token.prefix) }</code></td>
+                                    <td>{ format_datetime(token.created_at) }</td>
+                                    <td>{ token.last_used_at.map(format_datetime).unwrap_or_else(|| "-".to_string()) }</td>
+                                    <td>
+                                        <button type="button" class="btn btn-outline-danger btn-small" onclick={on_revoke}>
+                                            { t(locale, "token.revoke") }
+                                        </button>
+                                    </td>
+                                </tr>
+                            }
+                        })}
+                    </tbody>
+                </table>
+            }
+
+            <div class="webhook-form token-form">
+                <input type="text" value={(*name).clone()} oninput={on_name} placeholder={t(locale, "token.name_placeholder")} />
+                <select onchange={on_scope}>
+                    { for TokenScope::ALL.iter().map(|s| html! {
+                        <option value={scope_value(*s)} selected={*s == *scope}>{ scope_label(locale, *s) }</option>
+                    })}
+                </select>
+                <button type="button" class="btn btn-primary btn-small" onclick={on_create}>{ t(locale, "common.create") }</button>
+            </div>
+
+            if !errors.is_empty() {
+                <div class="validation-errors">
+                    <ul>
+                        { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                    </ul>
+                </div>
+            }
+        </div>
+    }
+}
@@ -1,9 +1,12 @@
 use yew::prelude::*;
+use crate::i18n::t;
+use crate::services::locale::use_locale;
 use crate::state::{AppAction, AppStateContext};
 
 #[function_component(UnsavedChangesModal)]
 pub fn unsaved_changes_modal() -> Html {
     let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
 
     let on_continue = {
         let state = state.clone();
@@ -22,16 +25,16 @@ pub fn unsaved_changes_modal() -> Html {
     html! {
         <div class="modal-overlay" role="dialog" aria-modal="true" aria-labelledby="unsaved-modal-title">
             <div class="modal-box unsaved-modal">
-                <h2 id="unsaved-modal-title" class="modal-title">{"未保存の変更があります"}</h2>
+                <h2 id="unsaved-modal-title" class="modal-title">{ t(locale, "unsaved.title") }</h2>
                 <p class="modal-message">
-                    {"編集内容が保存されていません。編集を続けますか？それとも変更を破棄して画面を切り替えますか？"}
+                    { t(locale, "unsaved.message") }
                 </p>
                 <div class="modal-actions">
                     <button type="button" class="btn btn-primary" onclick={on_continue}>
-                        {"編集を続ける"}
+                        { t(locale, "unsaved.continue") }
                     </button>
                     <button type="button" class="btn btn-outline-danger" onclick={on_discard}>
-                        {"編集を止める"}
+                        { t(locale, "unsaved.discard") }
                     </button>
                 </div>
             </div>
@@ -0,0 +1,99 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use nekotimer_shared::{EventVolume, SoundEvent, SoundPreferences};
+
+use crate::components::sound_set_editor::sound_event_label;
+use crate::i18n::t;
+use crate::services::locale::use_locale;
+use crate::services::sound_preferences;
+
+/// 変えた設定はすぐ保存して反映する（実行中の音にも効く）
+fn update(preferences: &UseStateHandle<SoundPreferences>, f: impl FnOnce(&mut SoundPreferences)) {
+    let mut next = (**preferences).clone();
+    f(&mut next);
+    sound_preferences::save_preferences(&next);
+    preferences.set(next);
+}
+
+/// range（0〜100）の値を音量（0.0〜1.0）にする
+fn volume_from(e: &InputEvent) -> Option<f32> {
+    let input: HtmlInputElement = e.target_unchecked_into();
+    input.value().parse::<f32>().ok().map(|v| (v / 100.0).clamp(0.0, 1.0))
+}
+
+fn percent(volume: f32) -> String {
+    ((volume * 100.0).round() as u32).to_string()
+}
+
+/// 実行中の画面に置く全体の音量・消音と、タイミングごとの有効・音量の設定
+#[function_component(VolumeControl)]
+pub fn volume_control() -> Html {
+    let locale = use_locale();
+    let preferences = use_state(sound_preferences::preferences);
+
+    let on_mute = {
+        let preferences = preferences.clone();
+        Callback::from(move |_: MouseEvent| update(&preferences, |p| p.muted = !p.muted))
+    };
+
+    let on_volume = {
+        let preferences = preferences.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(volume) = volume_from(&e) {
+                update(&preferences, |p| {
+                    p.volume = volume;
+                    p.muted = false;
+                });
+            }
+        })
+    };
+
+    let muted = preferences.muted;
+
+    html! {
+        <div class="volume-control">
+            <div class="volume-master">
+                <button type="button" class={classes!("btn", "btn-secondary", "btn-small", muted.then_some("active"))}
+                    onclick={on_mute} aria-pressed={muted.to_string()}
+                    title={if muted { t(locale, "volume.unmute") } else { t(locale, "volume.mute") }}>
+                    { if muted { "🔇" } else { "🔊" } }
+                </button>
+                <input type="range" min="0" max="100" step="5" value={percent(preferences.volume)}
+                    oninput={on_volume} disabled={muted} title={t(locale, "volume.master")} />
+            </div>
+            <details class="volume-events">
+                <summary>{ t(locale, "volume.events") }</summary>
+                { for SoundEvent::ALL.iter().map(|&event| {
+                    let setting = preferences.event(event);
+                    let on_enabled = {
+                        let preferences = preferences.clone();
+                        Callback::from(move |e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            let enabled = input.checked();
+                            update(&preferences, |p| p.set_event(event, EventVolume { enabled, ..setting }));
+                        })
+                    };
+                    let on_event_volume = {
+                        let preferences = preferences.clone();
+                        Callback::from(move |e: InputEvent| {
+                            if let Some(volume) = volume_from(&e) {
+                                update(&preferences, |p| p.set_event(event, EventVolume { volume, ..setting }));
+                            }
+                        })
+                    };
+                    html! {
+                        <div class="volume-event">
+                            <label>
+                                <input type="checkbox" checked={setting.enabled} onchange={on_enabled} />
+                                { sound_event_label(locale, event) }
+                            </label>
+                            <input type="range" min="0" max="100" step="5" value={percent(setting.volume)}
+                                oninput={on_event_volume} disabled={!setting.enabled} />
+                        </div>
+                    }
+                })}
+            </details>
+        </div>
+    }
+}
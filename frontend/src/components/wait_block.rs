@@ -1,11 +1,25 @@
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use nekotimer_shared::{TimerBlock, WaitBlock};
+use nekotimer_shared::{
+    SoundEvent, SoundSet, TimerBlock, ValidationError, WaitBlock, ANNOUNCEMENT_MAX_LEN,
+};
+
+use crate::components::sound_set_editor::SoundSetEditor;
+
+use crate::i18n::t;
+use crate::services::locale::use_locale;
+use crate::services::validation::field_message;
+use crate::state::AppStateContext;
 
 #[derive(Properties, PartialEq)]
 pub struct WaitBlockProps {
     pub block: WaitBlock,
+    /// ブロックの位置（エラーの項目名 `blocks[i].seconds` に使う）
+    pub index: usize,
+    /// 入力中のエラー
+    #[prop_or_default]
+    pub errors: Vec<ValidationError>,
     pub on_change: Callback<TimerBlock>,
 }
 
@@ -13,6 +27,8 @@ pub struct WaitBlockProps {
 pub fn wait_block_editor(props: &WaitBlockProps) -> Html {
     let block = props.block.clone();
     let on_change = props.on_change.clone();
+    let locale = use_locale();
+    let state = use_context::<AppStateContext>().expect("no context found");
 
     let on_name = {
         let block = block.clone();
@@ -47,22 +63,67 @@ pub fn wait_block_editor(props: &WaitBlockProps) -> Html {
         })
     };
 
+    let on_sounds = {
+        let block = block.clone();
+        let on_change = on_change.clone();
+        Callback::from(move |sounds: SoundSet| {
+            let mut b = block.clone();
+            b.sounds = sounds;
+            on_change.emit(TimerBlock::Wait(b));
+        })
+    };
+
+    // 空欄なら読み上げの設定のテンプレートを使う
+    let on_announcement = {
+        let block = block.clone();
+        let on_change = on_change.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut b = block.clone();
+            let value = input.value();
+            b.announcement = (!value.trim().is_empty()).then_some(value);
+            on_change.emit(TimerBlock::Wait(b));
+        })
+    };
+
+    let max_minutes = state.validation_policy.max_minutes.to_string();
+    let field = |name: &str| format!("blocks[{}].{}", props.index, name);
+    let minutes_error = field_message(&props.errors, &field("minutes"));
+    let seconds_error = field_message(&props.errors, &field("seconds"));
+
     html! {
         <div class="block-fields">
             <div class="block-field">
-                <label>{"名前"}</label>
-                <input type="text" value={block.name.clone()} oninput={on_name} placeholder="ブロック名" />
+                <label>{ t(locale, "block.name") }</label>
+                <input type="text" value={block.name.clone()} oninput={on_name} placeholder={t(locale, "block.name_placeholder")} />
             </div>
             <div class="block-field">
-                <label>{"分"}</label>
+                <label>{ t(locale, "block.minutes") }</label>
                 <input type="number" value={block.minutes.to_string()} oninput={on_minutes}
-                    min="0" max="1440" />
+                    min="0" max={max_minutes} data-field={field("minutes")}
+                    class={classes!(minutes_error.is_some().then_some("field-invalid"))}
+                    title={minutes_error.clone()} />
             </div>
             <div class="block-field">
-                <label>{"秒"}</label>
+                <label>{ t(locale, "block.seconds") }</label>
                 <input type="number" value={block.seconds.to_string()} oninput={on_seconds}
-                    min="1" max="59" />
+                    min="1" max="59" data-field={field("seconds")}
+                    class={classes!(seconds_error.is_some().then_some("field-invalid"))}
+                    title={seconds_error.clone()} />
             </div>
+            <details class="block-sounds">
+                <summary>{ t(locale, "sound.block_summary") }</summary>
+                <SoundSetEditor sounds={block.sounds.clone()}
+                    events={vec![SoundEvent::BlockStart, SoundEvent::BlockEnd, SoundEvent::Tick]}
+                    on_change={on_sounds} />
+                <div class="block-field block-announcement">
+                    <label>{ t(locale, "block.announcement") }</label>
+                    <input type="text" value={block.announcement.clone().unwrap_or_default()}
+                        oninput={on_announcement} maxlength={ANNOUNCEMENT_MAX_LEN.to_string()}
+                        data-field={format!("blocks[{}].announcement", props.index)}
+                        placeholder={t(locale, "block.announcement_placeholder")} />
+                </div>
+            </details>
         </div>
     }
 }
@@ -0,0 +1,334 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use nekotimer_shared::{Locale, Webhook, WebhookDelivery, WebhookEvent};
+
+use crate::i18n::{t, tf};
+use crate::services::api;
+use crate::services::datetime::format_datetime;
+use crate::services::locale::use_locale;
+use crate::state::AppStateContext;
+
+fn event_label(locale: Locale, event: WebhookEvent) -> &'static str {
+    let key = match event {
+        WebhookEvent::RunStarted => "event.run_started",
+        WebhookEvent::BlockStarted => "event.block_started",
+        WebhookEvent::RepetitionEnded => "event.repetition_ended",
+        WebhookEvent::BlockEnded => "event.block_ended",
+        WebhookEvent::Completed => "event.completed",
+        WebhookEvent::Aborted => "event.aborted",
+        WebhookEvent::Test => "event.test",
+    };
+    t(locale, key)
+}
+
+fn events_line(locale: Locale, events: &[WebhookEvent]) -> String {
+    if events.is_empty() {
+        t(locale, "webhook.all_events").to_string()
+    } else {
+        events
+            .iter()
+            .map(|e| event_label(locale, *e))
+            .collect::<Vec<_>>()
+            .join(t(locale, "common.list_sep"))
+    }
+}
+
+/// 例: 「成功 (200)」「失敗: HTTP 500 (3回試行)」
+fn delivery_result(locale: Locale, delivery: &WebhookDelivery) -> String {
+    if delivery.success {
+        match delivery.status {
+            Some(status) => tf(locale, "webhook.success_status", &[&status]),
+            None => t(locale, "webhook.success").to_string(),
+        }
+    } else {
+        let error = delivery
+            .error
+            .as_deref()
+            .unwrap_or(t(locale, "webhook.unknown_error"));
+        tf(locale, "webhook.failed", &[&error, &delivery.attempts])
+    }
+}
+
+/// Webhook の登録・テスト送信と送信ログ
+#[function_component(WebhookView)]
+pub fn webhook_view() -> Html {
+    let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
+    let webhooks = use_state(Vec::<Webhook>::new);
+    let deliveries = use_state(Vec::<WebhookDelivery>::new);
+    let errors = use_state(Vec::<String>::new);
+    let url = use_state(String::new);
+    let timer_id = use_state(|| None::<String>);
+    let events = use_state(Vec::<WebhookEvent>::new);
+    let is_admin = state.user.as_ref().is_some_and(|u| u.is_admin);
+
+    let reload = {
+        let webhooks = webhooks.clone();
+        let deliveries = deliveries.clone();
+        let errors = errors.clone();
+        Callback::from(move |_: ()| {
+            let webhooks = webhooks.clone();
+            let deliveries = deliveries.clone();
+            let errors = errors.clone();
+            spawn_local(async move {
+                match api::fetch_webhooks().await {
+                    Ok(list) => webhooks.set(list),
+                    Err(e) => errors.set(vec![e]),
+                }
+                match api::fetch_webhook_deliveries().await {
+                    Ok(list) => deliveries.set(list),
+                    Err(e) => errors.set(vec![e]),
+                }
+            });
+        })
+    };
+
+    {
+        let reload = reload.clone();
+        use_effect_with((), move |_| {
+            reload.emit(());
+            || ()
+        });
+    }
+
+    let on_url = {
+        let url = url.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            url.set(input.value());
+        })
+    };
+
+    let on_timer = {
+        let timer_id = timer_id.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            timer_id.set(if value.is_empty() { None } else { Some(value) });
+        })
+    };
+
+    let on_add = {
+        let url = url.clone();
+        let timer_id = timer_id.clone();
+        let events = events.clone();
+        let errors = errors.clone();
+        let reload = reload.clone();
+        Callback::from(move |_: MouseEvent| {
+            let webhook = Webhook {
+                id: String::new(),
+                url: url.trim().to_string(),
+                timer_id: (*timer_id).clone(),
+                events: (*events).clone(),
+                secret: String::new(),
+                enabled: true,
+                owner: None,
+            };
+            if let Err(errs) = webhook.validate() {
+                errors.set(errs.iter().map(|e| e.render(locale)).collect());
+                return;
+            }
+            let url = url.clone();
+            let errors = errors.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                match api::create_webhook(&webhook).await {
+                    Ok(_) => {
+                        url.set(String::new());
+                        errors.set(Vec::new());
+                        reload.emit(());
+                    }
+                    Err(errs) => errors.set(errs),
+                }
+            });
+        })
+    };
+
+    let timer_name = |id: &Option<String>| -> String {
+        match id {
+            None => t(locale, "webhook.all_timers").to_string(),
+            Some(id) => state
+                .timers
+                .iter()
+                .find(|t| t.id == *id)
+                .map(|t| t.name.clone())
+                .unwrap_or_else(|| t(locale, "webhook.deleted_timer").to_string()),
+        }
+    };
+
+    html! {
+        <div class="webhook-view">
+            <h2 class="history-title">{ t(locale, "webhook.title") }</h2>
+            <p class="webhook-help">
+                { t(locale, "webhook.help") }
+            </p>
+
+            if webhooks.is_empty() {
+                <p class="history-empty">{ t(locale, "webhook.empty") }</p>
+            } else {
+                <ul class="schedule-list">
+                    { for webhooks.iter().map(|w| {
+                        let on_toggle = {
+                            let mut toggled = w.clone();
+                            toggled.enabled = !toggled.enabled;
+                            let errors = errors.clone();
+                            let reload = reload.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                let toggled = toggled.clone();
+                                let errors = errors.clone();
+                                let reload = reload.clone();
+                                spawn_local(async move {
+                                    match api::update_webhook(&toggled).await {
+                                        Ok(_) => reload.emit(()),
+                                        Err(errs) => errors.set(errs),
+                                    }
+                                });
+                            })
+                        };
+                        let on_test = {
+                            let id = w.id.clone();
+                            let errors = errors.clone();
+                            let reload = reload.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                let id = id.clone();
+                                let errors = errors.clone();
+                                let reload = reload.clone();
+                                spawn_local(async move {
+                                    match api::test_webhook(&id).await {
+                                        Ok(_) => reload.emit(()),
+                                        Err(e) => errors.set(vec![e]),
+                                    }
+                                });
+                            })
+                        };
+                        let on_delete = {
+                            let id = w.id.clone();
+                            let errors = errors.clone();
+                            let reload = reload.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                let id = id.clone();
+                                let errors = errors.clone();
+                                let reload = reload.clone();
+                                spawn_local(async move {
+                                    match api::delete_webhook(&id).await {
+                                        Ok(()) => reload.emit(()),
+                                        Err(e) => errors.set(vec![e]),
+                                    }
+                                });
+                            })
+                        };
+                        let class = if w.enabled { "webhook-item" } else { "webhook-item disabled" };
+                        html! {
+                            <li {class}>
+                                <div class="webhook-url">{ &w.url }</div>
+                                <div class="webhook-meta">
+                                    { format!("{} / {}", timer_name(&w.timer_id), events_line(locale, &w.events)) }
+                                </div>
+                                if !w.secret.is_empty() {
+                                    <div class="webhook-secret">
+                                        { t(locale, "webhook.secret") }<code>{ &w.secret }</code>
+                                    </div>
+                                }
+                                <div class="schedule-item-actions">
+                                    <button type="button" class="btn btn-secondary btn-small" onclick={on_test}>
+                                        { t(locale, "webhook.test") }
+                                    </button>
+                                    <button type="button" class="btn btn-secondary btn-small" onclick={on_toggle}>
+                                        { if w.enabled { t(locale, "common.disable") } else { t(locale, "common.enable") } }
+                                    </button>
+                                    <button type="button" class="btn btn-outline-danger btn-small" onclick={on_delete}>
+                                        { t(locale, "common.delete") }
+                                    </button>
+                                </div>
+                            </li>
+                        }
+                    })}
+                </ul>
+            }
+
+            <div class="webhook-form">
+                <input type="url" class="webhook-url-input" value={(*url).clone()}
+                    oninput={on_url} placeholder="https://example.com/hook" />
+                <select onchange={on_timer}>
+                    // すべてのタイマー向けの Webhook は管理者だけが登録できる
+                    if is_admin {
+                        <option value="" selected={timer_id.is_none()}>{ t(locale, "webhook.all_timers") }</option>
+                    } else {
+                        <option value="" disabled=true selected={timer_id.is_none()}>{ t(locale, "webhook.choose_timer") }</option>
+                    }
+                    { for state.timers.iter().map(|timer| html! {
+                        <option value={timer.id.clone()} selected={timer_id.as_deref() == Some(timer.id.as_str())}>
+                            { &timer.name }
+                        </option>
+                    })}
+                </select>
+                <div class="webhook-events">
+                    { for WebhookEvent::SUBSCRIBABLE.iter().map(|event| {
+                        let event = *event;
+                        let active = events.contains(&event);
+                        let on_event = {
+                            let events = events.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                let mut next = (*events).clone();
+                                if let Some(pos) = next.iter().position(|e| *e == event) {
+                                    next.remove(pos);
+                                } else {
+                                    next.push(event);
+                                }
+                                events.set(next);
+                            })
+                        };
+                        html! {
+                            <button type="button"
+                                class={if active { "webhook-event active" } else { "webhook-event" }}
+                                onclick={on_event}>
+                                { event_label(locale, event) }
+                            </button>
+                        }
+                    })}
+                </div>
+                <button type="button" class="btn btn-primary btn-small" onclick={on_add}>{ t(locale, "common.add") }</button>
+            </div>
+            <p class="webhook-help">{ t(locale, "webhook.events_help") }</p>
+
+            if !errors.is_empty() {
+                <div class="validation-errors">
+                    <ul>
+                        { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                    </ul>
+                </div>
+            }
+
+            <h3 class="history-section-title">{ t(locale, "webhook.log") }</h3>
+            if deliveries.is_empty() {
+                <p class="history-empty">{ t(locale, "webhook.log_empty") }</p>
+            } else {
+                <table class="history-table">
+                    <thead>
+                        <tr>
+                            <th>{ t(locale, "webhook.time") }</th>
+                            <th>{ t(locale, "webhook.event") }</th>
+                            <th>{"URL"}</th>
+                            <th>{ t(locale, "webhook.result") }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for deliveries.iter().map(|d| {
+                            let class = if d.success { "delivery-ok" } else { "delivery-failed" };
+                            html! {
+                                <tr>
+                                    <td>{ format_datetime(d.timestamp) }</td>
+                                    <td>{ event_label(locale, d.event) }</td>
+                                    <td class="webhook-url">{ &d.url }</td>
+                                    <td {class}>{ delivery_result(locale, d) }</td>
+                                </tr>
+                            }
+                        })}
+                    </tbody>
+                </table>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,301 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use nekotimer_shared::{Locale, Workspace, WorkspaceRole};
+
+use crate::i18n::t;
+use crate::services::api;
+use crate::services::locale::use_locale;
+use crate::state::{AppAction, AppStateContext};
+
+pub fn role_label(locale: Locale, role: WorkspaceRole) -> &'static str {
+    let key = match role {
+        WorkspaceRole::Owner => "role.owner",
+        WorkspaceRole::Editor => "role.editor",
+        WorkspaceRole::Runner => "role.runner",
+        WorkspaceRole::Viewer => "role.viewer",
+    };
+    t(locale, key)
+}
+
+fn role_value(role: WorkspaceRole) -> &'static str {
+    match role {
+        WorkspaceRole::Owner => "Owner",
+        WorkspaceRole::Editor => "Editor",
+        WorkspaceRole::Runner => "Runner",
+        WorkspaceRole::Viewer => "Viewer",
+    }
+}
+
+fn parse_role(value: &str) -> Option<WorkspaceRole> {
+    WorkspaceRole::ALL.iter().copied().find(|r| role_value(*r) == value)
+}
+
+fn role_options(locale: Locale, selected: WorkspaceRole) -> Html {
+    html! {
+        { for WorkspaceRole::ALL.iter().map(|r| html! {
+            <option value={role_value(*r)} selected={*r == selected}>{ role_label(locale, *r) }</option>
+        })}
+    }
+}
+
+/// ワークスペースの一覧を取り直して AppState に反映する
+fn reload_workspaces(state: AppStateContext, errors: UseStateHandle<Vec<String>>) {
+    spawn_local(async move {
+        match api::fetch_workspaces().await {
+            Ok(list) => state.dispatch(AppAction::SetWorkspaces(list)),
+            Err(e) => errors.set(vec![e]),
+        }
+    });
+}
+
+/// チームで共有するワークスペースの作成とメンバーの管理
+#[function_component(WorkspaceView)]
+pub fn workspace_view() -> Html {
+    let state = use_context::<AppStateContext>().expect("no context found");
+    let locale = use_locale();
+    let errors = use_state(Vec::<String>::new);
+    let name = use_state(String::new);
+
+    {
+        let state = state.clone();
+        let errors = errors.clone();
+        use_effect_with((), move |_| {
+            reload_workspaces(state, errors);
+            || ()
+        });
+    }
+
+    let on_name = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    let on_create = {
+        let state = state.clone();
+        let name = name.clone();
+        let errors = errors.clone();
+        Callback::from(move |_: MouseEvent| {
+            let workspace_name = (*name).clone();
+            let state = state.clone();
+            let name = name.clone();
+            let errors = errors.clone();
+            spawn_local(async move {
+                match api::create_workspace(&workspace_name).await {
+                    Ok(_) => {
+                        name.set(String::new());
+                        errors.set(Vec::new());
+                        reload_workspaces(state, errors);
+                    }
+                    Err(errs) => errors.set(errs),
+                }
+            });
+        })
+    };
+
+    let on_changed = {
+        let state = state.clone();
+        let errors = errors.clone();
+        Callback::from(move |result: Result<(), String>| match result {
+            Ok(()) => {
+                errors.set(Vec::new());
+                reload_workspaces(state.clone(), errors.clone());
+            }
+            Err(e) => errors.set(vec![e]),
+        })
+    };
+
+    html! {
+        <div class="workspace-view">
+            <h2 class="history-title">{ t(locale, "workspace.title") }</h2>
+            <p class="webhook-help">
+                { t(locale, "workspace.help") }
+            </p>
+
+            if state.workspaces.is_empty() {
+                <p class="history-empty">{ t(locale, "workspace.empty") }</p>
+            }
+            { for state.workspaces.iter().map(|w| html! {
+                <WorkspaceCard
+                    key={w.id.clone()}
+                    workspace={w.clone()}
+                    user_id={state.user.as_ref().map(|u| u.id.clone()).unwrap_or_default()}
+                    on_changed={on_changed.clone()}
+                />
+            })}
+
+            <div class="webhook-form token-form">
+                <input type="text" value={(*name).clone()} oninput={on_name} placeholder={t(locale, "workspace.name_placeholder")} />
+                <button type="button" class="btn btn-primary btn-small" onclick={on_create}>{ t(locale, "common.create") }</button>
+            </div>
+
+            if !errors.is_empty() {
+                <div class="validation-errors">
+                    <ul>
+                        { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                    </ul>
+                </div>
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct WorkspaceCardProps {
+    workspace: Workspace,
+    /// ログイン中のユーザー（自分の脱退ボタン用）
+    user_id: String,
+    /// 変更後に呼ぶ。Err はエラー表示用のメッセージ
+    on_changed: Callback<Result<(), String>>,
+}
+
+#[function_component(WorkspaceCard)]
+fn workspace_card(props: &WorkspaceCardProps) -> Html {
+    let locale = use_locale();
+    let username = use_state(String::new);
+    let role = use_state(|| WorkspaceRole::Runner);
+    let workspace = &props.workspace;
+    let can_manage = workspace.role.can_manage();
+
+    let on_username = {
+        let username = username.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            username.set(input.value());
+        })
+    };
+
+    let on_role = {
+        let role = role.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(r) = parse_role(&select.value()) {
+                role.set(r);
+            }
+        })
+    };
+
+    let on_add = {
+        let id = workspace.id.clone();
+        let username = username.clone();
+        let role = role.clone();
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |_: MouseEvent| {
+            let id = id.clone();
+            let name = (*username).clone();
+            let member_role = *role;
+            let username = username.clone();
+            let on_changed = on_changed.clone();
+            spawn_local(async move {
+                match api::set_workspace_member(&id, &name, member_role).await {
+                    Ok(_) => {
+                        username.set(String::new());
+                        on_changed.emit(Ok(()));
+                    }
+                    Err(errs) => on_changed.emit(Err(errs.join(", "))),
+                }
+            });
+        })
+    };
+
+    let on_delete = {
+        let id = workspace.id.clone();
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |_: MouseEvent| {
+            let id = id.clone();
+            let on_changed = on_changed.clone();
+            spawn_local(async move {
+                on_changed.emit(api::delete_workspace(&id).await);
+            });
+        })
+    };
+
+    html! {
+        <div class="workspace-card">
+            <div class="workspace-card-header">
+                <h3 class="schedule-title">{ &workspace.name }</h3>
+                <span class="workspace-role">{ role_label(locale, workspace.role) }</span>
+                if can_manage {
+                    <button type="button" class="btn btn-outline-danger btn-small" onclick={on_delete}>
+                        { t(locale, "common.delete") }
+                    </button>
+                }
+            </div>
+            <table class="history-table">
+                <thead>
+                    <tr>
+                        <th>{ t(locale, "workspace.member") }</th>
+                        <th>{ t(locale, "workspace.role") }</th>
+                        <th></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for workspace.members.iter().map(|m| {
+                        let on_member_role = {
+                            let id = workspace.id.clone();
+                            let name = m.username.clone();
+                            let on_changed = props.on_changed.clone();
+                            Callback::from(move |e: Event| {
+                                let select: HtmlSelectElement = e.target_unchecked_into();
+                                let Some(member_role) = parse_role(&select.value()) else {
+                                    return;
+                                };
+                                let id = id.clone();
+                                let name = name.clone();
+                                let on_changed = on_changed.clone();
+                                spawn_local(async move {
+                                    let result = api::set_workspace_member(&id, &name, member_role).await;
+                                    on_changed.emit(result.map(|_| ()).map_err(|errs| errs.join(", ")));
+                                });
+                            })
+                        };
+                        let on_remove = {
+                            let id = workspace.id.clone();
+                            let user_id = m.user_id.clone();
+                            let on_changed = props.on_changed.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                let id = id.clone();
+                                let user_id = user_id.clone();
+                                let on_changed = on_changed.clone();
+                                spawn_local(async move {
+                                    on_changed.emit(api::remove_workspace_member(&id, &user_id).await);
+                                });
+                            })
+                        };
+                        let is_self = m.user_id == props.user_id;
+                        html! {
+                            <tr>
+                                <td>{ &m.username }</td>
+                                <td>
+                                    if can_manage {
+                                        <select onchange={on_member_role}>{ role_options(locale, m.role) }</select>
+                                    } else {
+                                        { role_label(locale, m.role) }
+                                    }
+                                </td>
+                                <td>
+                                    if can_manage || is_self {
+                                        <button type="button" class="btn btn-outline-danger btn-small" onclick={on_remove}>
+                                            { t(locale, if is_self { "workspace.leave" } else { "workspace.remove" }) }
+                                        </button>
+                                    }
+                                </td>
+                            </tr>
+                        }
+                    })}
+                </tbody>
+            </table>
+            if can_manage {
+                <div class="webhook-form token-form">
+                    <input type="text" value={(*username).clone()} oninput={on_username} placeholder={t(locale, "workspace.username")} />
+                    <select onchange={on_role}>{ role_options(locale, *role) }</select>
+                    <button type="button" class="btn btn-primary btn-small" onclick={on_add}>{ t(locale, "workspace.add_member") }</button>
+                </div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,271 @@
+//! 英語のメッセージ
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("duration.min_sec", "{}m {}s"),
+    ("duration.hour_min", "{}h {}m"),
+    ("builder.title_new", "New timer"),
+    ("builder.title_edit", "Edit timer"),
+    ("builder.name", "Timer name"),
+    ("builder.name_placeholder", "Enter a timer name..."),
+    ("builder.save", "Save"),
+    ("block.name", "Name"),
+    ("block.name_placeholder", "Block name"),
+    ("block.minutes", "Minutes"),
+    ("block.seconds", "Seconds"),
+    ("block.repeat_count", "Repeat count"),
+    ("block.interval_minutes", "Interval minutes"),
+    ("block.interval_seconds", "Interval seconds"),
+    ("canvas.title", "Blocks"),
+    ("canvas.add_wait", "+ Wait block"),
+    ("canvas.add_countdown", "+ Countdown block"),
+    ("sidebar.workspace_select", "Space to show"),
+    ("sidebar.personal", "Personal"),
+    ("sidebar.run", "Run"),
+    ("sidebar.add", "Add"),
+    ("sidebar.history", "History"),
+    ("sidebar.webhooks", "Webhooks"),
+    ("sidebar.tokens", "Tokens"),
+    ("sidebar.workspaces", "Teams"),
+    ("sidebar.admin", "Admin"),
+    ("sidebar.logout", "Log out"),
+    ("locale.select", "Language"),
+    ("login.username", "Username"),
+    ("login.password", "Password"),
+    ("login.submit", "Log in"),
+    ("no_content.hint", "Press \"Add\" in the sidebar to create a timer"),
+    ("unsaved.title", "You have unsaved changes"),
+    (
+        "unsaved.message",
+        "Your edits have not been saved. Keep editing, or discard the changes and switch screens?",
+    ),
+    ("unsaved.continue", "Keep editing"),
+    ("unsaved.discard", "Discard changes"),
+    ("transfer.title", "Move / copy"),
+    ("transfer.move", "Move"),
+    ("transfer.copy", "Copy"),
+    ("transfer.copied", "Copied"),
+    ("shared.run_here", "Run on this device"),
+    ("shared.expires", "This link expires: {}"),
+    ("common.loading", "Loading…"),
+    ("block_type.wait", "Wait"),
+    ("block_type.countdown", "Countdown"),
+    ("timer_view.wait_line", "Wait {}"),
+    ("timer_view.countdown_line", "Count down {}, repeat {} times (interval: {})"),
+    ("timer_view.edit", "Edit"),
+    ("running.wait_line", "Wait {}"),
+    ("running.countdown_line", "Count down {} ({} times, interval {})"),
+    ("running.title", "Timer running"),
+    ("running.paused", "Paused"),
+    ("running.complete", "Done!"),
+    ("running.repetition", "Round {} / {}"),
+    ("running.phase_countdown", "Countdown"),
+    ("running.phase_interval", "Interval"),
+    ("running.remaining", "{} s left"),
+    ("running.resume", "Resume"),
+    ("running.pause", "Pause"),
+    ("running.skip", "Skip"),
+    ("running.close", "Close"),
+    ("running.stop", "Stop"),
+    ("history.block_n", "Block {}"),
+    ("history.completed", "Completed"),
+    ("history.aborted", "Aborted"),
+    ("history.aborted_at", "Aborted ({})"),
+    ("history.aborted_at_run", "Aborted ({} {}/{})"),
+    ("history.day_runs", "{}: {} runs"),
+    ("history.week_runs", "Week of {}: {} runs ({} completed)"),
+    ("history.total_runs", "Runs"),
+    ("history.runs_value", "{}"),
+    ("history.completion_rate", "Completion rate"),
+    ("history.total_time", "Total time"),
+    ("history.streak", "Streak"),
+    ("history.streak_days", "{} days"),
+    ("history.longest_streak", " (longest {} days)"),
+    ("history.calendar", "Calendar"),
+    ("history.weekly", "Runs per week"),
+    ("history.per_timer", "By timer"),
+    ("history.timer", "Timer"),
+    ("history.title", "Run history"),
+    ("history.all", "All"),
+    ("history.recent", "Recent runs"),
+    ("history.empty", "No runs yet"),
+    ("event.run_started", "Run started"),
+    ("event.block_started", "Block started"),
+    ("event.repetition_ended", "Repetition ended"),
+    ("event.block_ended", "Block ended"),
+    ("event.completed", "Completed"),
+    ("event.aborted", "Aborted"),
+    ("event.test", "Test"),
+    ("common.list_sep", ", "),
+    ("webhook.all_events", "All events"),
+    ("webhook.success", "Success"),
+    ("webhook.success_status", "Success ({})"),
+    ("webhook.failed", "Failed: {} ({} attempts)"),
+    ("webhook.unknown_error", "Unknown error"),
+    ("webhook.all_timers", "All timers"),
+    ("webhook.deleted_timer", "(deleted timer)"),
+    ("webhook.title", "Webhooks"),
+    (
+        "webhook.help",
+        "Run events are POSTed as JSON. The HMAC-SHA256 of the body is sent in the X-Nekotimer-Signature header (sha256=...).",
+    ),
+    ("webhook.empty", "No webhooks registered"),
+    ("webhook.secret", "Secret: "),
+    ("webhook.test", "Send test"),
+    ("common.disable", "Disable"),
+    ("common.enable", "Enable"),
+    ("common.delete", "Delete"),
+    ("common.add", "Add"),
+    ("webhook.events_help", "If no event is selected, all events are sent."),
+    ("webhook.log", "Delivery log"),
+    ("webhook.log_empty", "Nothing sent yet"),
+    ("webhook.time", "Time"),
+    ("webhook.event", "Event"),
+    ("webhook.result", "Result"),
+    ("day.mon", "Mon"),
+    ("day.tue", "Tue"),
+    ("day.wed", "Wed"),
+    ("day.thu", "Thu"),
+    ("day.fri", "Fri"),
+    ("day.sat", "Sat"),
+    ("day.sun", "Sun"),
+    ("schedule.time_required", "Enter a time"),
+    ("schedule.title", "Schedules"),
+    ("schedule.empty", "No scheduled runs"),
+    ("schedule.next", "Next: {}"),
+    ("schedule.weekly", "Days and time"),
+    ("schedule.cron", "Cron expression"),
+    ("schedule.timezone", "Time zone"),
+    ("token.scope_read", "Read only"),
+    ("token.scope_run", "Read and run"),
+    ("token.scope_manage", "Everything including edits"),
+    ("token.title", "API tokens"),
+    (
+        "token.help",
+        "Scripts can call the API with Authorization: Bearer <token>. Tokens cannot manage tokens or log in.",
+    ),
+    (
+        "token.created",
+        "Token created. It will not be shown again once you leave this screen, so copy it now.",
+    ),
+    ("token.empty", "No tokens"),
+    ("token.name", "Name"),
+    ("token.scope", "Scope"),
+    ("token.token", "Token"),
+    ("token.created_at", "Created"),
+    ("token.last_used", "Last used"),
+    ("token.revoke", "Revoke"),
+    ("token.name_placeholder", "Token name (e.g. backup-script)"),
+    ("common.create", "Create"),
+    ("role.owner", "Owner"),
+    ("role.editor", "Editor"),
+    ("role.runner", "Runner"),
+    ("role.viewer", "Viewer"),
+    ("workspace.title", "Workspaces"),
+    (
+        "workspace.help",
+        "Timers in a workspace are shared with all members. Viewers can only look, runners can also run, editors can also edit timers, and owners can also manage members.",
+    ),
+    ("workspace.empty", "You are not a member of any workspace"),
+    ("workspace.name_placeholder", "Workspace name"),
+    ("workspace.member", "Member"),
+    ("workspace.role", "Role"),
+    ("workspace.leave", "Leave"),
+    ("workspace.remove", "Remove"),
+    ("workspace.username", "Username"),
+    ("workspace.add_member", "Add member"),
+    ("share.expiry_none", "No expiry"),
+    ("share.expiry_1d", "1 day"),
+    ("share.expiry_7d", "7 days"),
+    ("share.expiry_30d", "30 days"),
+    ("share.title", "Share links"),
+    ("share.empty", "No share links"),
+    ("share.runnable", "Runnable"),
+    ("share.expires", "Expires: {}"),
+    ("share.revoke", "Revoke"),
+    ("share.allow_run", "Allow running on the viewer's device"),
+    ("share.expiry", "Expiry"),
+    ("share.create", "Create link"),
+    ("sidebar.sounds", "Sounds"),
+    ("sound_event.block_start", "Block start"),
+    ("sound_event.block_end", "Block end"),
+    ("sound_event.tick", "Final seconds tick"),
+    ("sound_event.repetition", "Next repetition"),
+    ("sound_event.finish", "Timer finished"),
+    ("sound.tick", "Tick"),
+    ("sound.block_end", "Block end chime"),
+    ("sound.all_end", "Finish chime"),
+    ("sound.countdown_blk_next", "Next repetition chime"),
+    ("sound.silent", "Silent"),
+    ("sound.inherit", "Default"),
+    ("sound.preview", "Preview"),
+    ("sound.block_summary", "Sounds & announcement"),
+    ("sound.timer_summary", "Sounds"),
+    ("sound.profile", "Profile"),
+    ("sound.no_profile", "None"),
+    ("sound_profile.title", "Sound profiles"),
+    (
+        "sound_profile.help",
+        "Create named sets of sounds and pick them under a timer's Sounds. Sounds chosen on a block or timer take precedence over the profile. Profiles are shared with everyone; only their creator and admins can change them.",
+    ),
+    ("sound_profile.empty", "No sound profiles"),
+    ("sound_profile.name_placeholder", "Profile name (e.g. Quiet office)"),
+    ("sound_profile.save", "Save"),
+    ("sidebar.sound_library", "Sound library"),
+    ("sound.custom_group", "Uploaded sounds"),
+    ("sound_library.title", "Sound library"),
+    (
+        "sound_library.help",
+        "Upload audio files such as MP3, WAV or OGG (up to {} KB) to use them as sounds for timers, blocks and sound profiles. Deleting a sound resets the places that used it to the default sound.",
+    ),
+    ("sound_library.empty", "No sounds uploaded yet"),
+    ("sound_library.name", "Name"),
+    ("sound_library.size", "Size"),
+    ("sound_library.uploaded_at", "Uploaded"),
+    ("sound_library.name_placeholder", "Sound name (e.g. Gong)"),
+    ("sound_library.upload", "Upload"),
+    ("sound_library.no_file", "Choose a file"),
+    ("sound_library.rename", "Rename"),
+    ("sound.beep_group", "Beeps"),
+    ("sound.beep", "Beep {} Hz, {} ms × {}"),
+    ("sound.beep_frequency", "Pitch (Hz)"),
+    ("sound.beep_duration", "Length (ms)"),
+    ("sound.beep_count", "Count"),
+    ("sound.beep_gap", "Gap (ms)"),
+    ("block.announcement", "Announcement"),
+    ("block.announcement_placeholder", "Blank uses the phrase from settings"),
+    ("sidebar.speech", "Announcements"),
+    ("speech.title", "Spoken announcements"),
+    (
+        "speech.help",
+        "Reads block names and time left aloud while a timer runs. Settings are saved in this browser.",
+    ),
+    ("speech.unsupported", "This browser does not support speech synthesis."),
+    ("speech.enabled", "Enable announcements"),
+    ("speech.language", "Language"),
+    ("speech.voice", "Voice"),
+    ("speech.voice_default", "Browser default"),
+    ("speech.rate", "Rate"),
+    ("speech.remaining_at", "Announce time left at (seconds)"),
+    ("speech.remaining_at_placeholder", "e.g. 30, 10"),
+    ("speech.templates", "Phrases"),
+    (
+        "speech.templates_help",
+        "Leave blank to use the default. {name} block name, {duration} length, {run} round number, {total} rounds, {remaining} time left",
+    ),
+    ("speech.event.block_start", "Block start"),
+    ("speech.event.repetition", "Round start"),
+    ("speech.event.interval", "Interval"),
+    ("speech.event.remaining", "Time left"),
+    ("speech.event.finish", "Finish"),
+    ("sound.tick_under_secs", "Tick seconds"),
+    (
+        "sound.tick_under_secs_help",
+        "Play the tick every second from this many seconds left (0 to disable, blank for the default)",
+    ),
+    ("volume.mute", "Mute"),
+    ("volume.unmute", "Unmute"),
+    ("volume.master", "Volume"),
+    ("volume.events", "Volume per event"),
+    ("webhook.choose_timer", "Choose a timer"),
+    ("builder.fix_errors", "Fix the errors to save"),
+];
@@ -0,0 +1,256 @@
+//! 日本語のメッセージ（既定のロケール。キーが見つからないときの代替にもなる）
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("duration.min_sec", "{}分{}秒"),
+    ("duration.hour_min", "{}時間{}分"),
+    ("builder.title_new", "新規タイマー作成"),
+    ("builder.title_edit", "タイマー編集"),
+    ("builder.name", "タイマー名"),
+    ("builder.name_placeholder", "タイマー名を入力..."),
+    ("builder.save", "Save"),
+    ("block.name", "名前"),
+    ("block.name_placeholder", "ブロック名"),
+    ("block.minutes", "分"),
+    ("block.seconds", "秒"),
+    ("block.repeat_count", "繰り返し回数"),
+    ("block.interval_minutes", "インターバル分"),
+    ("block.interval_seconds", "インターバル秒"),
+    ("canvas.title", "ブロック"),
+    ("canvas.add_wait", "+ 待機ブロック"),
+    ("canvas.add_countdown", "+ カウントダウンブロック"),
+    ("sidebar.workspace_select", "表示するスペース"),
+    ("sidebar.personal", "個人"),
+    ("sidebar.run", "実行"),
+    ("sidebar.add", "Add"),
+    ("sidebar.history", "履歴"),
+    ("sidebar.webhooks", "Webhook"),
+    ("sidebar.tokens", "トークン"),
+    ("sidebar.workspaces", "チーム"),
+    ("sidebar.admin", "管理者"),
+    ("sidebar.logout", "ログアウト"),
+    ("locale.select", "表示言語"),
+    ("login.username", "ユーザー名"),
+    ("login.password", "パスワード"),
+    ("login.submit", "ログイン"),
+    ("no_content.hint", "サイドバーの「Add」ボタンを押してタイマーを作成してください"),
+    ("unsaved.title", "未保存の変更があります"),
+    ("unsaved.message", "編集内容が保存されていません。編集を続けますか？それとも変更を破棄して画面を切り替えますか？"),
+    ("unsaved.continue", "編集を続ける"),
+    ("unsaved.discard", "編集を止める"),
+    ("transfer.title", "移動・コピー"),
+    ("transfer.move", "移動"),
+    ("transfer.copy", "コピー"),
+    ("transfer.copied", "コピーしました"),
+    ("shared.run_here", "この端末で実行"),
+    ("shared.expires", "このリンクの期限: {}"),
+    ("common.loading", "読み込み中…"),
+    ("block_type.wait", "待機"),
+    ("block_type.countdown", "カウントダウン"),
+    ("timer_view.wait_line", "{} 待機"),
+    ("timer_view.countdown_line", "{} カウントダウン、{}回繰り返し (インターバル待機: {})"),
+    ("timer_view.edit", "編集する"),
+    ("running.wait_line", "{} ウェイト"),
+    ("running.countdown_line", "{} カウントダウン (繰り返し{}回, インターバル {})"),
+    ("running.title", "タイマー実行中"),
+    ("running.paused", "一時停止中"),
+    ("running.complete", "完了！"),
+    ("running.repetition", "現在 {} / {} 回"),
+    ("running.phase_countdown", "カウントダウン"),
+    ("running.phase_interval", "インターバル"),
+    ("running.remaining", "残り {} 秒"),
+    ("running.resume", "再開"),
+    ("running.pause", "一時停止"),
+    ("running.skip", "スキップ"),
+    ("running.close", "閉じる"),
+    ("running.stop", "中止"),
+    ("history.block_n", "ブロック{}"),
+    ("history.completed", "完了"),
+    ("history.aborted", "中止"),
+    ("history.aborted_at", "中止（{}）"),
+    ("history.aborted_at_run", "中止（{} {}/{}回目）"),
+    ("history.day_runs", "{}: {}回"),
+    ("history.week_runs", "{} の週: {}回（完了 {}回）"),
+    ("history.total_runs", "実行回数"),
+    ("history.runs_value", "{}回"),
+    ("history.completion_rate", "完了率"),
+    ("history.total_time", "合計時間"),
+    ("history.streak", "連続日数"),
+    ("history.streak_days", "{}日"),
+    ("history.longest_streak", " (最長 {}日)"),
+    ("history.calendar", "カレンダー"),
+    ("history.weekly", "週ごとの実行回数"),
+    ("history.per_timer", "タイマー別"),
+    ("history.timer", "タイマー"),
+    ("history.title", "実行履歴"),
+    ("history.all", "すべて"),
+    ("history.recent", "最近の実行"),
+    ("history.empty", "まだ実行記録がありません"),
+    ("event.run_started", "実行開始"),
+    ("event.block_started", "ブロック開始"),
+    ("event.repetition_ended", "繰り返し終了"),
+    ("event.block_ended", "ブロック終了"),
+    ("event.completed", "完了"),
+    ("event.aborted", "中止"),
+    ("event.test", "テスト"),
+    ("common.list_sep", "・"),
+    ("webhook.all_events", "すべてのイベント"),
+    ("webhook.success", "成功"),
+    ("webhook.success_status", "成功 ({})"),
+    ("webhook.failed", "失敗: {} ({}回試行)"),
+    ("webhook.unknown_error", "不明なエラー"),
+    ("webhook.all_timers", "全タイマー"),
+    ("webhook.deleted_timer", "（削除されたタイマー）"),
+    ("webhook.title", "Webhook"),
+    (
+        "webhook.help",
+        "実行イベントを JSON で POST します。本文の HMAC-SHA256 を X-Nekotimer-Signature ヘッダー（sha256=...）に付けます。",
+    ),
+    ("webhook.empty", "Webhook は登録されていません"),
+    ("webhook.secret", "秘密鍵: "),
+    ("webhook.test", "テスト送信"),
+    ("common.disable", "無効にする"),
+    ("common.enable", "有効にする"),
+    ("common.delete", "削除"),
+    ("common.add", "追加"),
+    ("webhook.events_help", "イベントを選ばない場合はすべてのイベントを送ります。"),
+    ("webhook.log", "送信ログ"),
+    ("webhook.log_empty", "まだ送信していません"),
+    ("webhook.time", "日時"),
+    ("webhook.event", "イベント"),
+    ("webhook.result", "結果"),
+    ("day.mon", "月"),
+    ("day.tue", "火"),
+    ("day.wed", "水"),
+    ("day.thu", "木"),
+    ("day.fri", "金"),
+    ("day.sat", "土"),
+    ("day.sun", "日"),
+    ("schedule.time_required", "時刻を入力してください"),
+    ("schedule.title", "スケジュール"),
+    ("schedule.empty", "定期実行は設定されていません"),
+    ("schedule.next", "次回: {}"),
+    ("schedule.weekly", "曜日と時刻"),
+    ("schedule.cron", "cron 式"),
+    ("schedule.timezone", "タイムゾーン"),
+    ("token.scope_read", "参照のみ"),
+    ("token.scope_run", "参照・実行"),
+    ("token.scope_manage", "編集を含むすべて"),
+    ("token.title", "API トークン"),
+    ("token.help", "スクリプトから Authorization: Bearer <トークン> で API を呼び出せます。トークンの管理とログインには使えません。"),
+    ("token.created", "トークンを作成しました。この画面を離れると再表示できないので、今すぐ控えてください。"),
+    ("token.empty", "トークンはありません"),
+    ("token.name", "名前"),
+    ("token.scope", "権限"),
+    ("token.token", "トークン"),
+    ("token.created_at", "作成"),
+    ("token.last_used", "最終使用"),
+    ("token.revoke", "失効"),
+    ("token.name_placeholder", "トークン名（例: backup-script）"),
+    ("common.create", "作成"),
+    ("role.owner", "オーナー"),
+    ("role.editor", "編集者"),
+    ("role.runner", "実行者"),
+    ("role.viewer", "閲覧者"),
+    ("workspace.title", "ワークスペース"),
+    (
+        "workspace.help",
+        "ワークスペースのタイマーはメンバー全員で使えます。閲覧者は見るだけ、実行者は実行まで、編集者はタイマーの編集まで、オーナーはメンバーの管理もできます。",
+    ),
+    ("workspace.empty", "所属しているワークスペースはありません"),
+    ("workspace.name_placeholder", "ワークスペース名"),
+    ("workspace.member", "メンバー"),
+    ("workspace.role", "役割"),
+    ("workspace.leave", "抜ける"),
+    ("workspace.remove", "外す"),
+    ("workspace.username", "ユーザー名"),
+    ("workspace.add_member", "メンバーを追加"),
+    ("share.expiry_none", "無期限"),
+    ("share.expiry_1d", "1日"),
+    ("share.expiry_7d", "7日"),
+    ("share.expiry_30d", "30日"),
+    ("share.title", "共有リンク"),
+    ("share.empty", "共有リンクはありません"),
+    ("share.runnable", "実行可"),
+    ("share.expires", "期限: {}"),
+    ("share.revoke", "取り消す"),
+    ("share.allow_run", "この端末での実行を許可"),
+    ("share.expiry", "有効期限"),
+    ("share.create", "リンクを作成"),
+    ("sidebar.sounds", "効果音"),
+    ("sound_event.block_start", "ブロック開始"),
+    ("sound_event.block_end", "ブロック終了"),
+    ("sound_event.tick", "残り秒読み"),
+    ("sound_event.repetition", "回の切り替わり"),
+    ("sound_event.finish", "すべて終了"),
+    ("sound.tick", "チック"),
+    ("sound.block_end", "ブロック終了音"),
+    ("sound.all_end", "終了音"),
+    ("sound.countdown_blk_next", "切り替わり音"),
+    ("sound.silent", "鳴らさない"),
+    ("sound.inherit", "既定"),
+    ("sound.preview", "試聴"),
+    ("sound.block_summary", "効果音・読み上げ"),
+    ("sound.timer_summary", "効果音"),
+    ("sound.profile", "プロファイル"),
+    ("sound.no_profile", "使わない"),
+    ("sound_profile.title", "効果音プロファイル"),
+    (
+        "sound_profile.help",
+        "名前付きの効果音の組み合わせを作り、タイマーの「効果音」で選べます。ブロックやタイマーで個別に選んだ効果音はプロファイルより優先されます。プロファイルは全員で共有され、変更できるのは作成者と管理者だけです。",
+    ),
+    ("sound_profile.empty", "効果音プロファイルはありません"),
+    ("sound_profile.name_placeholder", "プロファイル名（例: 静かなオフィス）"),
+    ("sound_profile.save", "保存"),
+    ("sidebar.sound_library", "効果音ライブラリ"),
+    ("sound.custom_group", "アップロードした効果音"),
+    ("sound_library.title", "効果音ライブラリ"),
+    (
+        "sound_library.help",
+        "MP3・WAV・OGG などの音声ファイル（{}KB まで）をアップロードすると、タイマー・ブロック・効果音プロファイルの効果音に選べます。削除すると、使っていた箇所は既定の効果音に戻ります。",
+    ),
+    ("sound_library.empty", "アップロードした効果音はありません"),
+    ("sound_library.name", "名前"),
+    ("sound_library.size", "サイズ"),
+    ("sound_library.uploaded_at", "アップロード日時"),
+    ("sound_library.name_placeholder", "効果音の名前（例: ゴング）"),
+    ("sound_library.upload", "アップロード"),
+    ("sound_library.no_file", "ファイルを選んでください"),
+    ("sound_library.rename", "名前を変更"),
+    ("sound.beep_group", "電子音"),
+    ("sound.beep", "電子音 {}Hz・{}ms×{}"),
+    ("sound.beep_frequency", "高さ(Hz)"),
+    ("sound.beep_duration", "長さ(ms)"),
+    ("sound.beep_count", "回数"),
+    ("sound.beep_gap", "間隔(ms)"),
+    ("block.announcement", "読み上げ文"),
+    ("block.announcement_placeholder", "空欄なら読み上げの設定の文"),
+    ("sidebar.speech", "読み上げ"),
+    ("speech.title", "読み上げの設定"),
+    ("speech.help", "実行中にブロック名や残り時間を音声で読み上げます。設定はこの端末のブラウザに保存されます。"),
+    ("speech.unsupported", "このブラウザは読み上げに対応していません。"),
+    ("speech.enabled", "読み上げる"),
+    ("speech.language", "言語"),
+    ("speech.voice", "声"),
+    ("speech.voice_default", "ブラウザの既定"),
+    ("speech.rate", "速さ"),
+    ("speech.remaining_at", "残り時間を読み上げる秒数"),
+    ("speech.remaining_at_placeholder", "例: 30, 10"),
+    ("speech.templates", "読み上げる文"),
+    (
+        "speech.templates_help",
+        "空欄なら既定の文を使います。{name} ブロック名、{duration} 長さ、{run} 何回目、{total} 繰り返し回数、{remaining} 残り時間",
+    ),
+    ("speech.event.block_start", "ブロックの開始"),
+    ("speech.event.repetition", "各回の開始"),
+    ("speech.event.interval", "インターバル"),
+    ("speech.event.remaining", "残り時間"),
+    ("speech.event.finish", "終了"),
+    ("sound.tick_under_secs", "tick 音の秒数"),
+    ("sound.tick_under_secs_help", "残り何秒から毎秒 tick 音を鳴らすか（0 で鳴らさない、空欄なら既定）"),
+    ("volume.mute", "消音"),
+    ("volume.unmute", "消音を解除"),
+    ("volume.master", "音量"),
+    ("volume.events", "タイミングごとの音量"),
+    ("webhook.choose_timer", "タイマーを選択"),
+    ("builder.fix_errors", "入力エラーを直すと保存できます"),
+];
@@ -0,0 +1,62 @@
+//! 画面の文言。キーから言語ごとのカタログ（ja.rs / en.rs）を引く。
+//! カタログにないキーは日本語で、日本語にもなければキーそのものを出す。
+
+mod en;
+mod ja;
+
+use std::fmt::Display;
+
+use nekotimer_shared::Locale;
+
+fn catalog(locale: Locale) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        Locale::Ja => ja::MESSAGES,
+        Locale::En => en::MESSAGES,
+    }
+}
+
+fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    catalog(locale)
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, text)| *text)
+}
+
+/// key の文言
+pub fn t(locale: Locale, key: &'static str) -> &'static str {
+    lookup(locale, key)
+        .or_else(|| lookup(Locale::Ja, key))
+        .unwrap_or(key)
+}
+
+/// key の文言の `{}` を args で順に置き換える
+pub fn tf(locale: Locale, key: &'static str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut out = String::new();
+    let mut rest = t(locale, key);
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 分と秒（例: 1分30秒 / 1m 30s）
+pub fn min_sec(locale: Locale, minutes: u32, seconds: u32) -> String {
+    tf(locale, "duration.min_sec", &[&minutes, &seconds])
+}
+
+/// 所要時間: 1時間未満は「x分y秒」、それ以上は「x時間y分」
+pub fn duration(locale: Locale, secs: u64) -> String {
+    let hours = secs / 3600;
+    let minutes = secs % 3600 / 60;
+    if hours > 0 {
+        tf(locale, "duration.hour_min", &[&hours, &minutes])
+    } else {
+        tf(locale, "duration.min_sec", &[&minutes, &(secs % 60)])
+    }
+}
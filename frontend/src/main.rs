@@ -1,9 +1,24 @@
 mod app;
 mod components;
+mod i18n;
 mod services;
 mod state;
 
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
-    yew::Renderer::<app::App>::new().render();
+    // 共有リンク /s/<token> はログイン不要の閲覧画面を出す
+    let path = web_sys::window()
+        .and_then(|w| w.location().pathname().ok())
+        .unwrap_or_default();
+    match path.strip_prefix("/s/") {
+        Some(token) if !token.is_empty() => {
+            let props = components::shared_timer_page::SharedTimerPageProps {
+                token: token.trim_end_matches('/').to_string(),
+            };
+            yew::Renderer::<components::shared_timer_page::SharedTimerPage>::with_props(props).render();
+        }
+        _ => {
+            yew::Renderer::<app::App>::new().render();
+        }
+    }
 }
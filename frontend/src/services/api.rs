@@ -1,8 +1,60 @@
 use gloo_net::http::Request;
-use nekotimer_shared::{ApiResponse, TimerConfig, ValidationError};
+use nekotimer_shared::{
+    ApiResponse, ApiToken, CreateApiTokenRequest, CreateShareLinkRequest, CreateWorkspaceRequest,
+    CreatedApiToken, CustomSound, ErrorCode, LoginRequest, LoginResponse, RenameCustomSoundRequest,
+    RunRecord, RunSession, RunStats, Schedule, SetMemberRequest, ShareLink, SharedTimer,
+    SoundProfile, StartRunRequest, TimerConfig, TokenScope, TransferTimerRequest, UserInfo,
+    ValidationError, ValidationPolicy, Webhook, WebhookDelivery, Workspace, WorkspaceRole,
+};
+
+use crate::services::locale::current_locale;
 
 const BASE_URL: &str = "/api";
 
+/// ログイン中のユーザー。未ログイン（セッション Cookie なし・期限切れ）なら Err
+pub async fn fetch_me() -> Result<UserInfo, String> {
+    let resp = Request::get(&format!("{}/auth/me", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<UserInfo> = resp.json().await.map_err(|e| e.to_string())?;
+
+    match api_resp.data {
+        Some(user) if api_resp.success => Ok(user),
+        _ => Err(format_errors(api_resp.errors)),
+    }
+}
+
+/// ログインする。セッションはサーバーが Cookie で設定する
+pub async fn login(username: &str, password: &str) -> Result<UserInfo, String> {
+    let body = LoginRequest {
+        username: username.to_string(),
+        password: password.to_string(),
+    };
+    let resp = Request::post(&format!("{}/auth/login", BASE_URL))
+        .json(&body)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<LoginResponse> = resp.json().await.map_err(|e| e.to_string())?;
+
+    match api_resp.data {
+        Some(login) if api_resp.success => Ok(login.user),
+        _ => Err(format_errors(api_resp.errors)),
+    }
+}
+
+pub async fn logout() -> Result<(), String> {
+    Request::post(&format!("{}/auth/logout", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 pub async fn fetch_timers() -> Result<Vec<TimerConfig>, String> {
     let resp = Request::get(&format!("{}/timers", BASE_URL))
         .send()
@@ -18,15 +70,100 @@ pub async fn fetch_timers() -> Result<Vec<TimerConfig>, String> {
     }
 }
 
-pub async fn create_timer(timer: &TimerConfig) -> Result<TimerConfig, Vec<String>> {
+pub async fn create_timer(timer: &TimerConfig) -> Result<TimerConfig, Vec<ValidationError>> {
     let resp = Request::post(&format!("{}/timers", BASE_URL))
         .json(timer)
+        .map_err(send_failed)?
+        .send()
+        .await
+        .map_err(send_failed)?;
+
+    let api_resp: ApiResponse<TimerConfig> = resp.json().await.map_err(send_failed)?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(api_resp.errors.unwrap_or_default())
+    }
+}
+
+pub async fn update_timer(id: &str, timer: &TimerConfig) -> Result<TimerConfig, Vec<ValidationError>> {
+    let resp = Request::put(&format!("{}/timers/{}", BASE_URL, id))
+        .json(timer)
+        .map_err(send_failed)?
+        .send()
+        .await
+        .map_err(send_failed)?;
+
+    let api_resp: ApiResponse<TimerConfig> = resp.json().await.map_err(send_failed)?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(api_resp.errors.unwrap_or_default())
+    }
+}
+
+/// タイマーを移動・コピーする。workspace_id が None なら自分の個人タイマーへ
+pub async fn transfer_timer(id: &str, workspace_id: Option<String>, copy: bool) -> Result<TimerConfig, String> {
+    let body = TransferTimerRequest { workspace_id, copy };
+    let resp = Request::post(&format!("{}/timers/{}/transfer", BASE_URL, id))
+        .json(&body)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<TimerConfig> = resp.json().await.map_err(|e| e.to_string())?;
+
+    match api_resp.data {
+        Some(timer) if api_resp.success => Ok(timer),
+        _ => Err(format_errors(api_resp.errors)),
+    }
+}
+
+pub async fn fetch_validation_policy() -> Result<ValidationPolicy, String> {
+    let resp = Request::get(&format!("{}/validation-policy", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<ValidationPolicy> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn fetch_workspaces() -> Result<Vec<Workspace>, String> {
+    let resp = Request::get(&format!("{}/workspaces", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<Workspace>> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn create_workspace(name: &str) -> Result<Workspace, Vec<String>> {
+    let body = CreateWorkspaceRequest {
+        name: name.to_string(),
+    };
+    let resp = Request::post(&format!("{}/workspaces", BASE_URL))
+        .json(&body)
         .map_err(|e| vec![e.to_string()])?
         .send()
         .await
         .map_err(|e| vec![e.to_string()])?;
 
-    let api_resp: ApiResponse<TimerConfig> =
+    let api_resp: ApiResponse<Workspace> =
         resp.json().await.map_err(|e| vec![e.to_string()])?;
 
     if api_resp.success {
@@ -36,15 +173,220 @@ pub async fn create_timer(timer: &TimerConfig) -> Result<TimerConfig, Vec<String
     }
 }
 
-pub async fn update_timer(id: &str, timer: &TimerConfig) -> Result<TimerConfig, Vec<String>> {
-    let resp = Request::put(&format!("{}/timers/{}", BASE_URL, id))
-        .json(timer)
+pub async fn delete_workspace(id: &str) -> Result<(), String> {
+    let resp = Request::delete(&format!("{}/workspaces/{}", BASE_URL, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<()> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+/// メンバーを追加する。既にメンバーなら役割を変える
+pub async fn set_workspace_member(id: &str, username: &str, role: WorkspaceRole) -> Result<Workspace, Vec<String>> {
+    let body = SetMemberRequest {
+        username: username.to_string(),
+        role,
+    };
+    let resp = Request::put(&format!("{}/workspaces/{}/members", BASE_URL, id))
+        .json(&body)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<Workspace> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(extract_errors(api_resp.errors))
+    }
+}
+
+pub async fn remove_workspace_member(id: &str, user_id: &str) -> Result<(), String> {
+    let resp = Request::delete(&format!("{}/workspaces/{}/members/{}", BASE_URL, id, user_id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<()> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn start_run(timer_id: &str) -> Result<RunSession, String> {
+    let body = StartRunRequest {
+        timer_id: timer_id.to_string(),
+    };
+    let resp = Request::post(&format!("{}/runs", BASE_URL))
+        .json(&body)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<RunSession> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+/// 実行セッションの操作。action は "pause" / "resume" / "skip" / "stop"。
+pub async fn control_run(session_id: &str, action: &str) -> Result<RunSession, String> {
+    let resp = Request::post(&format!("{}/runs/{}/{}", BASE_URL, session_id, action))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<RunSession> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+/// 実行履歴（新しい順）。timer_id を指定するとそのタイマーのみ。
+pub async fn fetch_history(timer_id: Option<&str>, limit: usize) -> Result<Vec<RunRecord>, String> {
+    let mut req = Request::get(&format!("{}/history", BASE_URL)).query([("limit", limit.to_string())]);
+    if let Some(id) = timer_id {
+        req = req.query([("timer_id", id)]);
+    }
+    let resp = req.send().await.map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<RunRecord>> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+/// 実行統計。tz は日付の区切りに使う IANA タイムゾーン名。
+pub async fn fetch_run_stats(timer_id: Option<&str>, tz: &str) -> Result<RunStats, String> {
+    let mut req = Request::get(&format!("{}/history/stats", BASE_URL)).query([("tz", tz)]);
+    if let Some(id) = timer_id {
+        req = req.query([("timer_id", id)]);
+    }
+    let resp = req.send().await.map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<RunStats> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn fetch_schedules(timer_id: &str) -> Result<Vec<Schedule>, String> {
+    let resp = Request::get(&format!("{}/schedules", BASE_URL))
+        .query([("timer_id", timer_id)])
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<Schedule>> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn create_schedule(schedule: &Schedule) -> Result<Schedule, Vec<String>> {
+    let resp = Request::post(&format!("{}/schedules", BASE_URL))
+        .json(schedule)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<Schedule> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(extract_errors(api_resp.errors))
+    }
+}
+
+pub async fn update_schedule(schedule: &Schedule) -> Result<Schedule, Vec<String>> {
+    let resp = Request::put(&format!("{}/schedules/{}", BASE_URL, schedule.id))
+        .json(schedule)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<Schedule> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(extract_errors(api_resp.errors))
+    }
+}
+
+pub async fn delete_schedule(id: &str) -> Result<(), String> {
+    let resp = Request::delete(&format!("{}/schedules/{}", BASE_URL, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<()> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn fetch_webhooks() -> Result<Vec<Webhook>, String> {
+    let resp = Request::get(&format!("{}/webhooks", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<Webhook>> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn create_webhook(webhook: &Webhook) -> Result<Webhook, Vec<String>> {
+    let resp = Request::post(&format!("{}/webhooks", BASE_URL))
+        .json(webhook)
         .map_err(|e| vec![e.to_string()])?
         .send()
         .await
         .map_err(|e| vec![e.to_string()])?;
 
-    let api_resp: ApiResponse<TimerConfig> =
+    let api_resp: ApiResponse<Webhook> =
         resp.json().await.map_err(|e| vec![e.to_string()])?;
 
     if api_resp.success {
@@ -54,19 +396,337 @@ pub async fn update_timer(id: &str, timer: &TimerConfig) -> Result<TimerConfig,
     }
 }
 
+pub async fn update_webhook(webhook: &Webhook) -> Result<Webhook, Vec<String>> {
+    let resp = Request::put(&format!("{}/webhooks/{}", BASE_URL, webhook.id))
+        .json(webhook)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<Webhook> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(extract_errors(api_resp.errors))
+    }
+}
+
+pub async fn delete_webhook(id: &str) -> Result<(), String> {
+    let resp = Request::delete(&format!("{}/webhooks/{}", BASE_URL, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<()> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+/// テスト送信（サーバーが再試行を終えるまで待つ）
+pub async fn test_webhook(id: &str) -> Result<WebhookDelivery, String> {
+    let resp = Request::post(&format!("{}/webhooks/{}/test", BASE_URL, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<WebhookDelivery> = resp.json().await.map_err(|e| e.to_string())?;
+
+    match api_resp.data {
+        Some(delivery) if api_resp.success => Ok(delivery),
+        _ => Err(format_errors(api_resp.errors)),
+    }
+}
+
+pub async fn fetch_webhook_deliveries() -> Result<Vec<WebhookDelivery>, String> {
+    let resp = Request::get(&format!("{}/webhooks/deliveries", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<WebhookDelivery>> =
+        resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn fetch_tokens() -> Result<Vec<ApiToken>, String> {
+    let resp = Request::get(&format!("{}/tokens", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<ApiToken>> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+/// トークンを作成する。戻り値の token はこのときしか取得できない
+pub async fn create_token(name: &str, scope: TokenScope) -> Result<CreatedApiToken, Vec<String>> {
+    let body = CreateApiTokenRequest {
+        name: name.to_string(),
+        scope,
+    };
+    let resp = Request::post(&format!("{}/tokens", BASE_URL))
+        .json(&body)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<CreatedApiToken> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(extract_errors(api_resp.errors))
+    }
+}
+
+pub async fn revoke_token(id: &str) -> Result<(), String> {
+    let resp = Request::delete(&format!("{}/tokens/{}", BASE_URL, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<()> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn fetch_shares(timer_id: &str) -> Result<Vec<ShareLink>, String> {
+    let resp = Request::get(&format!("{}/shares", BASE_URL))
+        .query([("timer_id", timer_id)])
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<ShareLink>> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn create_share(req: &CreateShareLinkRequest) -> Result<ShareLink, Vec<String>> {
+    let resp = Request::post(&format!("{}/shares", BASE_URL))
+        .json(req)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<ShareLink> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(extract_errors(api_resp.errors))
+    }
+}
+
+pub async fn delete_share(id: &str) -> Result<(), String> {
+    let resp = Request::delete(&format!("{}/shares/{}", BASE_URL, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<()> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+/// 共有リンクのタイマー（ログイン不要）
+pub async fn fetch_shared(token: &str) -> Result<SharedTimer, String> {
+    let resp = Request::get(&format!("{}/shared/{}", BASE_URL, token))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<SharedTimer> = resp.json().await.map_err(|e| e.to_string())?;
+
+    match api_resp.data {
+        Some(shared) if api_resp.success => Ok(shared),
+        _ => Err(format_errors(api_resp.errors)),
+    }
+}
+
+/// 通信できなかったときのエラー（項目に紐づかない）
+fn send_failed(e: impl std::fmt::Display) -> Vec<ValidationError> {
+    vec![ValidationError::new("system", ErrorCode::SaveFailed { detail: e.to_string() })]
+}
+
+/// 各エラーを利用者の言語で
 fn extract_errors(errors: Option<Vec<ValidationError>>) -> Vec<String> {
+    let locale = current_locale();
     errors
         .unwrap_or_default()
         .iter()
-        .map(|e| e.message.clone())
+        .map(|e| e.render(locale))
         .collect()
 }
 
 fn format_errors(errors: Option<Vec<ValidationError>>) -> String {
-    errors
-        .unwrap_or_default()
-        .iter()
-        .map(|e| e.message.clone())
-        .collect::<Vec<_>>()
-        .join(", ")
+    extract_errors(errors).join(", ")
+}
+
+pub async fn fetch_sound_profiles() -> Result<Vec<SoundProfile>, String> {
+    let resp = Request::get(&format!("{}/sound-profiles", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<SoundProfile>> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn create_sound_profile(profile: &SoundProfile) -> Result<SoundProfile, Vec<String>> {
+    let resp = Request::post(&format!("{}/sound-profiles", BASE_URL))
+        .json(profile)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<SoundProfile> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(extract_errors(api_resp.errors))
+    }
+}
+
+pub async fn update_sound_profile(profile: &SoundProfile) -> Result<SoundProfile, Vec<String>> {
+    let resp = Request::put(&format!("{}/sound-profiles/{}", BASE_URL, profile.id))
+        .json(profile)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<SoundProfile> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap())
+    } else {
+        Err(extract_errors(api_resp.errors))
+    }
+}
+
+pub async fn delete_sound_profile(id: &str) -> Result<(), String> {
+    let resp = Request::delete(&format!("{}/sound-profiles/{}", BASE_URL, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<()> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+pub async fn fetch_custom_sounds() -> Result<Vec<CustomSound>, String> {
+    let resp = Request::get(&format!("{}/sounds", BASE_URL))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<Vec<CustomSound>> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(api_resp.data.unwrap_or_default())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
+}
+
+/// 効果音ファイルをそのまま本文にして送る（名前はクエリ）
+pub async fn upload_custom_sound(name: &str, file: &web_sys::File) -> Result<CustomSound, Vec<String>> {
+    let url = format!("{}/sounds?name={}", BASE_URL, js_sys::encode_uri_component(name));
+    let resp = Request::post(&url)
+        .header("Content-Type", &file.type_())
+        .body(file.clone())
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<CustomSound> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    match api_resp.data {
+        Some(sound) if api_resp.success => Ok(sound),
+        _ => Err(extract_errors(api_resp.errors)),
+    }
+}
+
+pub async fn rename_custom_sound(id: &str, name: &str) -> Result<CustomSound, Vec<String>> {
+    let body = RenameCustomSoundRequest { name: name.to_string() };
+    let resp = Request::put(&format!("{}/sounds/{}", BASE_URL, id))
+        .json(&body)
+        .map_err(|e| vec![e.to_string()])?
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+
+    let api_resp: ApiResponse<CustomSound> =
+        resp.json().await.map_err(|e| vec![e.to_string()])?;
+
+    match api_resp.data {
+        Some(sound) if api_resp.success => Ok(sound),
+        _ => Err(extract_errors(api_resp.errors)),
+    }
+}
+
+/// 削除するとタイマー・効果音プロファイルでの指定も外れる（サーバー側で既定に戻す）
+pub async fn delete_custom_sound(id: &str) -> Result<(), String> {
+    let resp = Request::delete(&format!("{}/sounds/{}", BASE_URL, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let api_resp: ApiResponse<()> = resp.json().await.map_err(|e| e.to_string())?;
+
+    if api_resp.success {
+        Ok(())
+    } else {
+        Err(format_errors(api_resp.errors))
+    }
 }
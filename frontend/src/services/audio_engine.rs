@@ -0,0 +1,198 @@
+//! Web Audio による効果音の再生。
+//! 効果音ファイルはデコードしたバッファを、電子音は発振器で鳴らし、どちらも AudioContext の時計で時刻を指定して予約する。
+//! 鳴らすたびに新しいノードを作るので、短い間隔で同じ音が続いても前の音が途切れない。
+//! 音はすべて全体の音量のノードを通すので、音量・消音の変更は予約済みの音にもすぐ効く。
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use gloo_net::http::Request;
+use nekotimer_shared::{sound_path, BeepPattern};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{AudioBuffer, AudioContext, AudioScheduledSourceNode, GainNode, OscillatorType};
+
+/// 電子音の音量（0.0〜1.0）
+const BEEP_GAIN: f32 = 0.3;
+/// 電子音の立ち上がり・減衰の時間。ぷつっというノイズを防ぐ
+const BEEP_RAMP_SECS: f64 = 0.005;
+
+thread_local! {
+    static CONTEXT: RefCell<Option<AudioContext>> = const { RefCell::new(None) };
+    /// 全体の音量のノード。すべての音をここにつなぐ
+    static MASTER: RefCell<Option<GainNode>> = const { RefCell::new(None) };
+    /// AudioContext を作る前に設定された全体の音量
+    static MASTER_VOLUME: Cell<f32> = const { Cell::new(1.0) };
+    /// 効果音の URL → デコード済みのバッファ
+    static BUFFERS: RefCell<HashMap<String, AudioBuffer>> = RefCell::new(HashMap::new());
+}
+
+/// 予約した音。鳴り終わる前なら取り消せる
+pub struct Scheduled(Vec<AudioScheduledSourceNode>);
+
+impl Scheduled {
+    pub fn cancel(&self) {
+        for node in &self.0 {
+            let _ = node.stop();
+        }
+    }
+}
+
+fn context() -> Option<AudioContext> {
+    CONTEXT.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        if cell.is_none() {
+            *cell = AudioContext::new().ok();
+        }
+        cell.clone()
+    })
+}
+
+fn master(ctx: &AudioContext) -> Option<GainNode> {
+    MASTER.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        if cell.is_none() {
+            let gain = ctx.create_gain().ok()?;
+            gain.gain().set_value(MASTER_VOLUME.with(Cell::get));
+            gain.connect_with_audio_node(&ctx.destination()).ok()?;
+            *cell = Some(gain);
+        }
+        cell.clone()
+    })
+}
+
+/// 全体の音量（0.0〜1.0、消音なら 0）を設定する
+pub fn set_master_volume(volume: f32) {
+    MASTER_VOLUME.with(|v| v.set(volume));
+    if let Some(gain) = MASTER.with(|cell| cell.borrow().clone()) {
+        gain.gain().set_value(volume);
+    }
+}
+
+/// 音ごとの音量のノードを作って全体の音量のノードにつなぐ
+fn output(ctx: &AudioContext, volume: f32) -> Option<GainNode> {
+    let gain = ctx.create_gain().ok()?;
+    gain.gain().set_value(volume);
+    let master = master(ctx)?;
+    gain.connect_with_audio_node(&master).ok()?;
+    Some(gain)
+}
+
+/// ユーザー操作（実行ボタン・試聴ボタン）内で呼び、suspended の AudioContext を再開する。
+/// iOS WebKit などでは、これをしないと音が鳴らない
+pub fn resume() {
+    if let Some(ctx) = context() {
+        let _ = ctx.resume();
+    }
+}
+
+/// AudioContext の現在時刻（秒）。予約の基準に使う
+pub fn now() -> Option<f64> {
+    context().map(|ctx| ctx.current_time())
+}
+
+async fn fetch_buffer(ctx: &AudioContext, path: &str) -> Result<AudioBuffer, String> {
+    let resp = Request::get(path).send().await.map_err(|e| e.to_string())?;
+    if !resp.ok() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    let bytes = resp.binary().await.map_err(|e| e.to_string())?;
+    let data = js_sys::Uint8Array::from(bytes.as_slice()).buffer();
+    let promise = ctx.decode_audio_data(&data).map_err(|e| format!("{:?}", e))?;
+    let decoded = JsFuture::from(promise).await.map_err(|e| format!("{:?}", e))?;
+    decoded.dyn_into::<AudioBuffer>().map_err(|e| format!("{:?}", e))
+}
+
+/// デコード済みのバッファ。なければ読み込んでキャッシュする
+async fn buffer(ctx: &AudioContext, path: &str) -> Option<AudioBuffer> {
+    if let Some(buffer) = BUFFERS.with(|b| b.borrow().get(path).cloned()) {
+        return Some(buffer);
+    }
+    match fetch_buffer(ctx, path).await {
+        Ok(buffer) => {
+            BUFFERS.with(|b| b.borrow_mut().insert(path.to_string(), buffer.clone()));
+            Some(buffer)
+        }
+        Err(e) => {
+            log::warn!("Failed to load sound {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// 効果音ファイルを読み込んでデコードしておく（再生時には読み込まない）
+pub fn preload(path: &str) {
+    let cached = BUFFERS.with(|b| b.borrow().contains_key(path));
+    let Some(ctx) = context().filter(|_| !cached) else {
+        return;
+    };
+    let path = path.to_string();
+    spawn_local(async move {
+        buffer(&ctx, &path).await;
+    });
+}
+
+fn schedule_buffer(
+    ctx: &AudioContext,
+    buffer: &AudioBuffer,
+    when: f64,
+    volume: f32,
+) -> Option<AudioScheduledSourceNode> {
+    let source = ctx.create_buffer_source().ok()?;
+    source.set_buffer(Some(buffer));
+    let output = output(ctx, volume)?;
+    source.connect_with_audio_node(&output).ok()?;
+    source.start_with_when(when).ok()?;
+    Some((*source).clone())
+}
+
+fn schedule_beep(ctx: &AudioContext, beep: BeepPattern, when: f64, volume: f32) -> Vec<AudioScheduledSourceNode> {
+    let length = beep.duration_ms as f64 / 1000.0;
+    let step = length + beep.gap_ms as f64 / 1000.0;
+    (0..beep.count)
+        .filter_map(|i| {
+            let start = when + step * i as f64;
+            let end = start + length;
+            let osc = ctx.create_oscillator().ok()?;
+            osc.set_type(OscillatorType::Sine);
+            osc.frequency().set_value(beep.frequency_hz as f32);
+            let gain = output(ctx, 0.0)?;
+            let level = gain.gain();
+            let peak = BEEP_GAIN * volume;
+            level.set_value_at_time(0.0, start).ok()?;
+            level.linear_ramp_to_value_at_time(peak, start + BEEP_RAMP_SECS).ok()?;
+            level.set_value_at_time(peak, end - BEEP_RAMP_SECS).ok()?;
+            level.linear_ramp_to_value_at_time(0.0, end).ok()?;
+            osc.connect_with_audio_node(&gain).ok()?;
+            osc.start_with_when(start).ok()?;
+            osc.stop_with_when(end).ok()?;
+            Some((*osc).clone())
+        })
+        .collect()
+}
+
+/// 効果音を when（AudioContext の時刻。過ぎていればすぐ）に volume（0.0〜1.0）で鳴らすよう予約する。
+/// 読み込み前の効果音ファイルは、読み込み終わりしだい鳴らす（この分は取り消せない）
+pub fn schedule(sound: &str, when: f64, volume: f32) -> Scheduled {
+    let Some(ctx) = context() else {
+        return Scheduled(Vec::new());
+    };
+    let when = when.max(ctx.current_time());
+    if let Some(beep) = BeepPattern::parse(sound) {
+        return Scheduled(schedule_beep(&ctx, beep, when, volume));
+    }
+    let Some(path) = sound_path(sound) else {
+        return Scheduled(Vec::new());
+    };
+    match BUFFERS.with(|b| b.borrow().get(&path).cloned()) {
+        Some(buffer) => Scheduled(schedule_buffer(&ctx, &buffer, when, volume).into_iter().collect()),
+        None => {
+            spawn_local(async move {
+                if let Some(buffer) = buffer(&ctx, &path).await {
+                    schedule_buffer(&ctx, &buffer, when, volume);
+                }
+            });
+            Scheduled(Vec::new())
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! 日時・所要時間の表示用ヘルパー（ブラウザのローカル時刻基準）
+
+use js_sys::{Array, Date, Object, Reflect};
+use wasm_bindgen::JsValue;
+
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// ブラウザのタイムゾーン名（例: Asia/Tokyo）。取得できなければ UTC。
+pub fn browser_time_zone() -> String {
+    let options = js_sys::Intl::DateTimeFormat::new(&Array::new(), &Object::new()).resolved_options();
+    Reflect::get(&options, &JsValue::from_str("timeZone"))
+        .ok()
+        .and_then(|v| v.as_string())
+        .unwrap_or_else(|| "UTC".to_string())
+}
+
+/// UNIX 秒 → Date
+pub fn date_from_unix(secs: u64) -> Date {
+    Date::new(&JsValue::from_f64(secs as f64 * 1000.0))
+}
+
+/// 今日の正午（ローカル）から days 日前の Date。夏時間の切り替えで日付がずれないよう正午基準にする。
+pub fn days_ago(days: u32) -> Date {
+    let now = Date::new_0();
+    let noon = Date::new_with_year_month_day_hr(now.get_full_year(), now.get_month() as i32, now.get_date() as i32, 12);
+    Date::new(&JsValue::from_f64(noon.get_time() - days as f64 * DAY_MS))
+}
+
+/// YYYY-MM-DD（サーバーの集計キーと同じ形式）
+pub fn date_key(date: &Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date()
+    )
+}
+
+/// 月曜日を 0 とした曜日
+pub fn weekday_from_monday(date: &Date) -> u32 {
+    (date.get_day() + 6) % 7
+}
+
+/// YYYY/MM/DD HH:MM
+pub fn format_datetime(secs: u64) -> String {
+    let d = date_from_unix(secs);
+    format!(
+        "{:04}/{:02}/{:02} {:02}:{:02}",
+        d.get_full_year(),
+        d.get_month() + 1,
+        d.get_date(),
+        d.get_hours(),
+        d.get_minutes()
+    )
+}
@@ -0,0 +1,68 @@
+//! 表示言語の判定と切り替え
+
+use std::cell::Cell;
+
+use nekotimer_shared::Locale;
+use yew::prelude::*;
+
+/// 利用者が選んだ言語を保存する localStorage のキー
+const STORAGE_KEY: &str = "nekotimer.locale";
+
+thread_local! {
+    static CURRENT: Cell<Option<Locale>> = const { Cell::new(None) };
+}
+
+/// 画面の言語。切り替えると購読しているコンポーネントが描き直される
+pub type LocaleContext = UseStateHandle<Locale>;
+
+/// 選んだ言語（localStorage）、なければブラウザの言語設定（navigator.language）。どちらもなければ日本語
+pub fn current_locale() -> Locale {
+    if let Some(locale) = CURRENT.with(Cell::get) {
+        return locale;
+    }
+    let window = web_sys::window();
+    let stored = window
+        .as_ref()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|tag| Locale::from_tag(&tag));
+    let locale = stored
+        .or_else(|| {
+            window
+                .and_then(|w| w.navigator().language())
+                .and_then(|tag| Locale::from_tag(&tag))
+        })
+        .unwrap_or_default();
+    CURRENT.with(|c| c.set(Some(locale)));
+    locale
+}
+
+/// 言語を切り替えて localStorage に保存する
+pub fn set_locale(context: &LocaleContext, locale: Locale) {
+    CURRENT.with(|c| c.set(Some(locale)));
+    if let Some(window) = web_sys::window() {
+        if let Ok(Some(storage)) = window.local_storage() {
+            let _ = storage.set_item(STORAGE_KEY, locale.tag());
+        }
+        apply_document_lang(locale);
+    }
+    context.set(locale);
+}
+
+/// <html lang> を合わせる（読み上げや禁則処理のため）
+pub fn apply_document_lang(locale: Locale) {
+    if let Some(root) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.document_element())
+    {
+        let _ = root.set_attribute("lang", locale.tag());
+    }
+}
+
+/// 画面の言語。LocaleContext の外（共有リンクの画面など）ではブラウザの設定から決める
+#[hook]
+pub fn use_locale() -> Locale {
+    use_context::<LocaleContext>()
+        .map(|locale| *locale)
+        .unwrap_or_else(current_locale)
+}
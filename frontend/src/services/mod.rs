@@ -1,2 +1,9 @@
 pub mod api;
+pub mod audio_engine;
+pub mod datetime;
+pub mod locale;
+pub mod run_events;
+pub mod sound_preferences;
+pub mod speech;
 pub mod timer_runner;
+pub mod validation;
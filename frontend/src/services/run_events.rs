@@ -0,0 +1,106 @@
+//! サーバーの実行セッション更新（SSE）を購読する。
+//! 受信した更新は state に反映し、モーダルに表示しているセッションのイベントに応じた効果音を鳴らす
+//! （ワークスペースの他のメンバーの実行やスケジュール実行など、表示していないセッションは鳴らさない）。
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use nekotimer_shared::{RunSession, RunUpdate};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{EventSource, MessageEvent};
+
+use crate::services::timer_runner;
+use crate::state::{AppAction, AppStateContext, RunningInfo};
+
+const RUN_EVENTS_URL: &str = "/api/runs/events";
+
+thread_local! {
+    /// この画面でモーダルを閉じたセッション。以降の更新は無視する（再表示・効果音なし）。
+    /// 終了の更新を受け取ったら（それ以上届かないので）忘れる
+    static DISMISSED_RUNS: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    /// モーダルに表示しているセッション。AppState.running と同じ規則（RunningInfo::apply_update）で追う
+    static SHOWN_RUN: RefCell<Option<RunningInfo>> = const { RefCell::new(None) };
+}
+
+/// 実行を始めてモーダルに表示したときに呼ぶ
+pub fn show(session: &RunSession) {
+    let previous = SHOWN_RUN.with(|s| s.borrow_mut().replace(RunningInfo::from_session(session.clone())));
+    if let Some(previous) = previous.filter(|p| p.session_id != session.id) {
+        timer_runner::cancel_run_sounds(&previous.session_id);
+    }
+}
+
+/// モーダルを閉じたときに呼ぶ
+pub fn dismiss(session_id: &str) {
+    timer_runner::cancel_scheduled_sounds();
+    SHOWN_RUN.with(|s| *s.borrow_mut() = None);
+    DISMISSED_RUNS.with(|d| d.borrow_mut().insert(session_id.to_string()));
+}
+
+/// 更新をモーダルの表示に反映し、そのセッションを表示しているかを返す
+fn follow(session: &RunSession) -> bool {
+    SHOWN_RUN.with(|s| {
+        let mut shown = s.borrow_mut();
+        RunningInfo::apply_update(&mut shown, session.clone());
+        shown.as_ref().is_some_and(|r| r.session_id == session.id)
+    })
+}
+
+fn is_dismissed(session: &RunSession) -> bool {
+    DISMISSED_RUNS.with(|d| {
+        let mut dismissed = d.borrow_mut();
+        if session.status.is_finished() {
+            dismissed.remove(&session.id)
+        } else {
+            dismissed.contains(&session.id)
+        }
+    })
+}
+
+/// 購読を開始する。戻り値を呼ぶと購読を終えてハンドラーを解放する。
+/// 接続が切れた場合はブラウザが自動的に再接続する。
+pub fn subscribe(state: AppStateContext) -> Option<impl FnOnce()> {
+    let source = match EventSource::new(RUN_EVENTS_URL) {
+        Ok(s) => s,
+        Err(e) => {
+            log::error!("Failed to open run event stream: {:?}", e);
+            return None;
+        }
+    };
+
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+        let Some(text) = e.data().as_string() else {
+            return;
+        };
+        match serde_json::from_str::<RunUpdate>(&text) {
+            Ok(update) => {
+                if is_dismissed(&update.session) {
+                    return;
+                }
+                let session = &update.session;
+                if follow(session) {
+                    timer_runner::init_audio_cache(&session.timer);
+                    timer_runner::play_run_sounds(
+                        &session.id,
+                        &session.timer,
+                        &session.status,
+                        &session.progress,
+                        &update.events,
+                    );
+                } else {
+                    timer_runner::cancel_run_sounds(&session.id);
+                }
+                state.dispatch(AppAction::ApplyRunUpdate(update.session));
+            }
+            Err(e) => log::error!("Invalid run update: {}", e),
+        }
+    });
+    source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+    Some(move || {
+        source.set_onmessage(None);
+        source.close();
+        drop(on_message);
+    })
+}
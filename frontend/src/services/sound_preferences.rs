@@ -0,0 +1,57 @@
+//! 効果音の音量・消音の設定。ログインしている利用者ごと・端末ごとに localStorage に保存する
+//! （共有リンクで開いたときはログインしていないので端末ごと）。
+
+use std::cell::RefCell;
+
+use nekotimer_shared::SoundPreferences;
+
+use crate::services::audio_engine;
+
+/// 設定を保存する localStorage のキー。ログイン中は後ろに利用者の ID を付ける
+const STORAGE_KEY: &str = "nekotimer.sound";
+
+thread_local! {
+    static USER: RefCell<Option<String>> = const { RefCell::new(None) };
+    static PREFERENCES: RefCell<Option<SoundPreferences>> = const { RefCell::new(None) };
+}
+
+fn storage_key() -> String {
+    USER.with(|user| match user.borrow().as_deref() {
+        Some(id) => format!("{}.{}", STORAGE_KEY, id),
+        None => STORAGE_KEY.to_string(),
+    })
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+}
+
+/// ログイン・ログアウトしたときに呼び、その利用者の設定に切り替える
+pub fn set_user(user_id: Option<String>) {
+    USER.with(|user| *user.borrow_mut() = user_id);
+    PREFERENCES.with(|cell| *cell.borrow_mut() = None);
+    audio_engine::set_master_volume(preferences().master_volume());
+}
+
+/// 保存した設定。なければ既定（すべて有効・最大音量）
+pub fn preferences() -> SoundPreferences {
+    PREFERENCES.with(|cell| {
+        cell.borrow_mut()
+            .get_or_insert_with(|| {
+                storage()
+                    .and_then(|s| s.get_item(&storage_key()).ok().flatten())
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default()
+            })
+            .clone()
+    })
+}
+
+/// 設定を保存して全体の音量をすぐ反映する
+pub fn save_preferences(preferences: &SoundPreferences) {
+    PREFERENCES.with(|cell| *cell.borrow_mut() = Some(preferences.clone()));
+    audio_engine::set_master_volume(preferences.master_volume());
+    if let (Some(storage), Ok(json)) = (storage(), serde_json::to_string(preferences)) {
+        let _ = storage.set_item(&storage_key(), &json);
+    }
+}
@@ -0,0 +1,130 @@
+//! 実行中の読み上げ（ブラウザの Speech Synthesis API）。
+//! 読み上げる文は shared の announcements で組み立て、設定は端末ごとに localStorage に保存する。
+
+use std::cell::RefCell;
+
+use nekotimer_shared::{announcements, Locale, RunEvent, SpeechSettings, TimerConfig};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{SpeechSynthesis, SpeechSynthesisUtterance, SpeechSynthesisVoice};
+
+use crate::services::locale::current_locale;
+use crate::services::sound_preferences;
+
+/// 読み上げの設定を保存する localStorage のキー
+const STORAGE_KEY: &str = "nekotimer.speech";
+
+thread_local! {
+    static SETTINGS: RefCell<Option<SpeechSettings>> = const { RefCell::new(None) };
+}
+
+fn synthesis() -> Option<SpeechSynthesis> {
+    web_sys::window()?.speech_synthesis().ok()
+}
+
+/// このブラウザで読み上げができるか
+pub fn is_supported() -> bool {
+    synthesis().is_some()
+}
+
+/// 読み上げの言語タグ（声の選択と SpeechSynthesisUtterance.lang に使う）
+pub fn speech_lang(locale: Locale) -> &'static str {
+    match locale {
+        Locale::Ja => "ja-JP",
+        Locale::En => "en-US",
+    }
+}
+
+/// 保存した設定。なければ画面の言語で読み上げる既定の設定（読み上げはオフ）
+pub fn settings() -> SpeechSettings {
+    SETTINGS.with(|cell| {
+        cell.borrow_mut()
+            .get_or_insert_with(|| {
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|s| s.get_item(STORAGE_KEY).ok().flatten())
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_else(|| SpeechSettings::for_locale(current_locale()))
+            })
+            .clone()
+    })
+}
+
+/// 設定をこの端末の localStorage に保存する
+pub fn save_settings(settings: &SpeechSettings) {
+    SETTINGS.with(|cell| *cell.borrow_mut() = Some(settings.clone()));
+    let storage = web_sys::window().and_then(|w| w.local_storage().ok().flatten());
+    if let (Some(storage), Ok(json)) = (storage, serde_json::to_string(settings)) {
+        let _ = storage.set_item(STORAGE_KEY, &json);
+    }
+}
+
+/// 言語に合う声。ブラウザによっては読み込みが遅れ、最初は空のことがある（voiceschanged で取り直す）
+pub fn voices(locale: Locale) -> Vec<SpeechSynthesisVoice> {
+    let Some(synthesis) = synthesis() else {
+        return Vec::new();
+    };
+    synthesis
+        .get_voices()
+        .iter()
+        .filter_map(|v| v.dyn_into::<SpeechSynthesisVoice>().ok())
+        .filter(|v| Locale::from_tag(&v.lang()) == Some(locale))
+        .collect()
+}
+
+/// 声の一覧が読み込まれたら on_change を呼ぶ。戻り値はコンポーネントを外すときに呼ぶ後片付け
+pub fn watch_voices(on_change: impl Fn() + 'static) -> impl FnOnce() {
+    let synthesis = synthesis();
+    let closure = Closure::<dyn Fn()>::new(on_change);
+    if let Some(synthesis) = &synthesis {
+        synthesis.set_onvoiceschanged(Some(closure.as_ref().unchecked_ref()));
+    }
+    move || {
+        if let Some(synthesis) = synthesis {
+            synthesis.set_onvoiceschanged(None);
+        }
+        drop(closure);
+    }
+}
+
+/// 読み上げを順番待ちに加える（前の文の途中では割り込まない）。音量は効果音の全体の音量に合わせる
+pub fn speak(text: &str, settings: &SpeechSettings) {
+    let (Some(synthesis), Ok(utterance)) = (synthesis(), SpeechSynthesisUtterance::new_with_text(text)) else {
+        return;
+    };
+    utterance.set_lang(speech_lang(settings.locale));
+    let (min, max) = SpeechSettings::RATE_RANGE;
+    utterance.set_rate(settings.rate.clamp(min, max));
+    utterance.set_volume(sound_preferences::preferences().master_volume());
+    if let Some(name) = &settings.voice {
+        let voice = voices(settings.locale).into_iter().find(|v| v.name() == *name);
+        utterance.set_voice(voice.as_ref());
+    }
+    synthesis.speak(&utterance);
+}
+
+/// 実行イベントに対応する文を読み上げる（読み上げがオフ・消音中なら何もしない）
+pub fn announce(timer: &TimerConfig, events: &[RunEvent]) {
+    let settings = settings();
+    if !settings.enabled || sound_preferences::preferences().muted {
+        return;
+    }
+    for text in announcements(timer, &settings, events) {
+        speak(&text, &settings);
+    }
+}
+
+/// iOS Safari 向け: ユーザー操作（実行ボタン押下）内で一度読み上げておくと、以降の読み上げが鳴るようになる
+pub fn unlock() {
+    let settings = settings();
+    if settings.enabled {
+        speak("", &settings);
+    }
+}
+
+/// 読み上げ中・順番待ちの文を取り消す
+pub fn cancel() {
+    if let Some(synthesis) = synthesis() {
+        synthesis.cancel();
+    }
+}
@@ -1,268 +1,178 @@
-//! タイマー通し実行: ブロック順に Wait / Countdown を実行し、効果音を鳴らす。
-//! キャンセルトークンと進捗 dispatch に対応。
-//! 効果音は起動時にプリロードしてキャッシュし、再生時は毎回ロードしない。
-
-use std::cell::{Cell, RefCell};
+//! タイマー実行の効果音: 実行そのものはサーバー側のセッション（共有リンクではブラウザ内のエンジン）で行い、
+//! ここでは受信した実行イベントに合わせて効果音を鳴らす。
+//! 効果音はタイマーを選んだときにそのタイマーで使うものだけをプリロードしてキャッシュし、再生時は毎回ロードしない。
+//!
+//! 更新の受信は通信などで前後するため、tick を受け取るたびに次の1秒で起きるイベントを先読みし、
+//! その効果音を AudioContext の時計で予約しておく。予想どおりの更新が届いたら鳴らし直さず、
+//! 外れたとき（一時停止・スキップなど）は予約を取り消して受信したイベントの効果音を鳴らす。
+//! 読み上げ（speech）は予約できないので受信したときに行う。
+//! 音量と鳴らすタイミングの有効・無効は sound_preferences の設定に従う。
+
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::rc::Rc;
 
-use gloo_timers::future::TimeoutFuture;
-use nekotimer_shared::{CountdownBlock, TimerBlock, TimerConfig, WaitBlock};
-use web_sys::{AudioContext, HtmlAudioElement};
+use nekotimer_shared::{
+    resolve_sound, sound_events, sound_path, used_sounds, RunEngine, RunEvent, RunProgress,
+    RunStatus, SoundProfile, TimerConfig, SILENT_SOUND,
+};
+
+use crate::services::audio_engine::{self, Scheduled};
+use crate::services::{sound_preferences, speech};
+
+/// 受信の遅れとみなす上限。これより遅れて tick が届いたら時刻の基準を取り直す
+const MAX_JITTER_SECS: f64 = 0.25;
+/// 予約はこれより先の時刻のときだけ行う（間に合わなければ受信時に鳴らす）
+const MIN_LEAD_SECS: f64 = 0.05;
 
-use crate::state::{AppAction, CountdownPhase, AppStateContext};
+/// 鳴らす効果音の ID と音量
+type EventSound = (String, f32);
 
-const TICK_UNDER_SECS: u32 = 5;
-const SOUND_TICK: &str = "/sound/tick.mp3";
-const SOUND_BLOCK_END: &str = "/sound/block_end.mp3";
-const SOUND_ALL_END: &str = "/sound/all_end.mp3";
-const SOUND_COUNTDOWN_BLK_NEXT: &str = "/sound/countdown_blk_next.mp3";
+/// 実行ごとの先読みの状態
+struct Lookahead {
+    /// 経過0秒にあたる AudioContext の時刻。tick の受信が最も早かったものに合わせる
+    origin: f64,
+    /// 予約済みの効果音: (経過秒数, 効果音の ID と音量, 予約)
+    pending: Option<(u64, Vec<EventSound>, Vec<Scheduled>)>,
+}
+
+/// ブラウザ内で実行するときの run_key
+pub const LOCAL_RUN: &str = "local";
 
 thread_local! {
-    static AUDIO_CACHE: RefCell<Option<HashMap<String, HtmlAudioElement>>> = RefCell::new(None);
-    /// iOS WebKit などで AudioContext が suspended のままになるのを防ぐ。ユーザー操作（実行ボタン）内で呼ぶ。
-    static AUDIO_CONTEXT: RefCell<Option<AudioContext>> = RefCell::new(None);
+    /// 効果音プロファイル。SSE の受信時など AppState を参照できない場所でも使うためここに持つ
+    static SOUND_PROFILES: RefCell<Vec<SoundProfile>> = const { RefCell::new(Vec::new()) };
+    /// 実行の識別（サーバーのセッション ID、ブラウザ内の実行は LOCAL_RUN）→ 先読みの状態
+    static LOOKAHEAD: RefCell<HashMap<String, Lookahead>> = RefCell::new(HashMap::new());
 }
 
 /// iOS WebKit 向け: ユーザー操作（実行ボタン押下）内で呼び、AudioContext を resume する。
-/// これによりオーディオが unlocked され、その後の HtmlAudioElement 再生が可能になることがある。
 pub fn unlock_audio_for_ios() {
-    AUDIO_CONTEXT.with(|cell| {
-        if cell.borrow().is_none() {
-            if let Ok(ctx) = AudioContext::new() {
-                let _ = ctx.resume();
-                *cell.borrow_mut() = Some(ctx);
-            }
-        } else {
-            if let Some(ref ctx) = *cell.borrow() {
-                let _ = ctx.resume();
-            }
-        }
-    });
+    audio_engine::resume();
+    speech::unlock();
 }
 
-/// ウェブアセンブリ起動時に呼び、各 MP3 をプリロードしてキャッシュする。
-pub fn init_audio_cache() {
-    AUDIO_CACHE.with(|cache| {
-        if cache.borrow().is_none() {
-            let mut m = HashMap::new();
-            for path in [SOUND_TICK, SOUND_BLOCK_END, SOUND_ALL_END, SOUND_COUNTDOWN_BLK_NEXT] {
-                if let Ok(audio) = HtmlAudioElement::new_with_src(path) {
-                    let _ = audio.load();
-                    m.insert(path.to_string(), audio);
-                }
-            }
-            *cache.borrow_mut() = Some(m);
-        }
-    });
+/// サーバーから取得した効果音プロファイルを設定する
+pub fn set_sound_profiles(profiles: Vec<SoundProfile>) {
+    SOUND_PROFILES.with(|cell| *cell.borrow_mut() = profiles);
 }
 
-fn play_sound(path: &str) {
-    AUDIO_CACHE.with(|cache| {
-        if let Ok(guard) = cache.try_borrow() {
-            if let Some(ref map) = *guard {
-                if let Some(audio) = map.get(path) {
-                    let _ = audio.set_current_time(0.0);
-                    let _ = audio.play();
-                }
-            }
-        }
-    });
+fn with_profile<R>(timer: &TimerConfig, f: impl FnOnce(Option<&SoundProfile>) -> R) -> R {
+    SOUND_PROFILES.with(|cell| {
+        let profiles = cell.borrow();
+        let profile = timer
+            .sound_profile
+            .as_ref()
+            .and_then(|id| profiles.iter().find(|p| p.id == *id));
+        f(profile)
+    })
 }
 
-fn block_initial_remaining(block: &TimerBlock) -> u32 {
-    match block {
-        TimerBlock::Wait(w) => w.minutes * 60 + w.seconds,
-        TimerBlock::Countdown(c) => c.minutes * 60 + c.seconds,
+/// タイマーを選んだとき・実行を始めるときに呼び、そのタイマーで鳴る効果音だけをプリロードする。
+pub fn init_audio_cache(timer: &TimerConfig) {
+    let sounds = with_profile(timer, |profile| used_sounds(timer, profile));
+    for id in sounds {
+        if let Some(path) = sound_path(&id) {
+            audio_engine::preload(&path);
+        }
     }
 }
 
-/// 1秒待ち、残り秒数を on_tick で報告。キャンセルされていたら false。
-async fn wait_one_second_report(
-    remaining_after_this_sec: u32,
-    cancel: &Rc<Cell<bool>>,
-    on_tick: &impl Fn(u32),
-) -> bool {
-    if cancel.get() {
-        return false;
-    }
-    TimeoutFuture::new(1000).await;
-    if remaining_after_this_sec <= TICK_UNDER_SECS && remaining_after_this_sec >= 1 {
-        play_sound(SOUND_TICK);
+/// 効果音を1回鳴らす（選択画面での試聴に使う）
+pub fn play_sound(id: &str) {
+    audio_engine::resume();
+    if let Some(now) = audio_engine::now() {
+        audio_engine::schedule(id, now, 1.0);
     }
-    on_tick(remaining_after_this_sec);
-    !cancel.get()
 }
 
-/// 指定秒数だけ待機。残り5秒以下で1秒ごとに tick。毎秒 on_tick(残り秒) を呼ぶ。
-async fn wait_seconds_with_tick(
-    total_secs: u32,
-    cancel: &Rc<Cell<bool>>,
-    on_tick: &impl Fn(u32),
-) -> bool {
-    if total_secs == 0 {
-        return true;
-    }
-    for elapsed in 1..=total_secs {
-        let remaining = total_secs - elapsed;
-        if !wait_one_second_report(remaining, cancel, on_tick).await {
-            return false;
-        }
-    }
-    true
+/// イベントで鳴らす効果音の ID と音量（無音と、設定で鳴らさないタイミングを除く）
+fn event_sounds(timer: &TimerConfig, events: &[RunEvent]) -> Vec<EventSound> {
+    let preferences = sound_preferences::preferences();
+    with_profile(timer, |profile| {
+        sound_events(events, timer.tick_under_secs())
+            .into_iter()
+            .filter_map(|(block_ix, event)| {
+                let volume = preferences.event_volume(event)?;
+                let id = resolve_sound(timer, profile, block_ix, event);
+                (id != SILENT_SOUND).then(|| (id.to_string(), volume))
+            })
+            .collect()
+    })
 }
 
-fn dispatch_progress(
-    state: &AppStateContext,
-    block_ix: usize,
-    remaining_secs: u32,
-    countdown_run: Option<(u32, u32)>,
-    countdown_phase: Option<CountdownPhase>,
+/// 実行の更新を受け取るたびに呼び、イベントに対応する効果音・読み上げを行って次の1秒の効果音を予約する。
+/// どのイベントで鳴らすかは shared の sound_events、何を鳴らすかは resolve_sound で決める。
+pub fn play_run_sounds(
+    run_key: &str,
+    timer: &TimerConfig,
+    status: &RunStatus,
+    progress: &RunProgress,
+    events: &[RunEvent],
 ) {
-    state.dispatch(AppAction::SetRunningProgress {
-        block_ix,
-        remaining_secs,
-        countdown_run,
-        countdown_phase,
-    });
-}
-
-async fn run_wait_block(
-    w: &WaitBlock,
-    block_ix: usize,
-    cancel: &Rc<Cell<bool>>,
-    state: &AppStateContext,
-) -> bool {
-    let total_secs = w.minutes * 60 + w.seconds;
-    let state = state.clone();
-    let on_tick = move |remaining: u32| {
-        dispatch_progress(&state, block_ix, remaining, None, None);
+    speech::announce(timer, events);
+    let Some(now) = audio_engine::now() else {
+        return;
     };
-    wait_seconds_with_tick(total_secs, cancel, &on_tick).await
-}
-
-async fn run_countdown_block(
-    c: &CountdownBlock,
-    block_ix: usize,
-    cancel: &Rc<Cell<bool>>,
-    state: &AppStateContext,
-) -> bool {
-    let countdown_secs = c.minutes * 60 + c.seconds;
-    let interval_secs = c.interval_minutes * 60 + c.interval_seconds;
-    let repeat = c.repeat_count.max(1);
-    let state = state.clone();
-
-    for run in 0..repeat {
-        if cancel.get() {
-            return false;
+    let sounds = event_sounds(timer, events);
+    let elapsed = progress.elapsed_secs;
+    let ticked = events.iter().any(|e| matches!(e, RunEvent::Tick { .. }));
+
+    LOOKAHEAD.with(|cell| {
+        let mut runs = cell.borrow_mut();
+        let previous = runs.remove(run_key);
+
+        // 予約どおりの更新なら鳴らし直さない。外れたら予約を取り消して今鳴らす
+        let mut played = false;
+        if let Some((at, predicted, scheduled)) = previous.as_ref().and_then(|la| la.pending.as_ref()) {
+            if ticked && *at == elapsed && *predicted == sounds {
+                played = true;
+            } else {
+                scheduled.iter().for_each(Scheduled::cancel);
+            }
         }
-        let run_1based = run + 1;
-        dispatch_progress(
-            &state,
-            block_ix,
-            countdown_secs,
-            Some((run_1based, repeat)),
-            Some(CountdownPhase::Countdown),
-        );
-        let on_tick = {
-            let state = state.clone();
-            move |remaining: u32| {
-                dispatch_progress(
-                    &state,
-                    block_ix,
-                    remaining,
-                    Some((run_1based, repeat)),
-                    Some(CountdownPhase::Countdown),
-                );
+        if !played {
+            for (id, volume) in &sounds {
+                audio_engine::schedule(id, now, *volume);
             }
-        };
-        if !wait_seconds_with_tick(countdown_secs, cancel, &on_tick).await {
-            return false;
         }
-        // 最後のカウントダウンでない場合、カウントダウン終了後に再生
-        if run + 1 < repeat {
-            play_sound(SOUND_COUNTDOWN_BLK_NEXT);
+
+        // 次の tick の時刻が分かるのは tick を受け取ったときだけ（開始・再開・スキップの直後は待つ）
+        if !ticked || *status != RunStatus::Running {
+            return;
         }
-        if run + 1 < repeat {
-            if cancel.get() {
-                return false;
-            }
-            dispatch_progress(
-                &state,
-                block_ix,
-                interval_secs,
-                Some((run_1based, repeat)),
-                Some(CountdownPhase::Interval),
-            );
-            let on_tick_int = {
-                let state = state.clone();
-                move |remaining: u32| {
-                    dispatch_progress(
-                        &state,
-                        block_ix,
-                        remaining,
-                        Some((run_1based, repeat)),
-                        Some(CountdownPhase::Interval),
-                    );
-                }
-            };
-            if !wait_seconds_with_tick(interval_secs, cancel, &on_tick_int).await {
-                return false;
-            }
-            // インターバル終了・次のカウントダウンに移る場合に再生
-            play_sound(SOUND_COUNTDOWN_BLK_NEXT);
+        let arrived = now - elapsed as f64;
+        let origin = match previous {
+            Some(la) if arrived - la.origin < MAX_JITTER_SECS => la.origin.min(arrived),
+            _ => arrived,
+        };
+        let mut lookahead = Lookahead { origin, pending: None };
+        let at = origin + (elapsed + 1) as f64;
+        if at > now + MIN_LEAD_SECS {
+            let mut engine = RunEngine::from_progress(timer.clone(), status.clone(), progress);
+            let next = event_sounds(timer, &engine.tick());
+            let scheduled = next.iter().map(|(id, volume)| audio_engine::schedule(id, at, *volume)).collect();
+            lookahead.pending = Some((elapsed + 1, next, scheduled));
         }
-    }
-    true
+        runs.insert(run_key.to_string(), lookahead);
+    });
 }
 
-async fn run_block(
-    block: &TimerBlock,
-    block_ix: usize,
-    is_last: bool,
-    cancel: &Rc<Cell<bool>>,
-    state: &AppStateContext,
-) -> bool {
-    let ok = match block {
-        TimerBlock::Wait(w) => run_wait_block(w, block_ix, cancel, state).await,
-        TimerBlock::Countdown(c) => run_countdown_block(c, block_ix, cancel, state).await,
-    };
-    if !ok {
-        return false;
-    }
-    if is_last {
-        play_sound(SOUND_ALL_END);
-    } else {
-        play_sound(SOUND_BLOCK_END);
+fn cancel_lookahead(lookahead: Lookahead) {
+    if let Some((_, _, scheduled)) = lookahead.pending {
+        scheduled.iter().for_each(Scheduled::cancel);
     }
-    true
 }
 
-/// タイマーを先頭ブロックから最後まで実行。キャンセル時は TimerExecutionStopped を dispatch。
-pub async fn run_timer(timer: TimerConfig, cancel_token: Rc<Cell<bool>>, state: AppStateContext) {
-    let blocks = timer.blocks.clone();
-    if blocks.is_empty() {
-        state.dispatch(AppAction::TimerExecutionStopped);
-        return;
+/// 効果音を鳴らさなくなった実行（モーダルに表示しなくなったセッション）の予約を取り消す
+pub fn cancel_run_sounds(run_key: &str) {
+    if let Some(lookahead) = LOOKAHEAD.with(|cell| cell.borrow_mut().remove(run_key)) {
+        cancel_lookahead(lookahead);
     }
-    let initial = block_initial_remaining(&blocks[0]);
-    dispatch_progress(&state, 0, initial, None, None);
+}
 
-    for (i, block) in blocks.iter().enumerate() {
-        if cancel_token.get() {
-            state.dispatch(AppAction::TimerExecutionStopped);
-            return;
-        }
-        let is_last = i == blocks.len() - 1;
-        if !run_block(block, i, is_last, &cancel_token, &state).await {
-            state.dispatch(AppAction::TimerExecutionStopped);
-            return;
-        }
-        let next_ix = i + 1;
-        if next_ix < blocks.len() {
-            let next_initial = block_initial_remaining(&blocks[next_ix]);
-            dispatch_progress(&state, next_ix, next_initial, None, None);
-        }
-    }
-    state.dispatch(AppAction::TimerExecutionComplete);
+/// 実行の画面を閉じたときに呼び、予約済みの効果音と読み上げ待ちの文を取り消す
+pub fn cancel_scheduled_sounds() {
+    speech::cancel();
+    let runs = LOOKAHEAD.with(|cell| std::mem::take(&mut *cell.borrow_mut()));
+    runs.into_values().for_each(cancel_lookahead);
 }
@@ -0,0 +1,32 @@
+//! 入力エラーを入力欄に表示するためのヘルパー
+
+use nekotimer_shared::ValidationError;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+use crate::services::locale::current_locale;
+
+/// field（`name` や `blocks[2].seconds`）の最初のエラーを利用者の言語で
+pub fn field_message(errors: &[ValidationError], field: &str) -> Option<String> {
+    errors
+        .iter()
+        .find(|e| e.field == field)
+        .map(|e| e.render(current_locale()))
+}
+
+/// data-field が field の入力欄（なければ最初のエラーの入力欄）までスクロールしてフォーカスする
+pub fn focus_field(field: Option<&str>) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    let selector = match field {
+        Some(field) => format!("[data-field=\"{}\"]", field),
+        None => ".field-invalid".to_string(),
+    };
+    if let Ok(Some(el)) = document.query_selector(&selector) {
+        el.scroll_into_view_with_bool(false);
+        if let Ok(el) = el.dyn_into::<HtmlElement>() {
+            let _ = el.focus();
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use std::cell::Cell;
 use std::rc::Rc;
 use yew::prelude::*;
-use nekotimer_shared::{TimerConfig, TimerBlock, WaitBlock};
+use nekotimer_shared::{
+    CountdownPhase, CustomSound, RunEngine, RunProgress, RunSession, RunStatus, TimerConfig, TimerBlock,
+    SoundProfile, SoundSet, UserInfo, ValidationError, ValidationPolicy, WaitBlock, Workspace,
+    WorkspaceRole,
+};
 
 pub type AppStateContext = UseReducerHandle<AppState>;
 
@@ -11,58 +14,113 @@ pub enum ViewMode {
     Builder,
     /// サイドバーでマウスオーバーしたタイマーのプレビュー表示用（非編集）
     ViewTimer(String),
+    /// 実行履歴・統計
+    History,
+    /// Webhook の管理
+    Webhooks,
+    /// API トークンの管理
+    Tokens,
+    /// ワークスペースとメンバーの管理
+    Workspaces,
+    /// 効果音プロファイルの管理
+    SoundProfiles,
+    /// アップロードした効果音の管理
+    SoundLibrary,
+    /// 読み上げの設定
+    Speech,
 }
 
 /// 未保存のまま遷移しようとしたときの保留先
 #[derive(Clone, Debug, PartialEq)]
 pub enum PendingNavigation {
-    ToViewTimer(String),
-    ToEditTimer(String),
-    ToNewTimer,
+    ViewTimer(String),
+    EditTimer(String),
+    NewTimer,
+    History,
+    Webhooks,
+    Tokens,
+    Workspaces,
+    SoundProfiles,
+    SoundLibrary,
+    Speech,
 }
 
-/// カウントダウンブロック内のどちらの区間を実行中か
+/// タイマー実行中モーダル用の状態。サーバー側の実行セッションを写したもの。
 #[derive(Clone, Debug, PartialEq)]
-pub enum CountdownPhase {
-    /// カウントダウン区間
-    Countdown,
-    /// インターバル待機区間
-    Interval,
-}
-
-/// タイマー実行中モーダル用の状態。閉じたら cancel_token を true にして実行中止。
-#[derive(Clone, Debug)]
 pub struct RunningInfo {
+    pub session_id: String,
     pub timer: TimerConfig,
     pub current_block_index: usize,
     pub remaining_secs: u32,
     pub is_complete: bool,
-    pub cancel_token: Rc<Cell<bool>>,
+    pub is_paused: bool,
     /// カウントダウンブロック時のみ: (現在の回数 1-based, 繰り返し回数)
     pub countdown_run: Option<(u32, u32)>,
     /// カウントダウンブロック時のみ: 現在カウントダウン中かインターバル中か
     pub countdown_phase: Option<CountdownPhase>,
 }
 
-impl PartialEq for RunningInfo {
-    fn eq(&self, other: &Self) -> bool {
-        self.timer == other.timer
-            && self.current_block_index == other.current_block_index
-            && self.remaining_secs == other.remaining_secs
-            && self.is_complete == other.is_complete
-            && self.countdown_run == other.countdown_run
-            && self.countdown_phase == other.countdown_phase
+impl RunningInfo {
+    fn from_parts(session_id: String, timer: TimerConfig, status: RunStatus, progress: RunProgress) -> Self {
+        Self {
+            session_id,
+            timer,
+            current_block_index: progress.block_ix,
+            remaining_secs: progress.remaining_secs,
+            is_complete: status == RunStatus::Completed,
+            is_paused: status == RunStatus::Paused,
+            countdown_run: progress.countdown_run,
+            countdown_phase: progress.countdown_phase,
+        }
+    }
+
+    pub fn from_session(session: RunSession) -> Self {
+        Self::from_parts(session.id, session.timer, session.status, session.progress)
+    }
+
+    /// 受信したセッションの更新をモーダルに表示する実行に反映する。
+    /// 表示中のセッションはそのまま更新し、表示中がない（または完了した）ときは実行中のセッションに切り替える。
+    /// run_events も同じ規則で表示中のセッションを追い、その効果音だけを鳴らす
+    pub fn apply_update(running: &mut Option<RunningInfo>, session: RunSession) {
+        let is_current = running.as_ref().is_some_and(|r| r.session_id == session.id);
+        if session.status == RunStatus::Aborted {
+            if is_current {
+                *running = None;
+            }
+        } else if is_current
+            || (!session.status.is_finished() && running.as_ref().is_none_or(|r| r.is_complete))
+        {
+            *running = Some(RunningInfo::from_session(session));
+        }
+    }
+
+    /// ブラウザ内で実行中のエンジンから作る（共有リンクでの実行用。session_id は空）
+    pub fn from_engine(engine: &RunEngine) -> Self {
+        Self::from_parts(String::new(), engine.timer().clone(), engine.status(), engine.progress())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct AppState {
+    /// ログイン中のユーザー。None ならログイン画面を表示する
+    pub user: Option<UserInfo>,
+    /// 起動時のログイン状態の確認が済んだか
+    pub auth_checked: bool,
+    /// 見えるすべてのタイマー（個人とワークスペース）。サイドバーには選択中のスペースの分だけ出す
     pub timers: Vec<TimerConfig>,
+    pub workspaces: Vec<Workspace>,
+    /// サイドバーで選択中のワークスペース。None なら個人タイマー
+    pub current_workspace: Option<String>,
     pub view: ViewMode,
     pub editing_timer: Option<TimerConfig>,
-    pub dragging_block_index: Option<usize>,
-    pub dragging_new_block: Option<String>,
-    pub validation_errors: Vec<String>,
+    /// 保存時にサーバーが返したエラー（入力中のエラーはビルダーがその場で検証する）
+    pub validation_errors: Vec<ValidationError>,
+    /// サーバーの検証ルール（ログイン後に取得する。取得前は既定値）
+    pub validation_policy: ValidationPolicy,
+    /// サーバーの効果音プロファイル（全ユーザー共通）
+    pub sound_profiles: Vec<SoundProfile>,
+    /// アップロードした効果音（全ユーザー共通）
+    pub custom_sounds: Vec<CustomSound>,
     pub last_saved_id: Option<String>,
     pub form_dirty: bool,
     /// 未保存確認モーダルで選択待ちの遷移先
@@ -76,12 +134,17 @@ pub struct AppState {
 impl Default for AppState {
     fn default() -> Self {
         Self {
+            user: None,
+            auth_checked: false,
             timers: Vec::new(),
+            workspaces: Vec::new(),
+            current_workspace: None,
             view: ViewMode::NoContent,
             editing_timer: None,
-            dragging_block_index: None,
-            dragging_new_block: None,
             validation_errors: Vec::new(),
+            validation_policy: ValidationPolicy::default(),
+            sound_profiles: Vec::new(),
+            custom_sounds: Vec::new(),
             last_saved_id: None,
             form_dirty: false,
             pending_navigation: None,
@@ -91,43 +154,81 @@ impl Default for AppState {
     }
 }
 
+impl AppState {
+    /// 選択中のスペースのタイマー
+    pub fn current_timers(&self) -> impl Iterator<Item = &TimerConfig> {
+        self.timers
+            .iter()
+            .filter(|t| t.workspace_id == self.current_workspace)
+    }
+
+    /// タイマーに対する自分の役割（個人タイマーはオーナー）。画面の出し分け用で、権限はサーバーが確認する
+    pub fn role_for(&self, timer: &TimerConfig) -> WorkspaceRole {
+        match &timer.workspace_id {
+            None => WorkspaceRole::Owner,
+            Some(id) => self
+                .workspaces
+                .iter()
+                .find(|w| w.id == *id)
+                .map(|w| w.role)
+                .unwrap_or(WorkspaceRole::Viewer),
+        }
+    }
+}
+
 pub enum AppAction {
+    /// ログイン状態を設定する。None（ログアウト）なら画面の状態も初期化する
+    SetUser(Option<UserInfo>),
     SetTimers(Vec<TimerConfig>),
+    SetWorkspaces(Vec<Workspace>),
+    SetValidationPolicy(ValidationPolicy),
+    SetSoundProfiles(Vec<SoundProfile>),
+    SetCustomSounds(Vec<CustomSound>),
+    /// サイドバーのスペースを切り替える。None なら個人タイマー
+    SelectWorkspace(Option<String>),
     StartNewTimer,
     /// サイドバーでタイマーにマウスオーバーしたときにプレビュー表示。None でプレビュー解除。
     PreviewTimer(Option<String>),
     EditTimer(String),
+    /// 実行履歴・統計を表示
+    ShowHistory,
+    /// Webhook の管理画面を表示
+    ShowWebhooks,
+    /// API トークンの管理画面を表示
+    ShowTokens,
+    /// ワークスペースの管理画面を表示
+    ShowWorkspaces,
+    /// 効果音プロファイルの管理画面を表示
+    ShowSoundProfiles,
+    /// 効果音ライブラリを表示
+    ShowSoundLibrary,
+    /// 読み上げの設定を表示
+    ShowSpeech,
     SetTimerName(String),
+    /// 編集中のタイマーの効果音プロファイル。None なら使わない
+    SetTimerSoundProfile(Option<String>),
+    /// 編集中のタイマー全体の効果音
+    SetTimerSounds(SoundSet),
+    /// 編集中のタイマーの tick 音を鳴らし始める残り秒数。None なら既定
+    SetTimerTickUnderSecs(Option<u32>),
     AddBlock(TimerBlock, usize),
     RemoveBlock(usize),
     UpdateBlock(usize, TimerBlock),
     MoveBlock { from: usize, to: usize },
-    StartDraggingBlock(usize),
-    StartDraggingNewBlock(String),
-    StopDragging,
     SaveSuccess(TimerConfig),
-    SetValidationErrors(Vec<String>),
+    SetValidationErrors(Vec<ValidationError>),
     /// 未保存確認で「編集を止める」→ 破棄して遷移を実行
     ConfirmDiscard,
     /// 未保存確認で「編集を続ける」→ モーダルを閉じてビルダーに留まる
     CancelNavigate,
     /// ビルダー名入力へフォーカス済みの通知（フラグクリア用）
     ClearFocusBuilderName,
-    /// タイマー実行開始（モーダル表示用。runner は Sidebar で spawn）
-    StartTimerExecution(TimerConfig, Rc<Cell<bool>>),
-    /// 実行中のブロック・残り秒数の更新（カウントダウン時は回数とフェーズも）
-    SetRunningProgress {
-        block_ix: usize,
-        remaining_secs: u32,
-        countdown_run: Option<(u32, u32)>,
-        countdown_phase: Option<CountdownPhase>,
-    },
-    /// 全ブロック実行完了
-    TimerExecutionComplete,
-    /// モーダルを閉じて実行を中止（cancel_token を立てる）
+    /// この画面から実行を開始した
+    StartTimerExecution(RunSession),
+    /// サーバーから実行セッションの状態を受信
+    ApplyRunUpdate(RunSession),
+    /// モーダルを閉じる（中止 API の送信と run_events::dismiss は呼び出し側で行う）
     CloseRunningModal,
-    /// 実行が中止 or 完了したので running をクリア
-    TimerExecutionStopped,
 }
 
 impl Reducible for AppState {
@@ -137,12 +238,47 @@ impl Reducible for AppState {
         let mut next = (*self).clone();
 
         match action {
+            AppAction::SetUser(user) => {
+                if user.is_none() {
+                    next = AppState::default();
+                }
+                next.user = user;
+                next.auth_checked = true;
+            }
             AppAction::SetTimers(timers) => {
                 next.timers = timers;
             }
+            AppAction::SetWorkspaces(workspaces) => {
+                next.workspaces = workspaces;
+                let exists = next
+                    .current_workspace
+                    .as_ref()
+                    .is_none_or(|id| next.workspaces.iter().any(|w| w.id == *id));
+                if !exists {
+                    next.current_workspace = None;
+                }
+            }
+            AppAction::SetValidationPolicy(policy) => {
+                next.validation_policy = policy;
+            }
+            AppAction::SetSoundProfiles(profiles) => {
+                next.sound_profiles = profiles;
+            }
+            AppAction::SetCustomSounds(sounds) => {
+                next.custom_sounds = sounds;
+            }
+            AppAction::SelectWorkspace(id) => {
+                next.current_workspace = id;
+                // 編集中の内容があればビルダーは残す（保存先は編集中のタイマーの所属のまま）
+                let keep_builder = next.view == ViewMode::Builder && next.form_dirty;
+                if !keep_builder && matches!(next.view, ViewMode::Builder | ViewMode::ViewTimer(_)) {
+                    next.view = ViewMode::NoContent;
+                    next.editing_timer = None;
+                }
+            }
             AppAction::StartNewTimer => {
                 if next.view == ViewMode::Builder && next.form_dirty {
-                    next.pending_navigation = Some(PendingNavigation::ToNewTimer);
+                    next.pending_navigation = Some(PendingNavigation::NewTimer);
                     return Rc::new(next);
                 }
                 next.view = ViewMode::Builder;
@@ -155,13 +291,20 @@ impl Reducible for AppState {
                         name: "default".into(),
                         minutes: 0,
                         seconds: 10,
+                        sounds: SoundSet::default(),
+                        announcement: None,
                     })],
+                    owner: None,
+                    workspace_id: next.current_workspace.clone(),
+                    sound_profile: None,
+                    sounds: SoundSet::default(),
+                    tick_under_secs: None,
                 });
             }
             AppAction::PreviewTimer(opt_id) => {
                 if let Some(ref id) = opt_id {
                     if next.view == ViewMode::Builder && next.form_dirty {
-                        next.pending_navigation = Some(PendingNavigation::ToViewTimer(id.clone()));
+                        next.pending_navigation = Some(PendingNavigation::ViewTimer(id.clone()));
                         return Rc::new(next);
                     }
                 }
@@ -172,7 +315,7 @@ impl Reducible for AppState {
             }
             AppAction::EditTimer(id) => {
                 if next.view == ViewMode::Builder && next.form_dirty {
-                    next.pending_navigation = Some(PendingNavigation::ToEditTimer(id.clone()));
+                    next.pending_navigation = Some(PendingNavigation::EditTimer(id.clone()));
                     return Rc::new(next);
                 }
                 if let Some(timer) = next.timers.iter().find(|t| t.id == id) {
@@ -182,10 +325,81 @@ impl Reducible for AppState {
                     next.editing_timer = Some(timer.clone());
                 }
             }
+            AppAction::ShowHistory => {
+                if next.view == ViewMode::Builder && next.form_dirty {
+                    next.pending_navigation = Some(PendingNavigation::History);
+                    return Rc::new(next);
+                }
+                next.view = ViewMode::History;
+            }
+            AppAction::ShowWebhooks => {
+                if next.view == ViewMode::Builder && next.form_dirty {
+                    next.pending_navigation = Some(PendingNavigation::Webhooks);
+                    return Rc::new(next);
+                }
+                next.view = ViewMode::Webhooks;
+            }
+            AppAction::ShowTokens => {
+                if next.view == ViewMode::Builder && next.form_dirty {
+                    next.pending_navigation = Some(PendingNavigation::Tokens);
+                    return Rc::new(next);
+                }
+                next.view = ViewMode::Tokens;
+            }
+            AppAction::ShowWorkspaces => {
+                if next.view == ViewMode::Builder && next.form_dirty {
+                    next.pending_navigation = Some(PendingNavigation::Workspaces);
+                    return Rc::new(next);
+                }
+                next.view = ViewMode::Workspaces;
+            }
+            AppAction::ShowSoundProfiles => {
+                if next.view == ViewMode::Builder && next.form_dirty {
+                    next.pending_navigation = Some(PendingNavigation::SoundProfiles);
+                    return Rc::new(next);
+                }
+                next.view = ViewMode::SoundProfiles;
+            }
+            AppAction::ShowSoundLibrary => {
+                if next.view == ViewMode::Builder && next.form_dirty {
+                    next.pending_navigation = Some(PendingNavigation::SoundLibrary);
+                    return Rc::new(next);
+                }
+                next.view = ViewMode::SoundLibrary;
+            }
+            AppAction::ShowSpeech => {
+                if next.view == ViewMode::Builder && next.form_dirty {
+                    next.pending_navigation = Some(PendingNavigation::Speech);
+                    return Rc::new(next);
+                }
+                next.view = ViewMode::Speech;
+            }
             AppAction::SetTimerName(name) => {
                 if let Some(ref mut timer) = next.editing_timer {
                     timer.name = name;
                     next.form_dirty = true;
+                    next.validation_errors.clear();
+                }
+            }
+            AppAction::SetTimerSoundProfile(profile) => {
+                if let Some(ref mut timer) = next.editing_timer {
+                    timer.sound_profile = profile;
+                    next.form_dirty = true;
+                    next.validation_errors.clear();
+                }
+            }
+            AppAction::SetTimerSounds(sounds) => {
+                if let Some(ref mut timer) = next.editing_timer {
+                    timer.sounds = sounds;
+                    next.form_dirty = true;
+                    next.validation_errors.clear();
+                }
+            }
+            AppAction::SetTimerTickUnderSecs(secs) => {
+                if let Some(ref mut timer) = next.editing_timer {
+                    timer.tick_under_secs = secs;
+                    next.form_dirty = true;
+                    next.validation_errors.clear();
                 }
             }
             AppAction::AddBlock(block, index) => {
@@ -193,6 +407,7 @@ impl Reducible for AppState {
                     let idx = index.min(timer.blocks.len());
                     timer.blocks.insert(idx, block);
                     next.form_dirty = true;
+                    next.validation_errors.clear();
                 }
             }
             AppAction::RemoveBlock(index) => {
@@ -200,6 +415,7 @@ impl Reducible for AppState {
                     if index < timer.blocks.len() && timer.blocks.len() > 1 {
                         timer.blocks.remove(index);
                         next.form_dirty = true;
+                        next.validation_errors.clear();
                     }
                 }
             }
@@ -208,6 +424,7 @@ impl Reducible for AppState {
                     if index < timer.blocks.len() {
                         timer.blocks[index] = block;
                         next.form_dirty = true;
+                        next.validation_errors.clear();
                     }
                 }
             }
@@ -222,21 +439,10 @@ impl Reducible for AppState {
                         };
                         timer.blocks.insert(adjusted_to, block);
                         next.form_dirty = true;
+                        next.validation_errors.clear();
                     }
                 }
             }
-            AppAction::StartDraggingBlock(index) => {
-                next.dragging_block_index = Some(index);
-                next.dragging_new_block = None;
-            }
-            AppAction::StartDraggingNewBlock(block_type) => {
-                next.dragging_new_block = Some(block_type);
-                next.dragging_block_index = None;
-            }
-            AppAction::StopDragging => {
-                next.dragging_block_index = None;
-                next.dragging_new_block = None;
-            }
             AppAction::SaveSuccess(timer) => {
                 next.last_saved_id = Some(timer.id.clone());
                 let existing = next.timers.iter().position(|t| t.id == timer.id);
@@ -256,16 +462,16 @@ impl Reducible for AppState {
                     next.form_dirty = false;
                     next.validation_errors.clear();
                     match pending {
-                        PendingNavigation::ToViewTimer(id) => {
+                        PendingNavigation::ViewTimer(id) => {
                             next.view = ViewMode::ViewTimer(id);
                         }
-                        PendingNavigation::ToEditTimer(id) => {
+                        PendingNavigation::EditTimer(id) => {
                             if let Some(timer) = next.timers.iter().find(|t| t.id == id) {
                                 next.view = ViewMode::Builder;
                                 next.editing_timer = Some(timer.clone());
                             }
                         }
-                        PendingNavigation::ToNewTimer => {
+                        PendingNavigation::NewTimer => {
                             next.view = ViewMode::Builder;
                             next.editing_timer = Some(TimerConfig {
                                 id: String::new(),
@@ -274,9 +480,37 @@ impl Reducible for AppState {
                                     name: "default".into(),
                                     minutes: 0,
                                     seconds: 10,
+                                    sounds: SoundSet::default(),
+                                    announcement: None,
                                 })],
+                                owner: None,
+                                workspace_id: next.current_workspace.clone(),
+                                sound_profile: None,
+                                sounds: SoundSet::default(),
+                                tick_under_secs: None,
                             });
                         }
+                        PendingNavigation::History => {
+                            next.view = ViewMode::History;
+                        }
+                        PendingNavigation::Webhooks => {
+                            next.view = ViewMode::Webhooks;
+                        }
+                        PendingNavigation::Tokens => {
+                            next.view = ViewMode::Tokens;
+                        }
+                        PendingNavigation::Workspaces => {
+                            next.view = ViewMode::Workspaces;
+                        }
+                        PendingNavigation::SoundProfiles => {
+                            next.view = ViewMode::SoundProfiles;
+                        }
+                        PendingNavigation::SoundLibrary => {
+                            next.view = ViewMode::SoundLibrary;
+                        }
+                        PendingNavigation::Speech => {
+                            next.view = ViewMode::Speech;
+                        }
                     }
                 }
             }
@@ -287,42 +521,13 @@ impl Reducible for AppState {
             AppAction::ClearFocusBuilderName => {
                 next.focus_builder_name = false;
             }
-            AppAction::StartTimerExecution(timer, cancel_token) => {
-                next.running = Some(RunningInfo {
-                    timer,
-                    current_block_index: 0,
-                    remaining_secs: 0,
-                    is_complete: false,
-                    cancel_token,
-                    countdown_run: None,
-                    countdown_phase: None,
-                });
+            AppAction::StartTimerExecution(session) => {
+                next.running = Some(RunningInfo::from_session(session));
             }
-            AppAction::SetRunningProgress {
-                block_ix,
-                remaining_secs,
-                countdown_run,
-                countdown_phase,
-            } => {
-                if let Some(ref mut r) = next.running {
-                    r.current_block_index = block_ix;
-                    r.remaining_secs = remaining_secs;
-                    r.countdown_run = countdown_run;
-                    r.countdown_phase = countdown_phase;
-                }
-            }
-            AppAction::TimerExecutionComplete => {
-                if let Some(ref mut r) = next.running {
-                    r.is_complete = true;
-                }
+            AppAction::ApplyRunUpdate(session) => {
+                RunningInfo::apply_update(&mut next.running, session);
             }
             AppAction::CloseRunningModal => {
-                if let Some(ref r) = next.running {
-                    r.cancel_token.set(true);
-                }
-                next.running = None;
-            }
-            AppAction::TimerExecutionStopped => {
                 next.running = None;
             }
         }
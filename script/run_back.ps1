@@ -7,7 +7,8 @@ Push-Location ".."
 try {
     # 2. cargo run の実行
     # -p tako でパッケージを指定
-    cargo run -p nekotimer-backend
+    # trunk serve（127.0.0.1:8083）の画面からの API 呼び出しを CORS で許可する
+    cargo run -p nekotimer-backend -- --cors-origin http://127.0.0.1:8083
 }
 finally {
     # 3. エラーが起きても、Ctrl+Cで止めても、元の場所に戻る
@@ -0,0 +1,113 @@
+//! ユーザーとログイン
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ErrorCode;
+use crate::model::ValidationError;
+
+/// ブラウザ用のセッション Cookie 名（CLI などは Authorization: Bearer を使う）
+pub const SESSION_COOKIE: &str = "nekotimer_session";
+pub const USERNAME_MAX_LEN: usize = 32;
+pub const PASSWORD_MIN_LEN: usize = 8;
+
+/// API で返すユーザー情報（パスワードハッシュは含まない）
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UserInfo {
+    pub id: String,
+    pub username: String,
+    pub is_admin: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoginResponse {
+    /// Authorization: Bearer に使うトークン（ブラウザには Cookie でも渡す）
+    pub token: String,
+    /// 有効期限（UNIX 秒）
+    pub expires_at: u64,
+    pub user: UserInfo,
+}
+
+/// ユーザー名は英数字と `_` `-` `.` で 1〜32 文字
+pub fn validate_username(username: &str) -> Result<(), ValidationError> {
+    let ok = !username.is_empty()
+        && username.chars().count() <= USERNAME_MAX_LEN
+        && username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if ok {
+        Ok(())
+    } else {
+        Err(ValidationError::new("username", ErrorCode::InvalidUsername { max: USERNAME_MAX_LEN }))
+    }
+}
+
+pub fn validate_password(password: &str) -> Result<(), ValidationError> {
+    if password.chars().count() >= PASSWORD_MIN_LEN {
+        Ok(())
+    } else {
+        Err(ValidationError::new("password", ErrorCode::TooShort { min: PASSWORD_MIN_LEN }))
+    }
+}
+
+/// API トークンの先頭に付ける文字列（セッションのトークンと区別する）
+pub const API_TOKEN_PREFIX: &str = "nkt_";
+pub const API_TOKEN_NAME_MAX_LEN: usize = 64;
+
+/// API トークンで許可する操作の範囲
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenScope {
+    /// 参照のみ（GET）
+    Read,
+    /// 参照と実行の開始・操作
+    Run,
+    /// タイマー・スケジュールなどの編集を含むすべて（トークンの管理を除く）
+    Manage,
+}
+
+impl TokenScope {
+    pub const ALL: [TokenScope; 3] = [TokenScope::Read, TokenScope::Run, TokenScope::Manage];
+}
+
+/// API トークンの情報（トークン本体は作成時にしか返さない）
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    pub scope: TokenScope,
+    /// 見分け用にトークンの先頭だけを保持する（例: nkt_1a2b3c4d）
+    pub prefix: String,
+    pub created_at: u64,
+    pub last_used_at: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scope: TokenScope,
+}
+
+impl CreateApiTokenRequest {
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let len = self.name.trim().chars().count();
+        if len == 0 || len > API_TOKEN_NAME_MAX_LEN {
+            return Err(vec![ValidationError::new(
+                "name",
+                ErrorCode::Length { min: 1, max: API_TOKEN_NAME_MAX_LEN },
+            )]);
+        }
+        Ok(())
+    }
+}
+
+/// 作成直後の応答。token はこのときだけ返す
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreatedApiToken {
+    pub token: String,
+    pub info: ApiToken,
+}
@@ -0,0 +1,562 @@
+//! タイマー実行エンジン: ブロック列を「区間」に展開し、1秒ごとの tick で進める状態機械。
+//! 実際の待機や効果音は呼び出し側（frontend / backend など）が担当し、
+//! ここでは tick / skip / pause などの操作に対して発生したイベントを返すだけにする。
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{TimerBlock, TimerConfig};
+
+/// カウントダウンブロック内のどちらの区間を実行中か
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CountdownPhase {
+    /// カウントダウン区間
+    Countdown,
+    /// インターバル待機区間
+    Interval,
+}
+
+/// 実行全体の状態
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RunStatus {
+    Running,
+    Paused,
+    Completed,
+    Aborted,
+}
+
+impl RunStatus {
+    /// 完了 or 中止済みなら true
+    pub fn is_finished(&self) -> bool {
+        matches!(self, RunStatus::Completed | RunStatus::Aborted)
+    }
+}
+
+/// 現在位置のスナップショット（表示用）
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunProgress {
+    pub block_ix: usize,
+    pub remaining_secs: u32,
+    /// カウントダウンブロック時のみ: (現在の回数 1-based, 繰り返し回数)
+    pub countdown_run: Option<(u32, u32)>,
+    /// カウントダウンブロック時のみ: 現在カウントダウン中かインターバル中か
+    pub countdown_phase: Option<CountdownPhase>,
+    /// 一時停止中を除いた経過秒数
+    pub elapsed_secs: u64,
+}
+
+/// 実行中に発生するイベント。効果音・通知などはこれを見て行う。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum RunEvent {
+    Started,
+    BlockStarted { block_ix: usize },
+    /// 1秒経過。remaining_secs は経過後の残り秒数
+    Tick { block_ix: usize, remaining_secs: u32 },
+    /// カウントダウンブロックの1回分のカウントダウンが終了
+    RepetitionEnded { block_ix: usize, run: u32, total: u32 },
+    /// カウントダウンブロックのインターバル待機が終了（次の回へ）
+    IntervalEnded { block_ix: usize, run: u32, total: u32 },
+    BlockEnded { block_ix: usize, is_last: bool },
+    Paused,
+    Resumed,
+    Completed,
+    Aborted { block_ix: usize },
+}
+
+/// 残り何秒以下から1秒ごとに tick 音を鳴らすか（タイマーで指定がないとき）
+pub const TICK_UNDER_SECS: u32 = 5;
+/// タイマーごとに指定できる tick 音の秒数の上限（0 なら tick 音を鳴らさない）
+pub const TICK_UNDER_SECS_MAX: u32 = 60;
+
+/// 展開後の1区間（待機ブロック全体 / カウントダウン1回 / インターバル1回）
+#[derive(Clone, Debug)]
+struct Segment {
+    block_ix: usize,
+    secs: u32,
+    countdown_run: Option<(u32, u32)>,
+    countdown_phase: Option<CountdownPhase>,
+}
+
+fn expand_segments(timer: &TimerConfig) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for (block_ix, block) in timer.blocks.iter().enumerate() {
+        match block {
+            TimerBlock::Wait(w) => segments.push(Segment {
+                block_ix,
+                secs: w.minutes * 60 + w.seconds,
+                countdown_run: None,
+                countdown_phase: None,
+            }),
+            TimerBlock::Countdown(c) => {
+                let repeat = c.repeat_count.max(1);
+                for run in 1..=repeat {
+                    segments.push(Segment {
+                        block_ix,
+                        secs: c.minutes * 60 + c.seconds,
+                        countdown_run: Some((run, repeat)),
+                        countdown_phase: Some(CountdownPhase::Countdown),
+                    });
+                    if run < repeat {
+                        segments.push(Segment {
+                            block_ix,
+                            secs: c.interval_minutes * 60 + c.interval_seconds,
+                            countdown_run: Some((run, repeat)),
+                            countdown_phase: Some(CountdownPhase::Interval),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    segments
+}
+
+/// タイマー1回分の実行状態
+#[derive(Clone, Debug)]
+pub struct RunEngine {
+    timer: TimerConfig,
+    segments: Vec<Segment>,
+    seg_ix: usize,
+    remaining_secs: u32,
+    elapsed_secs: u64,
+    status: RunStatus,
+}
+
+impl RunEngine {
+    pub fn new(timer: TimerConfig) -> Self {
+        let segments = expand_segments(&timer);
+        let remaining_secs = segments.first().map_or(0, |s| s.secs);
+        // ブロックのないタイマーは始めた時点で終わっている
+        let status = if segments.is_empty() { RunStatus::Completed } else { RunStatus::Running };
+        Self {
+            timer,
+            segments,
+            seg_ix: 0,
+            remaining_secs,
+            elapsed_secs: 0,
+            status,
+        }
+    }
+
+    /// 別の場所（サーバーのセッションなど）で進んでいる実行を、現在位置から再現する。
+    /// 次の tick で起きるイベントを先読みして効果音を予約するのに使う
+    pub fn from_progress(timer: TimerConfig, status: RunStatus, progress: &RunProgress) -> Self {
+        let mut engine = Self::new(timer);
+        let seg_ix = engine.segments.iter().position(|seg| {
+            seg.block_ix == progress.block_ix
+                && seg.countdown_run == progress.countdown_run
+                && seg.countdown_phase == progress.countdown_phase
+        });
+        if let Some(seg_ix) = seg_ix {
+            engine.seg_ix = seg_ix;
+            engine.remaining_secs = progress.remaining_secs;
+        }
+        // 見つからない位置（タイマーが変わったなど）からは進めない
+        engine.status = if seg_ix.is_some() || status.is_finished() { status } else { RunStatus::Aborted };
+        engine.elapsed_secs = progress.elapsed_secs;
+        engine
+    }
+
+    pub fn timer(&self) -> &TimerConfig {
+        &self.timer
+    }
+
+    pub fn status(&self) -> RunStatus {
+        self.status.clone()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status.is_finished()
+    }
+
+    pub fn progress(&self) -> RunProgress {
+        match self.segments.get(self.seg_ix) {
+            Some(seg) => RunProgress {
+                block_ix: seg.block_ix,
+                remaining_secs: self.remaining_secs,
+                countdown_run: seg.countdown_run,
+                countdown_phase: seg.countdown_phase.clone(),
+                elapsed_secs: self.elapsed_secs,
+            },
+            None => RunProgress {
+                block_ix: 0,
+                remaining_secs: 0,
+                countdown_run: None,
+                countdown_phase: None,
+                elapsed_secs: self.elapsed_secs,
+            },
+        }
+    }
+
+    /// 実行開始。最初に1回だけ呼ぶ。
+    pub fn start(&mut self) -> Vec<RunEvent> {
+        let mut events = vec![RunEvent::Started];
+        if self.segments.is_empty() {
+            self.status = RunStatus::Completed;
+            events.push(RunEvent::Completed);
+            return events;
+        }
+        events.push(RunEvent::BlockStarted { block_ix: 0 });
+        self.settle(&mut events);
+        events
+    }
+
+    /// 1秒進める。一時停止中・終了後は何もしない。
+    pub fn tick(&mut self) -> Vec<RunEvent> {
+        let mut events = Vec::new();
+        if self.status != RunStatus::Running {
+            return events;
+        }
+        self.remaining_secs = self.remaining_secs.saturating_sub(1);
+        self.elapsed_secs += 1;
+        events.push(RunEvent::Tick {
+            block_ix: self.progress().block_ix,
+            remaining_secs: self.remaining_secs,
+        });
+        self.settle(&mut events);
+        events
+    }
+
+    /// 現在の区間（待機 / カウントダウン1回 / インターバル1回）を飛ばす。
+    pub fn skip(&mut self) -> Vec<RunEvent> {
+        let mut events = Vec::new();
+        if self.is_finished() {
+            return events;
+        }
+        self.remaining_secs = 0;
+        self.settle(&mut events);
+        events
+    }
+
+    pub fn pause(&mut self) -> Vec<RunEvent> {
+        if self.status != RunStatus::Running {
+            return Vec::new();
+        }
+        self.status = RunStatus::Paused;
+        vec![RunEvent::Paused]
+    }
+
+    pub fn resume(&mut self) -> Vec<RunEvent> {
+        if self.status != RunStatus::Paused {
+            return Vec::new();
+        }
+        self.status = RunStatus::Running;
+        vec![RunEvent::Resumed]
+    }
+
+    pub fn abort(&mut self) -> Vec<RunEvent> {
+        if self.is_finished() {
+            return Vec::new();
+        }
+        self.status = RunStatus::Aborted;
+        vec![RunEvent::Aborted {
+            block_ix: self.progress().block_ix,
+        }]
+    }
+
+    /// 残り0秒の区間を終わらせて次へ進む（0秒のインターバルは即座に通過）
+    fn settle(&mut self, events: &mut Vec<RunEvent>) {
+        while !self.is_finished() && self.remaining_secs == 0 {
+            self.finish_segment(events);
+        }
+    }
+
+    fn finish_segment(&mut self, events: &mut Vec<RunEvent>) {
+        let seg = self.segments[self.seg_ix].clone();
+        if let Some((run, total)) = seg.countdown_run {
+            match seg.countdown_phase {
+                Some(CountdownPhase::Countdown) => events.push(RunEvent::RepetitionEnded {
+                    block_ix: seg.block_ix,
+                    run,
+                    total,
+                }),
+                Some(CountdownPhase::Interval) => events.push(RunEvent::IntervalEnded {
+                    block_ix: seg.block_ix,
+                    run,
+                    total,
+                }),
+                None => {}
+            }
+        }
+
+        let next_ix = self.seg_ix + 1;
+        let next = self.segments.get(next_ix).cloned();
+        let block_done = next.as_ref().is_none_or(|n| n.block_ix != seg.block_ix);
+        if block_done {
+            events.push(RunEvent::BlockEnded {
+                block_ix: seg.block_ix,
+                is_last: next.is_none(),
+            });
+        }
+
+        match next {
+            Some(n) => {
+                self.seg_ix = next_ix;
+                self.remaining_secs = n.secs;
+                if block_done {
+                    events.push(RunEvent::BlockStarted {
+                        block_ix: n.block_ix,
+                    });
+                }
+            }
+            None => {
+                self.status = RunStatus::Completed;
+                events.push(RunEvent::Completed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::{CountdownBlock, WaitBlock};
+
+    fn timer(blocks: Vec<TimerBlock>) -> TimerConfig {
+        TimerConfig {
+            id: "t1".into(),
+            name: "timer".into(),
+            blocks,
+            owner: None,
+            workspace_id: None,
+            sound_profile: None,
+            sounds: Default::default(),
+            tick_under_secs: None,
+        }
+    }
+
+    fn empty_timer() -> TimerConfig {
+        timer(Vec::new())
+    }
+
+    fn wait(seconds: u32) -> TimerBlock {
+        TimerBlock::Wait(WaitBlock {
+            name: "wait".into(),
+            minutes: 0,
+            seconds,
+            sounds: Default::default(),
+            announcement: None,
+        })
+    }
+
+    fn countdown(seconds: u32, repeat_count: u32, interval_seconds: u32) -> TimerBlock {
+        TimerBlock::Countdown(CountdownBlock {
+            name: "countdown".into(),
+            minutes: 0,
+            seconds,
+            repeat_count,
+            interval_minutes: 0,
+            interval_seconds,
+            sounds: Default::default(),
+            announcement: None,
+        })
+    }
+
+    /// 待機 2秒 → カウントダウン 2秒 × 2回（インターバル 1秒）
+    fn started() -> RunEngine {
+        let mut engine = RunEngine::new(timer(vec![wait(2), countdown(2, 2, 1)]));
+        engine.start();
+        engine
+    }
+
+    fn position(engine: &RunEngine) -> (usize, u32, Option<(u32, u32)>, Option<CountdownPhase>) {
+        let p = engine.progress();
+        (p.block_ix, p.remaining_secs, p.countdown_run, p.countdown_phase)
+    }
+
+    #[test]
+    fn empty_timer_is_finished_without_start() {
+        let mut engine = RunEngine::new(empty_timer());
+        assert!(engine.is_finished());
+        assert!(engine.tick().is_empty());
+        assert!(engine.skip().is_empty());
+    }
+
+    #[test]
+    fn empty_timer_start_completes() {
+        let mut engine = RunEngine::new(empty_timer());
+        assert_eq!(engine.start(), vec![RunEvent::Started, RunEvent::Completed]);
+        assert_eq!(engine.status(), RunStatus::Completed);
+    }
+
+    #[test]
+    fn start_begins_first_block() {
+        let mut engine = RunEngine::new(timer(vec![wait(2), countdown(2, 2, 1)]));
+        assert_eq!(engine.start(), vec![RunEvent::Started, RunEvent::BlockStarted { block_ix: 0 }]);
+        assert_eq!(engine.status(), RunStatus::Running);
+        assert_eq!(position(&engine), (0, 2, None, None));
+    }
+
+    #[test]
+    fn tick_counts_down_and_moves_to_next_block() {
+        let mut engine = started();
+        assert_eq!(engine.tick(), vec![RunEvent::Tick { block_ix: 0, remaining_secs: 1 }]);
+        assert_eq!(
+            engine.tick(),
+            vec![
+                RunEvent::Tick { block_ix: 0, remaining_secs: 0 },
+                RunEvent::BlockEnded { block_ix: 0, is_last: false },
+                RunEvent::BlockStarted { block_ix: 1 },
+            ]
+        );
+        assert_eq!(position(&engine), (1, 2, Some((1, 2)), Some(CountdownPhase::Countdown)));
+        assert_eq!(engine.progress().elapsed_secs, 2);
+    }
+
+    #[test]
+    fn countdown_repeats_with_interval_and_completes() {
+        let mut engine = started();
+        engine.tick();
+        engine.tick();
+
+        engine.tick();
+        assert_eq!(
+            engine.tick(),
+            vec![
+                RunEvent::Tick { block_ix: 1, remaining_secs: 0 },
+                RunEvent::RepetitionEnded { block_ix: 1, run: 1, total: 2 },
+            ]
+        );
+        assert_eq!(position(&engine), (1, 1, Some((1, 2)), Some(CountdownPhase::Interval)));
+
+        assert_eq!(
+            engine.tick(),
+            vec![
+                RunEvent::Tick { block_ix: 1, remaining_secs: 0 },
+                RunEvent::IntervalEnded { block_ix: 1, run: 1, total: 2 },
+            ]
+        );
+        assert_eq!(position(&engine), (1, 2, Some((2, 2)), Some(CountdownPhase::Countdown)));
+
+        engine.tick();
+        assert_eq!(
+            engine.tick(),
+            vec![
+                RunEvent::Tick { block_ix: 1, remaining_secs: 0 },
+                RunEvent::RepetitionEnded { block_ix: 1, run: 2, total: 2 },
+                RunEvent::BlockEnded { block_ix: 1, is_last: true },
+                RunEvent::Completed,
+            ]
+        );
+        assert_eq!(engine.status(), RunStatus::Completed);
+        assert_eq!(engine.progress().elapsed_secs, 7);
+        assert!(engine.tick().is_empty());
+    }
+
+    #[test]
+    fn skip_ends_current_segment_without_elapsing() {
+        let mut engine = started();
+        assert_eq!(
+            engine.skip(),
+            vec![
+                RunEvent::BlockEnded { block_ix: 0, is_last: false },
+                RunEvent::BlockStarted { block_ix: 1 },
+            ]
+        );
+        assert_eq!(
+            engine.skip(),
+            vec![RunEvent::RepetitionEnded { block_ix: 1, run: 1, total: 2 }]
+        );
+        assert_eq!(position(&engine), (1, 1, Some((1, 2)), Some(CountdownPhase::Interval)));
+        assert_eq!(engine.progress().elapsed_secs, 0);
+    }
+
+    #[test]
+    fn pause_stops_ticks_until_resume() {
+        let mut engine = started();
+        assert_eq!(engine.pause(), vec![RunEvent::Paused]);
+        assert_eq!(engine.status(), RunStatus::Paused);
+        assert!(engine.pause().is_empty());
+        assert!(engine.tick().is_empty());
+        assert_eq!(position(&engine), (0, 2, None, None));
+
+        assert_eq!(engine.resume(), vec![RunEvent::Resumed]);
+        assert!(engine.resume().is_empty());
+        assert_eq!(engine.tick(), vec![RunEvent::Tick { block_ix: 0, remaining_secs: 1 }]);
+    }
+
+    #[test]
+    fn resume_without_pause_does_nothing() {
+        let mut engine = started();
+        assert!(engine.resume().is_empty());
+        assert_eq!(engine.status(), RunStatus::Running);
+    }
+
+    #[test]
+    fn abort_finishes_run_at_current_block() {
+        let mut engine = started();
+        engine.skip();
+        assert_eq!(engine.abort(), vec![RunEvent::Aborted { block_ix: 1 }]);
+        assert_eq!(engine.status(), RunStatus::Aborted);
+        assert!(engine.abort().is_empty());
+        assert!(engine.tick().is_empty());
+        assert!(engine.skip().is_empty());
+        assert!(engine.pause().is_empty());
+    }
+
+    #[test]
+    fn zero_second_segments_pass_immediately() {
+        let mut engine = RunEngine::new(timer(vec![wait(0), countdown(1, 2, 0)]));
+        assert_eq!(
+            engine.start(),
+            vec![
+                RunEvent::Started,
+                RunEvent::BlockStarted { block_ix: 0 },
+                RunEvent::BlockEnded { block_ix: 0, is_last: false },
+                RunEvent::BlockStarted { block_ix: 1 },
+            ]
+        );
+        assert_eq!(
+            engine.tick(),
+            vec![
+                RunEvent::Tick { block_ix: 1, remaining_secs: 0 },
+                RunEvent::RepetitionEnded { block_ix: 1, run: 1, total: 2 },
+                RunEvent::IntervalEnded { block_ix: 1, run: 1, total: 2 },
+            ]
+        );
+        assert_eq!(position(&engine), (1, 1, Some((2, 2)), Some(CountdownPhase::Countdown)));
+        assert_eq!(
+            engine.tick(),
+            vec![
+                RunEvent::Tick { block_ix: 1, remaining_secs: 0 },
+                RunEvent::RepetitionEnded { block_ix: 1, run: 2, total: 2 },
+                RunEvent::BlockEnded { block_ix: 1, is_last: true },
+                RunEvent::Completed,
+            ]
+        );
+    }
+
+    #[test]
+    fn from_progress_resumes_at_same_position() {
+        let mut engine = started();
+        engine.tick();
+        engine.tick();
+        engine.tick();
+        let mut copy = RunEngine::from_progress(engine.timer().clone(), engine.status(), &engine.progress());
+        assert_eq!(copy.progress(), engine.progress());
+        assert_eq!(copy.status(), RunStatus::Running);
+        for _ in 0..4 {
+            assert_eq!(copy.tick(), engine.tick());
+        }
+        assert!(copy.is_finished());
+    }
+
+    #[test]
+    fn from_progress_keeps_paused_status() {
+        let mut engine = started();
+        engine.pause();
+        let copy = RunEngine::from_progress(engine.timer().clone(), engine.status(), &engine.progress());
+        assert_eq!(copy.status(), RunStatus::Paused);
+    }
+
+    #[test]
+    fn from_progress_at_unknown_position_is_aborted() {
+        let engine = started();
+        let mut progress = engine.progress();
+        progress.block_ix = 5;
+        let mut copy = RunEngine::from_progress(engine.timer().clone(), RunStatus::Running, &progress);
+        assert_eq!(copy.status(), RunStatus::Aborted);
+        assert!(copy.tick().is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+//! 入力エラー・API エラーの種類。
+//! `ValidationError.message` はサーバーが日本語で埋める既定の文言で、画面では code から利用者の言語で組み立て直す。
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Locale;
+use crate::model::ValidationError;
+
+/// 見つからなかったもの
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Resource {
+    Timer,
+    Schedule,
+    Webhook,
+    RunSession,
+    ApiToken,
+    ShareLink,
+    Workspace,
+    User,
+    Member,
+    SoundProfile,
+    Sound,
+    Api,
+}
+
+/// エラーの種類と文言に埋め込む値
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ErrorCode {
+    Required,
+    TooShort { min: usize },
+    TooLong { max: usize },
+    Length { min: usize, max: usize },
+    OutOfRange { min: i64, max: i64 },
+    /// ブロックの値が範囲外。block は 0 始まり
+    BlockOutOfRange { block: usize, min: i64, max: i64 },
+    InvalidChar { ch: char },
+    /// 形式が不正。detail はタイムゾーン名やパーサーのエラーなど
+    InvalidFormat { detail: Option<String> },
+    InvalidUsername { max: usize },
+    UrlScheme,
+    TestEventNotAllowed,
+    DuplicateName,
+    NotFound { resource: Resource },
+    ShareLinkUnavailable,
+    Unauthenticated,
+    LoginFailed,
+    /// ログインに続けて失敗したので、しばらく受け付けない
+    TooManyLoginAttempts { retry_after_secs: u64 },
+    /// API トークンのスコープ外
+    TokenScope,
+    /// ワークスペースでの役割が足りない
+    PermissionDenied,
+    LastOwner,
+    WorkspaceNotEmpty,
+    SameDestination,
+    /// アップロードしたファイルが大きすぎる
+    FileTooLarge { max_bytes: usize },
+    UnsupportedFileType,
+    SaveFailed { detail: String },
+    /// 古いサーバーなど code のない応答。message をそのまま出す
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+impl ValidationError {
+    /// code から既定（日本語）の message を埋めて作る
+    pub fn new(field: impl Into<String>, code: ErrorCode) -> Self {
+        let field = field.into();
+        let message = code.render(&field, Locale::Ja);
+        Self { field, code, message }
+    }
+
+    /// 利用者の言語での文言
+    pub fn render(&self, locale: Locale) -> String {
+        match self.code {
+            ErrorCode::Other => self.message.clone(),
+            _ => self.code.render(&self.field, locale),
+        }
+    }
+
+    /// `blocks[2].seconds` のようなブロックの項目なら (2, "seconds")
+    pub fn block_field(&self) -> Option<(usize, &str)> {
+        block_field(&self.field)
+    }
+}
+
+fn block_field(field: &str) -> Option<(usize, &str)> {
+    let rest = field.strip_prefix("blocks[")?;
+    let (index, name) = rest.split_once("].")?;
+    Some((index.parse().ok()?, name))
+}
+
+fn resource_name(resource: Resource, locale: Locale) -> &'static str {
+    match (resource, locale) {
+        (Resource::Timer, Locale::Ja) => "タイマー",
+        (Resource::Timer, Locale::En) => "Timer",
+        (Resource::Schedule, Locale::Ja) => "スケジュール",
+        (Resource::Schedule, Locale::En) => "Schedule",
+        (Resource::Webhook, _) => "Webhook",
+        (Resource::RunSession, Locale::Ja) => "実行セッション",
+        (Resource::RunSession, Locale::En) => "Run",
+        (Resource::ApiToken, Locale::Ja) => "トークン",
+        (Resource::ApiToken, Locale::En) => "Token",
+        (Resource::ShareLink, Locale::Ja) => "共有リンク",
+        (Resource::ShareLink, Locale::En) => "Share link",
+        (Resource::Workspace, Locale::Ja) => "ワークスペース",
+        (Resource::Workspace, Locale::En) => "Workspace",
+        (Resource::User, Locale::Ja) => "ユーザー",
+        (Resource::User, Locale::En) => "User",
+        (Resource::Member, Locale::Ja) => "メンバー",
+        (Resource::Member, Locale::En) => "Member",
+        (Resource::SoundProfile, Locale::Ja) => "効果音プロファイル",
+        (Resource::SoundProfile, Locale::En) => "Sound profile",
+        (Resource::Sound, Locale::Ja) => "効果音",
+        (Resource::Sound, Locale::En) => "Sound",
+        (Resource::Api, _) => "API",
+    }
+}
+
+/// 項目名。ブロックの項目は「ブロック3の秒」のように番号を付ける
+pub fn field_label(field: &str, locale: Locale) -> String {
+    if let Some((index, name)) = block_field(field) {
+        return match locale {
+            Locale::Ja => format!("ブロック{}の{}", index + 1, base_label(name, locale)),
+            Locale::En => format!("Block {} {}", index + 1, base_label(name, locale).to_lowercase()),
+        };
+    }
+    base_label(field, locale).to_string()
+}
+
+fn base_label(field: &str, locale: Locale) -> &str {
+    let ja = locale == Locale::Ja;
+    match field {
+        "name" => if ja { "名前" } else { "Name" },
+        "username" => if ja { "ユーザー名" } else { "Username" },
+        "password" => if ja { "パスワード" } else { "Password" },
+        "timer_id" => if ja { "タイマー" } else { "Timer" },
+        "timezone" | "tz" => if ja { "タイムゾーン" } else { "Time zone" },
+        "blocks" => if ja { "ブロック" } else { "Blocks" },
+        "minutes" => if ja { "分" } else { "Minutes" },
+        "seconds" => if ja { "秒" } else { "Seconds" },
+        "repeat_count" => if ja { "繰り返し回数" } else { "Repeat count" },
+        "interval_minutes" => if ja { "インターバル分" } else { "Interval minutes" },
+        "interval_seconds" => if ja { "インターバル秒" } else { "Interval seconds" },
+        "announcement" => if ja { "読み上げ文" } else { "Announcement" },
+        "rule.days" => if ja { "曜日" } else { "Days" },
+        "rule.hour" => if ja { "時" } else { "Hour" },
+        "rule.minute" => if ja { "分" } else { "Minute" },
+        "rule.expression" => if ja { "cron 式" } else { "Cron expression" },
+        "command" => if ja { "コマンド" } else { "Command" },
+        "event" | "events" => if ja { "イベント" } else { "Event" },
+        "timeout_secs" => if ja { "タイムアウト秒" } else { "Timeout (seconds)" },
+        "url" => "URL",
+        "expires_in_days" => if ja { "有効期限（日）" } else { "Expiry (days)" },
+        "role" => if ja { "役割" } else { "Role" },
+        "workspace_id" => if ja { "移動先" } else { "Destination" },
+        "tick_under_secs" => if ja { "tick 音の秒数" } else { "Tick seconds" },
+        "sound_profile" => if ja { "効果音プロファイル" } else { "Sound profile" },
+        "file" => if ja { "ファイル" } else { "File" },
+        other => other,
+    }
+}
+
+impl ErrorCode {
+    /// field の項目についての文言
+    pub fn render(&self, field: &str, locale: Locale) -> String {
+        let label = field_label(field, locale);
+        match locale {
+            Locale::Ja => self.render_ja(field, &label),
+            Locale::En => self.render_en(field, &label),
+        }
+    }
+
+    fn render_ja(&self, field: &str, label: &str) -> String {
+        match self {
+            ErrorCode::Required => format!("{}は必須です", label),
+            ErrorCode::TooShort { min } => format!("{}は{}文字以上にしてください", label, min),
+            ErrorCode::TooLong { max } => format!("{}は{}文字以内にしてください", label, max),
+            ErrorCode::Length { min, max } => format!("{}は{}〜{}文字で入力してください", label, min, max),
+            ErrorCode::OutOfRange { min, max } => format!("{}は{}〜{}の範囲で入力してください", label, min, max),
+            ErrorCode::BlockOutOfRange { block, min, max } => {
+                let name = block_field(field).map_or(field, |(_, name)| name);
+                format!(
+                    "ブロック{}の{}は{}〜{}の範囲で入力してください",
+                    block + 1,
+                    base_label(name, Locale::Ja),
+                    min,
+                    max
+                )
+            }
+            ErrorCode::InvalidChar { ch } => format!("{}に無効な文字が含まれています: '{}'", label, ch),
+            ErrorCode::InvalidFormat { detail: Some(detail) } => format!("{}が不正です: {}", label, detail),
+            ErrorCode::InvalidFormat { detail: None } => format!("{}が不正です", label),
+            ErrorCode::InvalidUsername { max } => {
+                format!("ユーザー名は英数字と _ - . で1〜{}文字にしてください", max)
+            }
+            ErrorCode::UrlScheme => "URL は http:// または https:// で始めてください".into(),
+            ErrorCode::TestEventNotAllowed => "Test イベントは登録できません".into(),
+            ErrorCode::DuplicateName => "同じ名前のタイマーが既に存在します".into(),
+            ErrorCode::NotFound { resource } => format!("{}が見つかりません", resource_name(*resource, Locale::Ja)),
+            ErrorCode::ShareLinkUnavailable => "共有リンクが見つからないか、期限が切れています".into(),
+            ErrorCode::Unauthenticated => "ログインが必要です".into(),
+            ErrorCode::LoginFailed => "ユーザー名またはパスワードが違います".into(),
+            ErrorCode::TooManyLoginAttempts { retry_after_secs } => {
+                format!("ログインの失敗が続いています。{}秒後にもう一度お試しください", retry_after_secs)
+            }
+            ErrorCode::TokenScope => "このトークンでは実行できない操作です".into(),
+            ErrorCode::PermissionDenied => "この操作を行う権限がありません".into(),
+            ErrorCode::LastOwner => "オーナーが1人以上必要です".into(),
+            ErrorCode::WorkspaceNotEmpty => "タイマーが残っています。先に移動するか削除してください".into(),
+            ErrorCode::SameDestination => "移動先が現在の場所と同じです".into(),
+            ErrorCode::FileTooLarge { max_bytes } => {
+                format!("{}は{}KB以下にしてください", label, max_bytes / 1024)
+            }
+            ErrorCode::UnsupportedFileType => {
+                "対応していない形式です（MP3・WAV・OGG・WebM・M4A を選んでください）".into()
+            }
+            ErrorCode::SaveFailed { detail } => format!("保存に失敗しました: {}", detail),
+            ErrorCode::Other => format!("{}が不正です", label),
+        }
+    }
+
+    fn render_en(&self, field: &str, label: &str) -> String {
+        match self {
+            ErrorCode::Required => format!("{} is required", label),
+            ErrorCode::TooShort { min } => format!("{} must be at least {} characters", label, min),
+            ErrorCode::TooLong { max } => format!("{} must be at most {} characters", label, max),
+            ErrorCode::Length { min, max } => format!("{} must be {}-{} characters", label, min, max),
+            ErrorCode::OutOfRange { min, max } => format!("{} must be between {} and {}", label, min, max),
+            ErrorCode::BlockOutOfRange { block, min, max } => {
+                let name = block_field(field).map_or(field, |(_, name)| name);
+                format!(
+                    "Block {}: {} must be between {} and {}",
+                    block + 1,
+                    base_label(name, Locale::En).to_lowercase(),
+                    min,
+                    max
+                )
+            }
+            ErrorCode::InvalidChar { ch } => format!("{} contains an invalid character: '{}'", label, ch),
+            ErrorCode::InvalidFormat { detail: Some(detail) } => format!("{} is invalid: {}", label, detail),
+            ErrorCode::InvalidFormat { detail: None } => format!("{} is invalid", label),
+            ErrorCode::InvalidUsername { max } => {
+                format!("Username must be 1-{} characters of letters, digits, _ - .", max)
+            }
+            ErrorCode::UrlScheme => "URL must start with http:// or https://".into(),
+            ErrorCode::TestEventNotAllowed => "The Test event cannot be registered".into(),
+            ErrorCode::DuplicateName => "A timer with the same name already exists".into(),
+            ErrorCode::NotFound { resource } => format!("{} not found", resource_name(*resource, Locale::En)),
+            ErrorCode::ShareLinkUnavailable => "This share link does not exist or has expired".into(),
+            ErrorCode::Unauthenticated => "Please log in".into(),
+            ErrorCode::LoginFailed => "Incorrect username or password".into(),
+            ErrorCode::TooManyLoginAttempts { retry_after_secs } => {
+                format!("Too many failed logins. Try again in {} seconds", retry_after_secs)
+            }
+            ErrorCode::TokenScope => "This token cannot perform this operation".into(),
+            ErrorCode::PermissionDenied => "You do not have permission to do this".into(),
+            ErrorCode::LastOwner => "A workspace needs at least one owner".into(),
+            ErrorCode::WorkspaceNotEmpty => "The workspace still has timers. Move or delete them first".into(),
+            ErrorCode::SameDestination => "The destination is the same as the current location".into(),
+            ErrorCode::FileTooLarge { max_bytes } => {
+                format!("{} must be {} KB or smaller", label, max_bytes / 1024)
+            }
+            ErrorCode::UnsupportedFileType => {
+                "Unsupported file type (choose MP3, WAV, OGG, WebM or M4A)".into()
+            }
+            ErrorCode::SaveFailed { detail } => format!("Failed to save: {}", detail),
+            ErrorCode::Other => format!("{} is invalid", label),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! 実行履歴と統計のやり取り用の型
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::TimerConfig;
+
+/// 実行の終わり方
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RunOutcome {
+    Completed,
+    Aborted,
+}
+
+/// 1回分の実行記録
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// 実行セッション ID
+    pub id: String,
+    pub timer_id: String,
+    /// 実行時点のタイマー設定（後で編集・削除されても残す）
+    pub timer: TimerConfig,
+    /// 開始・終了時刻（UNIX 秒）
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub outcome: RunOutcome,
+    /// 中止時のみ: 中止したブロック
+    pub aborted_block_ix: Option<usize>,
+    /// 中止時のみ: 中止したカウントダウンの (回数, 繰り返し回数)
+    pub aborted_countdown_run: Option<(u32, u32)>,
+    /// 一時停止中を除いた実行秒数
+    pub active_secs: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct RunHistoryFile {
+    pub runs: Vec<RunRecord>,
+}
+
+/// タイマーごとの集計
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimerRunStats {
+    pub timer_id: String,
+    /// 最後に実行したときのタイマー名
+    pub timer_name: String,
+    pub runs: u32,
+    pub completed: u32,
+    pub aborted: u32,
+    pub total_active_secs: u64,
+    pub last_run_at: u64,
+}
+
+/// 日・週ごとの集計
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeriodRunStats {
+    /// 期間の開始日 (YYYY-MM-DD)。週の場合は月曜日。
+    pub start: String,
+    pub runs: u32,
+    pub completed: u32,
+    pub total_active_secs: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct RunStats {
+    pub total_runs: u32,
+    pub completed_runs: u32,
+    pub aborted_runs: u32,
+    pub total_active_secs: u64,
+    /// 今日（または昨日）まで連続して完了実行がある日数
+    pub current_streak_days: u32,
+    pub longest_streak_days: u32,
+    pub per_timer: Vec<TimerRunStats>,
+    /// 実行のあった日のみ（古い順）
+    pub per_day: Vec<PeriodRunStats>,
+    /// 実行のあった週のみ（古い順）
+    pub per_week: Vec<PeriodRunStats>,
+}
@@ -0,0 +1,101 @@
+//! 実行イベントでサーバー上のコマンドを起動するフック
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ErrorCode;
+use crate::model::ValidationError;
+use crate::webhook::WebhookEvent;
+
+fn default_timeout_secs() -> u32 {
+    10
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// コマンドフック。イベントの種類は Webhook と共通。
+///
+/// `args` には次のテンプレート変数を書ける（シェルは経由しない）:
+/// `{event}` `{session_id}` `{timer_id}` `{timer_name}` `{block_index}` `{block_name}`
+/// `{repetition}` `{repetitions}`。該当する値がない場合は空文字になる。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommandHook {
+    /// 省略時は読み込み時に `hook-<番号>` が振られる
+    #[serde(default)]
+    pub id: String,
+    pub event: WebhookEvent,
+    /// 対象タイマー。None なら全タイマー
+    #[serde(default)]
+    pub timer_id: Option<String>,
+    /// 実行ファイル（PATH から検索される）
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// これを超えたらプロセスを終了させる
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CommandHookFile {
+    pub hooks: Vec<CommandHook>,
+}
+
+/// コマンドの実行結果
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommandHookExecution {
+    pub id: String,
+    pub hook_id: String,
+    pub event: WebhookEvent,
+    /// 変数を展開した後のコマンドライン（表示用）
+    pub command_line: String,
+    /// 開始時刻（UNIX 秒）
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    /// 起動できなかった場合の理由
+    pub error: Option<String>,
+}
+
+impl CommandHookExecution {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+impl CommandHook {
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        if self.command.trim().is_empty() {
+            errors.push(ValidationError::new("command", ErrorCode::Required));
+        }
+        if self.event == WebhookEvent::Test {
+            errors.push(ValidationError::new("event", ErrorCode::TestEventNotAllowed));
+        }
+        if self.timeout_secs == 0 || self.timeout_secs > 600 {
+            errors.push(ValidationError::new(
+                "timeout_secs",
+                ErrorCode::OutOfRange { min: 1, max: 600 },
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn accepts(&self, event: WebhookEvent, timer_id: &str) -> bool {
+        self.enabled
+            && self.event == event
+            && self.timer_id.as_deref().is_none_or(|id| id == timer_id)
+    }
+}
@@ -1,2 +1,30 @@
+pub mod auth;
+pub mod engine;
+pub mod error;
+pub mod history;
+pub mod hook;
+pub mod i18n;
 pub mod model;
+pub mod policy;
+pub mod run;
+pub mod schedule;
+pub mod share;
+pub mod sound;
+pub mod speech;
+pub mod webhook;
+pub mod workspace;
+pub use auth::*;
+pub use engine::*;
+pub use error::*;
+pub use history::*;
+pub use hook::*;
+pub use i18n::*;
 pub use model::*;
+pub use policy::*;
+pub use run::*;
+pub use schedule::*;
+pub use share::*;
+pub use sound::*;
+pub use speech::*;
+pub use webhook::*;
+pub use workspace::*;
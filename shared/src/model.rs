@@ -1,10 +1,31 @@
 use serde::{Deserialize, Serialize};
 
+use crate::engine::{TICK_UNDER_SECS, TICK_UNDER_SECS_MAX};
+use crate::error::ErrorCode;
+use crate::policy::ValidationPolicy;
+use crate::sound::SoundSet;
+use crate::speech::ANNOUNCEMENT_MAX_LEN;
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct TimerConfig {
     pub id: String,
     pub name: String,
     pub blocks: Vec<TimerBlock>,
+    /// 所有ユーザーの ID。サーバーが設定する（None は認証導入前のタイマーで、管理者のみ扱える）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// 所属するワークスペースの ID。None なら owner の個人タイマー
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+    /// 使う効果音プロファイルの ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sound_profile: Option<String>,
+    /// タイマー全体の効果音（プロファイルより優先）
+    #[serde(default, skip_serializing_if = "SoundSet::is_empty")]
+    pub sounds: SoundSet,
+    /// 残り何秒以下から tick 音を鳴らすか。None なら TICK_UNDER_SECS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tick_under_secs: Option<u32>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -14,11 +35,71 @@ pub enum TimerBlock {
     Countdown(CountdownBlock),
 }
 
+impl TimerBlock {
+    pub fn name(&self) -> &str {
+        match self {
+            TimerBlock::Wait(w) => w.name.as_str(),
+            TimerBlock::Countdown(c) => c.name.as_str(),
+        }
+    }
+
+    /// ブロックの効果音（タイマー・プロファイルより優先）
+    pub fn sounds(&self) -> &SoundSet {
+        match self {
+            TimerBlock::Wait(w) => &w.sounds,
+            TimerBlock::Countdown(c) => &c.sounds,
+        }
+    }
+
+    /// ブロックの読み上げ文（開始時と各回の開始時に読み上げるテンプレートの代わり）
+    pub fn announcement(&self) -> Option<&str> {
+        match self {
+            TimerBlock::Wait(w) => w.announcement.as_deref(),
+            TimerBlock::Countdown(c) => c.announcement.as_deref(),
+        }
+    }
+
+    pub fn sounds_mut(&mut self) -> &mut SoundSet {
+        match self {
+            TimerBlock::Wait(w) => &mut w.sounds,
+            TimerBlock::Countdown(c) => &mut c.sounds,
+        }
+    }
+
+    /// 所要秒数（カウントダウンは繰り返しとインターバルを含む）
+    pub fn total_secs(&self) -> u64 {
+        match self {
+            TimerBlock::Wait(w) => (w.minutes * 60 + w.seconds) as u64,
+            TimerBlock::Countdown(c) => {
+                let repeat = c.repeat_count.max(1) as u64;
+                let run = (c.minutes * 60 + c.seconds) as u64;
+                let interval = (c.interval_minutes * 60 + c.interval_seconds) as u64;
+                run * repeat + interval * (repeat - 1)
+            }
+        }
+    }
+}
+
+impl TimerConfig {
+    pub fn total_secs(&self) -> u64 {
+        self.blocks.iter().map(TimerBlock::total_secs).sum()
+    }
+
+    /// 残り何秒以下から tick 音を鳴らすか
+    pub fn tick_under_secs(&self) -> u32 {
+        self.tick_under_secs.unwrap_or(TICK_UNDER_SECS)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct WaitBlock {
     pub name: String,
     pub minutes: u32,
     pub seconds: u32,
+    #[serde(default, skip_serializing_if = "SoundSet::is_empty")]
+    pub sounds: SoundSet,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announcement: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -29,6 +110,10 @@ pub struct CountdownBlock {
     pub repeat_count: u32,
     pub interval_minutes: u32,
     pub interval_seconds: u32,
+    #[serde(default, skip_serializing_if = "SoundSet::is_empty")]
+    pub sounds: SoundSet,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announcement: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -36,9 +121,13 @@ pub struct TimerConfigFile {
     pub timers: Vec<TimerConfig>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ValidationError {
     pub field: String,
+    /// エラーの種類。古いサーバーの応答など code がなければ Other
+    #[serde(default)]
+    pub code: ErrorCode,
+    /// 既定（日本語）の文言
     pub message: String,
 }
 
@@ -50,87 +139,60 @@ pub struct ApiResponse<T> {
 }
 
 impl TimerConfig {
-    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+    pub fn validate(&self, policy: &ValidationPolicy) -> Result<(), Vec<ValidationError>> {
         let mut errors = Vec::new();
 
         if self.name.is_empty() {
-            errors.push(ValidationError {
-                field: "name".into(),
-                message: "タイマー名は必須です".into(),
-            });
+            errors.push(ValidationError::new("name", ErrorCode::Required));
         }
-        if self.name.chars().count() > 64 {
-            errors.push(ValidationError {
-                field: "name".into(),
-                message: "タイマー名は64文字以内にしてください".into(),
-            });
+        if self.name.chars().count() > policy.name_max_chars {
+            let max = policy.name_max_chars;
+            errors.push(ValidationError::new("name", ErrorCode::TooLong { max }));
         }
-        for ch in self.name.chars() {
-            if !(ch.is_alphanumeric() || ch == ' ' || ch == '_' || is_japanese(ch)) {
-                errors.push(ValidationError {
-                    field: "name".into(),
-                    message: format!("タイマー名に無効な文字が含まれています: '{}'", ch),
-                });
-                break;
-            }
+        if let Some(ch) = self.name.chars().find(|ch| !policy.name_charset.allows(*ch)) {
+            errors.push(ValidationError::new("name", ErrorCode::InvalidChar { ch }));
         }
 
         if self.blocks.is_empty() {
-            errors.push(ValidationError {
-                field: "blocks".into(),
-                message: "最低1つのブロックが必要です".into(),
-            });
+            errors.push(ValidationError::new("blocks", ErrorCode::Required));
+        }
+        if self.tick_under_secs.is_some_and(|secs| secs > TICK_UNDER_SECS_MAX) {
+            let max = TICK_UNDER_SECS_MAX as i64;
+            errors.push(ValidationError::new("tick_under_secs", ErrorCode::OutOfRange { min: 0, max }));
         }
 
         for (i, block) in self.blocks.iter().enumerate() {
+            let mut check = |name: &str, value: u32, min: u32, max: u32| {
+                if value < min || value > max {
+                    errors.push(ValidationError::new(
+                        format!("blocks[{}].{}", i, name),
+                        ErrorCode::BlockOutOfRange {
+                            block: i,
+                            min: min as i64,
+                            max: max as i64,
+                        },
+                    ));
+                }
+            };
             match block {
                 TimerBlock::Wait(w) => {
-                    if w.seconds < 1 || w.seconds > 59 {
-                        errors.push(ValidationError {
-                            field: format!("blocks[{}].seconds", i),
-                            message: "待機秒は1〜59の範囲で入力してください".into(),
-                        });
-                    }
-                    if w.minutes > 1440 {
-                        errors.push(ValidationError {
-                            field: format!("blocks[{}].minutes", i),
-                            message: "待機分は0〜1440の範囲で入力してください".into(),
-                        });
-                    }
+                    check("seconds", w.seconds, 1, 59);
+                    check("minutes", w.minutes, 0, policy.max_minutes);
                 }
                 TimerBlock::Countdown(c) => {
-                    if c.seconds < 1 || c.seconds > 59 {
-                        errors.push(ValidationError {
-                            field: format!("blocks[{}].seconds", i),
-                            message: "カウントダウン秒は1〜59の範囲で入力してください".into(),
-                        });
-                    }
-                    if c.minutes > 1440 {
-                        errors.push(ValidationError {
-                            field: format!("blocks[{}].minutes", i),
-                            message: "カウントダウン分は0〜1440の範囲で入力してください".into(),
-                        });
-                    }
-                    if c.repeat_count > 100 {
-                        errors.push(ValidationError {
-                            field: format!("blocks[{}].repeat_count", i),
-                            message: "繰り返し回数は0〜100の範囲で入力してください".into(),
-                        });
-                    }
-                    if c.interval_minutes > 1440 {
-                        errors.push(ValidationError {
-                            field: format!("blocks[{}].interval_minutes", i),
-                            message: "インターバル分は0〜1440の範囲で入力してください".into(),
-                        });
-                    }
-                    if c.interval_seconds > 59 {
-                        errors.push(ValidationError {
-                            field: format!("blocks[{}].interval_seconds", i),
-                            message: "インターバル秒は0〜59の範囲で入力してください".into(),
-                        });
-                    }
+                    check("seconds", c.seconds, 1, 59);
+                    check("minutes", c.minutes, 0, policy.max_minutes);
+                    check("repeat_count", c.repeat_count, 0, policy.max_repeat_count);
+                    check("interval_minutes", c.interval_minutes, 0, policy.max_minutes);
+                    check("interval_seconds", c.interval_seconds, 0, 59);
                 }
             }
+            if block.announcement().is_some_and(|a| a.chars().count() > ANNOUNCEMENT_MAX_LEN) {
+                errors.push(ValidationError::new(
+                    format!("blocks[{}].announcement", i),
+                    ErrorCode::TooLong { max: ANNOUNCEMENT_MAX_LEN },
+                ));
+            }
         }
 
         if errors.is_empty() {
@@ -140,12 +202,3 @@ impl TimerConfig {
         }
     }
 }
-
-fn is_japanese(ch: char) -> bool {
-    matches!(ch,
-        '\u{3040}'..='\u{309F}' |
-        '\u{30A0}'..='\u{30FF}' |
-        '\u{4E00}'..='\u{9FFF}' |
-        '\u{FF00}'..='\u{FFEF}'
-    )
-}
@@ -0,0 +1,71 @@
+//! タイマーの検証ルール。サーバーの設定ファイルの [validation] で変えられ、
+//! フロントエンドは /api/validation-policy から同じルールを取得して検証する。
+
+use serde::{Deserialize, Serialize};
+
+/// タイマー名に使える文字
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NameCharset {
+    /// 制御文字以外すべて（記号や絵文字も使える）
+    #[default]
+    Printable,
+    /// 英数字・スペース・アンダースコア・日本語のみ
+    Strict,
+}
+
+impl NameCharset {
+    pub fn allows(self, ch: char) -> bool {
+        match self {
+            NameCharset::Printable => !ch.is_control(),
+            NameCharset::Strict => {
+                ch.is_alphanumeric() || ch == ' ' || ch == '_' || is_japanese(ch)
+            }
+        }
+    }
+}
+
+fn is_japanese(ch: char) -> bool {
+    matches!(ch,
+        '\u{3040}'..='\u{309F}' |
+        '\u{30A0}'..='\u{30FF}' |
+        '\u{4E00}'..='\u{9FFF}' |
+        '\u{FF00}'..='\u{FFEF}'
+    )
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationPolicy {
+    /// タイマー名の最大文字数
+    pub name_max_chars: usize,
+    pub name_charset: NameCharset,
+    /// ブロックの分（待ち時間・カウントダウン・インターバル）の上限
+    pub max_minutes: u32,
+    /// カウントダウンの繰り返し回数の上限
+    pub max_repeat_count: u32,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            name_max_chars: 64,
+            name_charset: NameCharset::default(),
+            max_minutes: 1440,
+            max_repeat_count: 100,
+        }
+    }
+}
+
+impl ValidationPolicy {
+    /// 設定値の誤り（「項目: 理由」の形）
+    pub fn check(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.name_max_chars == 0 {
+            errors.push("name_max_chars: 1 以上を指定してください".to_string());
+        }
+        if self.max_minutes == 0 {
+            errors.push("max_minutes: 1 以上を指定してください".to_string());
+        }
+        errors
+    }
+}
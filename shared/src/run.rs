@@ -0,0 +1,29 @@
+//! サーバー側で実行される「実行セッション」のやり取り用の型
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{RunEvent, RunProgress, RunStatus};
+use crate::model::TimerConfig;
+
+/// サーバーが保持する実行セッションのスナップショット
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RunSession {
+    pub id: String,
+    pub timer: TimerConfig,
+    pub status: RunStatus,
+    pub progress: RunProgress,
+    /// 開始時刻（UNIX 秒）
+    pub started_at: u64,
+}
+
+/// 状態変化の通知。SSE で各フロントエンドに配信する。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RunUpdate {
+    pub session: RunSession,
+    pub events: Vec<RunEvent>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StartRunRequest {
+    pub timer_id: String,
+}
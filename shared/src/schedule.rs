@@ -0,0 +1,115 @@
+//! タイマーの定期実行スケジュール
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ErrorCode;
+use crate::model::ValidationError;
+
+/// 曜日（月曜始まり）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduleDay {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl ScheduleDay {
+    pub const ALL: [ScheduleDay; 7] = [
+        ScheduleDay::Mon,
+        ScheduleDay::Tue,
+        ScheduleDay::Wed,
+        ScheduleDay::Thu,
+        ScheduleDay::Fri,
+        ScheduleDay::Sat,
+        ScheduleDay::Sun,
+    ];
+
+    /// 月曜日を 0 とした番号
+    pub fn index_from_monday(self) -> u32 {
+        self as u32
+    }
+}
+
+/// いつ実行するか
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ScheduleRule {
+    /// 指定曜日の指定時刻
+    Weekly {
+        days: Vec<ScheduleDay>,
+        hour: u32,
+        minute: u32,
+    },
+    /// cron 式（「分 時 日 月 曜日」の5項目、または秒付きの6〜7項目）
+    Cron { expression: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    pub timer_id: String,
+    pub rule: ScheduleRule,
+    /// IANA タイムゾーン名（例: Asia/Tokyo）
+    pub timezone: String,
+    pub enabled: bool,
+    /// 次回実行予定（UNIX 秒）。サーバーが計算して返す。
+    #[serde(default)]
+    pub next_run_at: Option<u64>,
+    /// 最後に実行を開始した時刻（UNIX 秒）
+    #[serde(default)]
+    pub last_run_at: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ScheduleFile {
+    pub schedules: Vec<Schedule>,
+}
+
+impl Schedule {
+    /// 入力値の検証。cron 式とタイムゾーンの妥当性はサーバー側で確認する。
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        if self.timer_id.is_empty() {
+            errors.push(ValidationError::new("timer_id", ErrorCode::Required));
+        }
+        if self.timezone.is_empty() {
+            errors.push(ValidationError::new("timezone", ErrorCode::Required));
+        }
+
+        match &self.rule {
+            ScheduleRule::Weekly { days, hour, minute } => {
+                if days.is_empty() {
+                    errors.push(ValidationError::new("rule.days", ErrorCode::Required));
+                }
+                if *hour > 23 {
+                    errors.push(ValidationError::new(
+                        "rule.hour",
+                        ErrorCode::OutOfRange { min: 0, max: 23 },
+                    ));
+                }
+                if *minute > 59 {
+                    errors.push(ValidationError::new(
+                        "rule.minute",
+                        ErrorCode::OutOfRange { min: 0, max: 59 },
+                    ));
+                }
+            }
+            ScheduleRule::Cron { expression } => {
+                if expression.trim().is_empty() {
+                    errors.push(ValidationError::new("rule.expression", ErrorCode::Required));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
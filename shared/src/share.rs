@@ -0,0 +1,81 @@
+//! タイマーの共有リンク（ログインなしで閲覧できる `/s/<token>`）
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ErrorCode;
+use crate::model::{TimerConfig, ValidationError};
+use crate::sound::SoundProfile;
+
+/// 有効期限に指定できる最大日数
+pub const SHARE_MAX_DAYS: u32 = 365;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShareLink {
+    pub id: String,
+    pub timer_id: String,
+    pub token: String,
+    /// 閲覧者がブラウザ内でタイマーを実行できるか
+    pub allow_run: bool,
+    pub created_at: u64,
+    /// 有効期限（UNIX 秒）。None なら無期限
+    pub expires_at: Option<u64>,
+}
+
+impl ShareLink {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|t| t <= now)
+    }
+
+    /// 閲覧用のパス
+    pub fn path(&self) -> String {
+        format!("/s/{}", self.token)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ShareLinkFile {
+    pub links: Vec<ShareLink>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateShareLinkRequest {
+    pub timer_id: String,
+    #[serde(default)]
+    pub allow_run: bool,
+    /// 何日後に期限切れにするか。None なら無期限
+    #[serde(default)]
+    pub expires_in_days: Option<u32>,
+}
+
+impl CreateShareLinkRequest {
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        if self.timer_id.is_empty() {
+            errors.push(ValidationError::new("timer_id", ErrorCode::Required));
+        }
+        if let Some(days) = self.expires_in_days {
+            if days == 0 || days > SHARE_MAX_DAYS {
+                errors.push(ValidationError::new(
+                    "expires_in_days",
+                    ErrorCode::OutOfRange { min: 1, max: SHARE_MAX_DAYS as i64 },
+                ));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// 共有リンクで閲覧したときの応答
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SharedTimer {
+    pub timer: TimerConfig,
+    pub allow_run: bool,
+    pub expires_at: Option<u64>,
+    /// タイマーが使う効果音プロファイル（閲覧者はプロファイル一覧を取得できないため同梱する）
+    #[serde(default)]
+    pub sound_profile: Option<SoundProfile>,
+}
@@ -0,0 +1,522 @@
+//! 効果音の選択。ブロック → タイマー → 効果音プロファイル → 既定の順に、最初に指定されたものを鳴らす。
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::RunEvent;
+use crate::error::ErrorCode;
+use crate::model::{TimerBlock, TimerConfig, ValidationError};
+
+pub const SOUND_PROFILE_NAME_MAX_LEN: usize = 64;
+pub const CUSTOM_SOUND_NAME_MAX_LEN: usize = 64;
+
+/// アップロードできる効果音ファイルの上限（1 MiB）
+pub const CUSTOM_SOUND_MAX_BYTES: usize = 1024 * 1024;
+
+/// アップロードできる効果音ファイルの形式（Content-Type）
+pub const CUSTOM_SOUND_TYPES: [&str; 8] = [
+    "audio/mpeg",
+    "audio/mp3",
+    "audio/wav",
+    "audio/x-wav",
+    "audio/wave",
+    "audio/ogg",
+    "audio/webm",
+    "audio/mp4",
+];
+
+/// アップロードした効果音の ID の接頭辞。SoundSet では `custom:<ID>` と書く
+pub const CUSTOM_SOUND_PREFIX: &str = "custom:";
+
+/// 何も鳴らさない効果音の ID
+pub const SILENT_SOUND: &str = "silent";
+
+/// 組み込みの効果音（`/sound/<ID>.mp3`）。SILENT_SOUND は選択肢としてだけ並べる
+pub const BUILTIN_SOUNDS: [&str; 5] =
+    ["tick", "block_end", "all_end", "countdown_blk_next", SILENT_SOUND];
+
+/// 電子音（発振器で合成する音）の ID の接頭辞。`beep:<周波数Hz>:<長さms>:<回数>:<間隔ms>` と書く
+pub const BEEP_PREFIX: &str = "beep:";
+
+/// 選択肢に並べる電子音
+pub const BEEP_PRESETS: [&str; 4] = [
+    "beep:880:120:1:0",
+    "beep:440:250:1:0",
+    "beep:1320:80:2:80",
+    "beep:660:700:1:0",
+];
+
+/// 電子音の鳴らし方。値は範囲内に収めて扱う
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BeepPattern {
+    pub frequency_hz: u32,
+    /// 1回の長さ
+    pub duration_ms: u32,
+    pub count: u32,
+    /// 2回以上鳴らすときの間隔
+    pub gap_ms: u32,
+}
+
+impl BeepPattern {
+    pub const FREQUENCY_RANGE: (u32, u32) = (100, 4000);
+    pub const DURATION_RANGE: (u32, u32) = (20, 2000);
+    pub const COUNT_RANGE: (u32, u32) = (1, 5);
+    pub const GAP_RANGE: (u32, u32) = (0, 1000);
+
+    /// `beep:` で始まる ID を読む。数が足りない・数値でない場合は None
+    pub fn parse(id: &str) -> Option<Self> {
+        let mut parts = id.strip_prefix(BEEP_PREFIX)?.split(':').map(|p| p.parse::<u32>().ok());
+        let mut next = || parts.next().flatten();
+        let pattern = BeepPattern {
+            frequency_hz: next()?,
+            duration_ms: next()?,
+            count: next()?,
+            gap_ms: next()?,
+        };
+        Some(pattern.clamped())
+    }
+
+    pub fn clamped(self) -> Self {
+        let clamp = |v: u32, (min, max): (u32, u32)| v.clamp(min, max);
+        BeepPattern {
+            frequency_hz: clamp(self.frequency_hz, Self::FREQUENCY_RANGE),
+            duration_ms: clamp(self.duration_ms, Self::DURATION_RANGE),
+            count: clamp(self.count, Self::COUNT_RANGE),
+            gap_ms: clamp(self.gap_ms, Self::GAP_RANGE),
+        }
+    }
+
+    /// SoundSet に書く ID
+    pub fn id(&self) -> String {
+        format!(
+            "{}{}:{}:{}:{}",
+            BEEP_PREFIX, self.frequency_hz, self.duration_ms, self.count, self.gap_ms
+        )
+    }
+}
+
+/// 効果音を鳴らすタイミング
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SoundEvent {
+    /// ブロックの開始
+    BlockStart,
+    /// ブロックの終了（最後のブロックは Finish）
+    BlockEnd,
+    /// 残り数秒の毎秒（何秒からかはタイマーの tick_under_secs）
+    Tick,
+    /// カウントダウンの回の切り替わり
+    Repetition,
+    /// 最後のブロックの終了
+    Finish,
+}
+
+impl SoundEvent {
+    pub const ALL: [SoundEvent; 5] = [
+        SoundEvent::BlockStart,
+        SoundEvent::BlockEnd,
+        SoundEvent::Tick,
+        SoundEvent::Repetition,
+        SoundEvent::Finish,
+    ];
+
+    /// ブロックごとに選べるタイミング（Finish はタイマー全体の設定）
+    pub const BLOCK: [SoundEvent; 4] = [
+        SoundEvent::BlockStart,
+        SoundEvent::BlockEnd,
+        SoundEvent::Tick,
+        SoundEvent::Repetition,
+    ];
+
+    /// どこにも指定がないときの効果音
+    pub fn default_sound(self) -> &'static str {
+        match self {
+            SoundEvent::BlockStart => SILENT_SOUND,
+            SoundEvent::BlockEnd => "block_end",
+            SoundEvent::Tick => "tick",
+            SoundEvent::Repetition => "countdown_blk_next",
+            SoundEvent::Finish => "all_end",
+        }
+    }
+}
+
+/// タイミングごとの効果音の ID。None は上位（タイマー・プロファイル・既定）に任せる
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundSet {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repetition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finish: Option<String>,
+}
+
+impl SoundSet {
+    pub fn get(&self, event: SoundEvent) -> Option<&str> {
+        match event {
+            SoundEvent::BlockStart => self.block_start.as_deref(),
+            SoundEvent::BlockEnd => self.block_end.as_deref(),
+            SoundEvent::Tick => self.tick.as_deref(),
+            SoundEvent::Repetition => self.repetition.as_deref(),
+            SoundEvent::Finish => self.finish.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, event: SoundEvent, sound: Option<String>) {
+        let slot = match event {
+            SoundEvent::BlockStart => &mut self.block_start,
+            SoundEvent::BlockEnd => &mut self.block_end,
+            SoundEvent::Tick => &mut self.tick,
+            SoundEvent::Repetition => &mut self.repetition,
+            SoundEvent::Finish => &mut self.finish,
+        };
+        *slot = sound;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        SoundEvent::ALL.iter().all(|e| self.get(*e).is_none())
+    }
+
+    /// 指定の効果音を使っているタイミングを「既定」に戻す。変わったら true
+    pub fn forget(&mut self, sound: &str) -> bool {
+        let mut changed = false;
+        for event in SoundEvent::ALL {
+            if self.get(event) == Some(sound) {
+                self.set(event, None);
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// タイミングごとの再生の設定
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventVolume {
+    pub enabled: bool,
+    /// 音量（0.0〜1.0）。全体の音量と掛け合わせる
+    pub volume: f32,
+}
+
+impl Default for EventVolume {
+    fn default() -> Self {
+        EventVolume { enabled: true, volume: 1.0 }
+    }
+}
+
+/// 効果音の再生の設定（利用者・端末ごと）。どの効果音を使うかはタイマー側の SoundSet で決める
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundPreferences {
+    /// 全体の音量（0.0〜1.0）
+    pub volume: f32,
+    /// 消音。音量の設定は残したまますべて鳴らさない
+    pub muted: bool,
+    pub block_start: EventVolume,
+    pub block_end: EventVolume,
+    pub tick: EventVolume,
+    pub repetition: EventVolume,
+    pub finish: EventVolume,
+}
+
+impl Default for SoundPreferences {
+    fn default() -> Self {
+        SoundPreferences {
+            volume: 1.0,
+            muted: false,
+            block_start: EventVolume::default(),
+            block_end: EventVolume::default(),
+            tick: EventVolume::default(),
+            repetition: EventVolume::default(),
+            finish: EventVolume::default(),
+        }
+    }
+}
+
+impl SoundPreferences {
+    pub fn event(&self, event: SoundEvent) -> EventVolume {
+        match event {
+            SoundEvent::BlockStart => self.block_start,
+            SoundEvent::BlockEnd => self.block_end,
+            SoundEvent::Tick => self.tick,
+            SoundEvent::Repetition => self.repetition,
+            SoundEvent::Finish => self.finish,
+        }
+    }
+
+    pub fn set_event(&mut self, event: SoundEvent, setting: EventVolume) {
+        let slot = match event {
+            SoundEvent::BlockStart => &mut self.block_start,
+            SoundEvent::BlockEnd => &mut self.block_end,
+            SoundEvent::Tick => &mut self.tick,
+            SoundEvent::Repetition => &mut self.repetition,
+            SoundEvent::Finish => &mut self.finish,
+        };
+        *slot = setting;
+    }
+
+    /// 全体の音量。消音なら 0
+    pub fn master_volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.volume.clamp(0.0, 1.0)
+        }
+    }
+
+    /// タイミングごとの音量。鳴らさないタイミングなら None
+    pub fn event_volume(&self, event: SoundEvent) -> Option<f32> {
+        let setting = self.event(event);
+        let volume = setting.volume.clamp(0.0, 1.0);
+        (setting.enabled && volume > 0.0).then_some(volume)
+    }
+}
+
+/// 名前付きの効果音の組み合わせ（例: 「静かなオフィス」「ジム（大音量）」）。
+/// サーバー全体で共有し、作成者と管理者だけが変更できる
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SoundProfile {
+    pub id: String,
+    pub name: String,
+    /// 作成したユーザーの ID。サーバーが設定する
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub sounds: SoundSet,
+}
+
+impl SoundProfile {
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let name = self.name.trim();
+        if name.is_empty() {
+            errors.push(ValidationError::new("name", ErrorCode::Required));
+        } else if name.chars().count() > SOUND_PROFILE_NAME_MAX_LEN {
+            let max = SOUND_PROFILE_NAME_MAX_LEN;
+            errors.push(ValidationError::new("name", ErrorCode::TooLong { max }));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SoundProfileFile {
+    pub profiles: Vec<SoundProfile>,
+}
+
+/// アップロードした効果音（`/sound/custom/<ID>` で配信する）。サーバー全体で共有し、
+/// 作成者と管理者だけが名前の変更・削除をできる
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomSound {
+    pub id: String,
+    pub name: String,
+    /// アップロードしたユーザーの ID
+    pub owner: String,
+    pub content_type: String,
+    /// ファイルのバイト数
+    pub size: usize,
+    pub uploaded_at: u64,
+}
+
+impl CustomSound {
+    /// SoundSet に書くときの ID（`custom:<ID>`）
+    pub fn sound_id(&self) -> String {
+        format!("{}{}", CUSTOM_SOUND_PREFIX, self.id)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CustomSoundFile {
+    pub sounds: Vec<CustomSound>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RenameCustomSoundRequest {
+    pub name: String,
+}
+
+pub fn validate_custom_sound_name(name: &str) -> Result<(), Vec<ValidationError>> {
+    let name = name.trim();
+    if name.is_empty() {
+        Err(vec![ValidationError::new("name", ErrorCode::Required)])
+    } else if name.chars().count() > CUSTOM_SOUND_NAME_MAX_LEN {
+        let max = CUSTOM_SOUND_NAME_MAX_LEN;
+        Err(vec![ValidationError::new("name", ErrorCode::TooLong { max })])
+    } else {
+        Ok(())
+    }
+}
+
+/// アップロードする効果音の検証。content_type は `audio/ogg; codecs=opus` のような引数付きでもよい
+pub fn validate_custom_sound_upload(
+    name: &str,
+    content_type: &str,
+    size: usize,
+) -> Result<(), Vec<ValidationError>> {
+    let mut errors = validate_custom_sound_name(name).err().unwrap_or_default();
+    if !CUSTOM_SOUND_TYPES.contains(&base_content_type(content_type).as_str()) {
+        errors.push(ValidationError::new("file", ErrorCode::UnsupportedFileType));
+    }
+    if size == 0 {
+        errors.push(ValidationError::new("file", ErrorCode::Required));
+    } else if size > CUSTOM_SOUND_MAX_BYTES {
+        let max_bytes = CUSTOM_SOUND_MAX_BYTES;
+        errors.push(ValidationError::new("file", ErrorCode::FileTooLarge { max_bytes }));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Content-Type から引数を除いて小文字にする（`Audio/OGG; codecs=opus` → `audio/ogg`）
+pub fn base_content_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// ファイルの先頭が Content-Type の形式のものか（拡張子や申告だけで別の形式を受け付けないため）
+pub fn matches_sound_signature(content_type: &str, bytes: &[u8]) -> bool {
+    match base_content_type(content_type).as_str() {
+        // ID3 タグか、MPEG フレームの同期ワード（先頭11ビットが1）
+        "audio/mpeg" | "audio/mp3" => {
+            bytes.starts_with(b"ID3") || (bytes.len() >= 2 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0)
+        }
+        "audio/wav" | "audio/x-wav" | "audio/wave" => {
+            bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WAVE".as_slice())
+        }
+        "audio/ogg" => bytes.starts_with(b"OggS"),
+        // EBML ヘッダー
+        "audio/webm" => bytes.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]),
+        "audio/mp4" => bytes.get(4..8) == Some(b"ftyp".as_slice()),
+        _ => false,
+    }
+}
+
+/// アップロードした効果音の ID（`custom:` を除いた部分）。UUID の文字だけを受け付ける
+pub fn custom_sound_id(id: &str) -> Option<&str> {
+    let rest = id.strip_prefix(CUSTOM_SOUND_PREFIX)?;
+    let valid = !rest.is_empty() && rest.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+    valid.then_some(rest)
+}
+
+/// 効果音の URL。組み込みは `/sound/<ID>.mp3`、アップロードしたものは `/sound/custom/<ID>`。
+/// 電子音（合成する）・無音・不明な ID は None
+pub fn sound_path(id: &str) -> Option<String> {
+    if let Some(custom) = custom_sound_id(id) {
+        return Some(format!("/sound/custom/{}", custom));
+    }
+    (id != SILENT_SOUND && BUILTIN_SOUNDS.contains(&id)).then(|| format!("/sound/{}.mp3", id))
+}
+
+/// タイマー全体とブロックの設定から指定の効果音を外す（削除した効果音の後始末）。変わったら true
+pub fn forget_sound(timer: &mut TimerConfig, sound: &str) -> bool {
+    let mut changed = timer.sounds.forget(sound);
+    for block in &mut timer.blocks {
+        changed |= block.sounds_mut().forget(sound);
+    }
+    changed
+}
+
+/// block_ix のブロックの event で鳴らす効果音の ID
+pub fn resolve_sound<'a>(
+    timer: &'a TimerConfig,
+    profile: Option<&'a SoundProfile>,
+    block_ix: usize,
+    event: SoundEvent,
+) -> &'a str {
+    timer
+        .blocks
+        .get(block_ix)
+        .and_then(|b| b.sounds().get(event))
+        .or_else(|| timer.sounds.get(event))
+        .or_else(|| profile.and_then(|p| p.sounds.get(event)))
+        .unwrap_or(event.default_sound())
+}
+
+/// 実行イベントから効果音を鳴らすタイミングを順に取り出す（ブロックの位置と組）。
+/// tick は残り tick_under_secs 秒以下のときだけ
+pub fn sound_events(events: &[RunEvent], tick_under_secs: u32) -> Vec<(usize, SoundEvent)> {
+    events
+        .iter()
+        .filter_map(|event| match *event {
+            RunEvent::BlockStarted { block_ix } => Some((block_ix, SoundEvent::BlockStart)),
+            RunEvent::Tick { block_ix, remaining_secs }
+                if (1..=tick_under_secs).contains(&remaining_secs) =>
+            {
+                Some((block_ix, SoundEvent::Tick))
+            }
+            RunEvent::RepetitionEnded { block_ix, run, total } if run < total => {
+                Some((block_ix, SoundEvent::Repetition))
+            }
+            RunEvent::IntervalEnded { block_ix, .. } => Some((block_ix, SoundEvent::Repetition)),
+            RunEvent::BlockEnded { block_ix, is_last } => {
+                Some((block_ix, if is_last { SoundEvent::Finish } else { SoundEvent::BlockEnd }))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// タイマーの実行で鳴る可能性のある効果音の ID（重複なし・無音を除く）。プリロードに使う
+pub fn used_sounds(timer: &TimerConfig, profile: Option<&SoundProfile>) -> Vec<String> {
+    let mut sounds: Vec<String> = Vec::new();
+    let last = timer.blocks.len().saturating_sub(1);
+    for (ix, block) in timer.blocks.iter().enumerate() {
+        let repeats = matches!(block, TimerBlock::Countdown(c) if c.repeat_count > 1);
+        let end = if ix == last { SoundEvent::Finish } else { SoundEvent::BlockEnd };
+        let mut events = vec![SoundEvent::BlockStart, end];
+        if timer.tick_under_secs() > 0 {
+            events.push(SoundEvent::Tick);
+        }
+        if repeats {
+            events.push(SoundEvent::Repetition);
+        }
+        for event in events {
+            let id = resolve_sound(timer, profile, ix, event);
+            if id != SILENT_SOUND && !sounds.iter().any(|s| s == id) {
+                sounds.push(id.to_string());
+            }
+        }
+    }
+    sounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sound_signature_matches_each_format() {
+        assert!(matches_sound_signature("audio/mpeg", b"ID3\x04\x00"));
+        assert!(matches_sound_signature("audio/mp3", &[0xFF, 0xFB, 0x90, 0x00]));
+        assert!(matches_sound_signature("audio/wav", b"RIFF\x24\x08\x00\x00WAVEfmt "));
+        assert!(matches_sound_signature("audio/ogg; codecs=opus", b"OggS\x00\x02"));
+        assert!(matches_sound_signature("audio/webm", &[0x1A, 0x45, 0xDF, 0xA3, 0x9F]));
+        assert!(matches_sound_signature("audio/mp4", b"\x00\x00\x00\x20ftypM4A "));
+    }
+
+    #[test]
+    fn sound_signature_rejects_mismatched_content() {
+        assert!(!matches_sound_signature("audio/mpeg", b"<html><script>"));
+        assert!(!matches_sound_signature("audio/wav", b"RIFF\x24\x08\x00\x00AVI "));
+        assert!(!matches_sound_signature("audio/ogg", b"ID3\x04\x00"));
+        assert!(!matches_sound_signature("text/html", b"OggS"));
+        assert!(!matches_sound_signature("audio/mp4", b""));
+    }
+}
@@ -0,0 +1,255 @@
+//! 読み上げ（音声合成）の文言。実行イベントとテンプレートから読み上げる文を組み立てる。
+//! 読み上げそのものは呼び出し側（ブラウザの Speech Synthesis API など）が行う。
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::RunEvent;
+use crate::i18n::Locale;
+use crate::model::{TimerBlock, TimerConfig};
+
+/// ブロックごとの読み上げ文の上限
+pub const ANNOUNCEMENT_MAX_LEN: usize = 100;
+
+/// 読み上げるタイミング
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpeechEvent {
+    /// ブロックの開始（繰り返しのあるカウントダウンは Repetition）
+    BlockStart,
+    /// カウントダウンの各回の開始
+    Repetition,
+    /// インターバルの開始
+    Interval,
+    /// 残り時間（SpeechSettings.remaining_at の秒数になったとき）
+    Remaining,
+    /// 最後のブロックの終了
+    Finish,
+}
+
+impl SpeechEvent {
+    pub const ALL: [SpeechEvent; 5] = [
+        SpeechEvent::BlockStart,
+        SpeechEvent::Repetition,
+        SpeechEvent::Interval,
+        SpeechEvent::Remaining,
+        SpeechEvent::Finish,
+    ];
+
+    /// テンプレートを指定していないときの文言。
+    /// {name} ブロック名、{duration} 区間の長さ、{run} 何回目、{total} 繰り返し回数、{remaining} 残り時間
+    pub fn default_template(self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (SpeechEvent::BlockStart, Locale::Ja) => "{name}、{duration}",
+            (SpeechEvent::BlockStart, Locale::En) => "{name}, {duration}",
+            (SpeechEvent::Repetition, Locale::Ja) => "{name}、{duration}、{run}回目",
+            (SpeechEvent::Repetition, Locale::En) => "{name}, {duration}, round {run}",
+            (SpeechEvent::Interval, Locale::Ja) => "休憩、{duration}",
+            (SpeechEvent::Interval, Locale::En) => "Rest, {duration}",
+            (SpeechEvent::Remaining, Locale::Ja) => "残り{remaining}",
+            (SpeechEvent::Remaining, Locale::En) => "{remaining} left",
+            (SpeechEvent::Finish, Locale::Ja) => "終了です",
+            (SpeechEvent::Finish, Locale::En) => "Finished",
+        }
+    }
+}
+
+/// タイミングごとの読み上げのテンプレート。None は言語ごとの既定の文言
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeechTemplates {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repetition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finish: Option<String>,
+}
+
+impl SpeechTemplates {
+    pub fn get(&self, event: SpeechEvent) -> Option<&str> {
+        match event {
+            SpeechEvent::BlockStart => self.block_start.as_deref(),
+            SpeechEvent::Repetition => self.repetition.as_deref(),
+            SpeechEvent::Interval => self.interval.as_deref(),
+            SpeechEvent::Remaining => self.remaining.as_deref(),
+            SpeechEvent::Finish => self.finish.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, event: SpeechEvent, template: Option<String>) {
+        let slot = match event {
+            SpeechEvent::BlockStart => &mut self.block_start,
+            SpeechEvent::Repetition => &mut self.repetition,
+            SpeechEvent::Interval => &mut self.interval,
+            SpeechEvent::Remaining => &mut self.remaining,
+            SpeechEvent::Finish => &mut self.finish,
+        };
+        *slot = template;
+    }
+
+    /// 指定がなければ言語ごとの既定の文言
+    pub fn template(&self, event: SpeechEvent, locale: Locale) -> &str {
+        self.get(event).unwrap_or(event.default_template(locale))
+    }
+}
+
+/// 読み上げの設定（端末ごと）
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeechSettings {
+    pub enabled: bool,
+    /// 読み上げの言語。既定の文言・時間の読み方・声の候補に使う
+    pub locale: Locale,
+    /// 声の名前（SpeechSynthesisVoice.name）。None ならブラウザの既定の声
+    pub voice: Option<String>,
+    /// 話す速さ（0.5〜2.0）
+    pub rate: f32,
+    /// 残り時間を読み上げる秒数（例: 30秒前と10秒前）
+    pub remaining_at: Vec<u32>,
+    pub templates: SpeechTemplates,
+}
+
+impl SpeechSettings {
+    pub const RATE_RANGE: (f32, f32) = (0.5, 2.0);
+
+    pub fn for_locale(locale: Locale) -> Self {
+        SpeechSettings {
+            enabled: false,
+            locale,
+            voice: None,
+            rate: 1.0,
+            remaining_at: vec![10],
+            templates: SpeechTemplates::default(),
+        }
+    }
+}
+
+impl Default for SpeechSettings {
+    fn default() -> Self {
+        Self::for_locale(Locale::default())
+    }
+}
+
+/// 読み上げ用の時間（例: 90秒 → 「1分30秒」 / "1 minute 30 seconds"）
+pub fn spoken_duration(secs: u32, locale: Locale) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    let parts: Vec<String> = match locale {
+        Locale::Ja => [(hours, "時間"), (minutes, "分"), (seconds, "秒")]
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, unit)| format!("{}{}", n, unit))
+            .collect(),
+        Locale::En => [(hours, "hour"), (minutes, "minute"), (seconds, "second")]
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, unit)| format!("{} {}{}", n, unit, if *n == 1 { "" } else { "s" }))
+            .collect(),
+    };
+    if parts.is_empty() {
+        return match locale {
+            Locale::Ja => "0秒".to_string(),
+            Locale::En => "0 seconds".to_string(),
+        };
+    }
+    parts.join(if locale == Locale::Ja { "" } else { " " })
+}
+
+/// テンプレートに埋め込む値
+struct Vars<'a> {
+    name: &'a str,
+    duration: u32,
+    run: u32,
+    total: u32,
+    remaining: u32,
+}
+
+fn render(template: &str, vars: &Vars, locale: Locale) -> String {
+    template
+        .replace("{name}", vars.name)
+        .replace("{duration}", &spoken_duration(vars.duration, locale))
+        .replace("{run}", &vars.run.to_string())
+        .replace("{total}", &vars.total.to_string())
+        .replace("{remaining}", &spoken_duration(vars.remaining, locale))
+}
+
+/// 1回分（待機ブロックは全体）の秒数・インターバルの秒数・繰り返し回数
+fn block_timing(block: &TimerBlock) -> (u32, u32, u32) {
+    match block {
+        TimerBlock::Wait(w) => (w.minutes * 60 + w.seconds, 0, 1),
+        TimerBlock::Countdown(c) => (
+            c.minutes * 60 + c.seconds,
+            c.interval_minutes * 60 + c.interval_seconds,
+            c.repeat_count.max(1),
+        ),
+    }
+}
+
+/// 実行イベントから読み上げる文を順に組み立てる。
+/// ブロックの読み上げ文があれば、そのブロックの開始と各回の開始はそれを使う
+pub fn announcements(timer: &TimerConfig, settings: &SpeechSettings, events: &[RunEvent]) -> Vec<String> {
+    let locale = settings.locale;
+    let templates = &settings.templates;
+    let mut out = Vec::new();
+    for event in events {
+        let block_ix = match *event {
+            RunEvent::BlockStarted { block_ix }
+            | RunEvent::Tick { block_ix, .. }
+            | RunEvent::RepetitionEnded { block_ix, .. }
+            | RunEvent::IntervalEnded { block_ix, .. }
+            | RunEvent::BlockEnded { block_ix, .. } => block_ix,
+            _ => continue,
+        };
+        let Some(block) = timer.blocks.get(block_ix) else {
+            continue;
+        };
+        let (run_secs, interval_secs, total) = block_timing(block);
+        let mut vars = Vars { name: block.name(), duration: run_secs, run: 1, total, remaining: 0 };
+        let start_template = |speech_event: SpeechEvent| {
+            block
+                .announcement()
+                .filter(|text| !text.trim().is_empty())
+                .unwrap_or_else(|| templates.template(speech_event, locale))
+        };
+        let template = match *event {
+            RunEvent::BlockStarted { .. } => {
+                start_template(if total > 1 { SpeechEvent::Repetition } else { SpeechEvent::BlockStart })
+            }
+            RunEvent::IntervalEnded { run, .. } => {
+                vars.run = run + 1;
+                start_template(SpeechEvent::Repetition)
+            }
+            RunEvent::RepetitionEnded { run, total, .. } if run < total && interval_secs > 0 => {
+                vars.duration = interval_secs;
+                templates.template(SpeechEvent::Interval, locale)
+            }
+            RunEvent::Tick { remaining_secs, .. }
+                if remaining_secs > 0 && settings.remaining_at.contains(&remaining_secs) =>
+            {
+                vars.remaining = remaining_secs;
+                templates.template(SpeechEvent::Remaining, locale)
+            }
+            RunEvent::BlockEnded { is_last: true, .. } => templates.template(SpeechEvent::Finish, locale),
+            _ => continue,
+        };
+        let text = render(template, &vars, locale);
+        if !text.trim().is_empty() {
+            out.push(text);
+        }
+    }
+    out
+}
+
+/// 設定画面で試しに読み上げる文（ブロック名や時間は例の値）
+pub fn sample_announcement(settings: &SpeechSettings, event: SpeechEvent) -> String {
+    let locale = settings.locale;
+    let name = match locale {
+        Locale::Ja => "スクワット",
+        Locale::En => "Squats",
+    };
+    let duration = if event == SpeechEvent::Interval { 20 } else { 40 };
+    let vars = Vars { name, duration, run: 3, total: 5, remaining: 10 };
+    render(settings.templates.template(event, locale), &vars, locale)
+}
@@ -0,0 +1,154 @@
+//! 実行イベントを外部に HTTP で通知する Webhook
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ErrorCode;
+use crate::model::ValidationError;
+
+/// 通知するイベントの種類
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    RunStarted,
+    BlockStarted,
+    RepetitionEnded,
+    BlockEnded,
+    Completed,
+    Aborted,
+    /// 「テスト送信」ボタンによる送信
+    Test,
+}
+
+impl WebhookEvent {
+    /// 登録時に選択できるイベント（Test 以外）
+    pub const SUBSCRIBABLE: [WebhookEvent; 6] = [
+        WebhookEvent::RunStarted,
+        WebhookEvent::BlockStarted,
+        WebhookEvent::RepetitionEnded,
+        WebhookEvent::BlockEnded,
+        WebhookEvent::Completed,
+        WebhookEvent::Aborted,
+    ];
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    /// 対象タイマー。None なら全タイマー
+    #[serde(default)]
+    pub timer_id: Option<String>,
+    /// 通知するイベント。空なら全イベント
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    /// 署名用の秘密鍵。登録時に空ならサーバーが生成する。一覧では登録したユーザーにだけ返す
+    #[serde(default)]
+    pub secret: String,
+    pub enabled: bool,
+    /// 登録したユーザーの ID。サーバーが設定する（None は認証導入前の Webhook）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct WebhookFile {
+    pub webhooks: Vec<Webhook>,
+}
+
+/// Webhook で送る JSON 本文。
+/// 本文の HMAC-SHA256 を `X-Nekotimer-Signature: sha256=<hex>` ヘッダーに付ける。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub delivery_id: String,
+    pub event: WebhookEvent,
+    /// 送信時刻（UNIX 秒）
+    pub timestamp: u64,
+    pub session_id: String,
+    pub timer_id: String,
+    pub timer_name: String,
+    pub block_index: Option<usize>,
+    pub block_name: Option<String>,
+    /// カウントダウンブロック時のみ: 現在の回数 (1-based)
+    pub repetition: Option<u32>,
+    /// カウントダウンブロック時のみ: 繰り返し回数
+    pub repetitions: Option<u32>,
+}
+
+/// 送信結果の記録
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub url: String,
+    pub event: WebhookEvent,
+    pub timestamp: u64,
+    pub attempts: u32,
+    pub success: bool,
+    /// 最後の試行の HTTP ステータス
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl Webhook {
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        if !(self.url.starts_with("http://") || self.url.starts_with("https://")) {
+            errors.push(ValidationError::new("url", ErrorCode::UrlScheme));
+        }
+        if self.events.contains(&WebhookEvent::Test) {
+            errors.push(ValidationError::new("events", ErrorCode::TestEventNotAllowed));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// このイベント・タイマーを通知対象にしているか
+    pub fn accepts(&self, event: WebhookEvent, timer_id: &str) -> bool {
+        self.enabled
+            && self.timer_id.as_deref().is_none_or(|id| id == timer_id)
+            && (self.events.is_empty() || self.events.contains(&event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(timer_id: Option<&str>, events: Vec<WebhookEvent>) -> Webhook {
+        Webhook {
+            id: "w1".into(),
+            url: "https://example.com/hook".into(),
+            timer_id: timer_id.map(str::to_string),
+            events,
+            secret: String::new(),
+            enabled: true,
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn accepts_all_timers_and_events_when_unset() {
+        let webhook = webhook(None, Vec::new());
+        assert!(webhook.accepts(WebhookEvent::RunStarted, "t1"));
+        assert!(webhook.accepts(WebhookEvent::Completed, "t2"));
+    }
+
+    #[test]
+    fn accepts_only_selected_timer_and_events() {
+        let webhook = webhook(Some("t1"), vec![WebhookEvent::Completed, WebhookEvent::Aborted]);
+        assert!(webhook.accepts(WebhookEvent::Completed, "t1"));
+        assert!(!webhook.accepts(WebhookEvent::RunStarted, "t1"));
+        assert!(!webhook.accepts(WebhookEvent::Completed, "t2"));
+    }
+
+    #[test]
+    fn disabled_webhook_accepts_nothing() {
+        let mut webhook = webhook(None, Vec::new());
+        webhook.enabled = false;
+        assert!(!webhook.accepts(WebhookEvent::Completed, "t1"));
+    }
+}
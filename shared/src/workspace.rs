@@ -0,0 +1,95 @@
+//! チームで共有するワークスペースとメンバーの役割
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ErrorCode;
+use crate::model::ValidationError;
+
+pub const WORKSPACE_NAME_MAX_LEN: usize = 64;
+
+/// ワークスペースでの役割。下ほど権限が強い（並び順で比較する）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WorkspaceRole {
+    /// 閲覧のみ
+    Viewer,
+    /// 閲覧と実行
+    Runner,
+    /// タイマー・スケジュール・共有リンクの編集
+    Editor,
+    /// メンバーの管理とワークスペースの削除
+    Owner,
+}
+
+impl WorkspaceRole {
+    pub const ALL: [WorkspaceRole; 4] = [
+        WorkspaceRole::Owner,
+        WorkspaceRole::Editor,
+        WorkspaceRole::Runner,
+        WorkspaceRole::Viewer,
+    ];
+
+    pub fn can_run(self) -> bool {
+        self >= WorkspaceRole::Runner
+    }
+
+    pub fn can_edit(self) -> bool {
+        self >= WorkspaceRole::Editor
+    }
+
+    pub fn can_manage(self) -> bool {
+        self == WorkspaceRole::Owner
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceMember {
+    pub user_id: String,
+    pub username: String,
+    pub role: WorkspaceRole,
+}
+
+/// API で返すワークスペース。role は取得したユーザー自身の役割
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub role: WorkspaceRole,
+    pub members: Vec<WorkspaceMember>,
+    pub created_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateWorkspaceRequest {
+    pub name: String,
+}
+
+impl CreateWorkspaceRequest {
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let len = self.name.trim().chars().count();
+        if len == 0 || len > WORKSPACE_NAME_MAX_LEN {
+            return Err(vec![ValidationError::new(
+                "name",
+                ErrorCode::Length { min: 1, max: WORKSPACE_NAME_MAX_LEN },
+            )]);
+        }
+        Ok(())
+    }
+}
+
+/// メンバーの追加・役割の変更
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SetMemberRequest {
+    pub username: String,
+    pub role: WorkspaceRole,
+}
+
+/// タイマーを別のスペースへ移動・コピーする
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransferTimerRequest {
+    /// 移動先のワークスペース。None なら自分の個人タイマー
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    /// true ならコピー（元のタイマーは残る）
+    #[serde(default)]
+    pub copy: bool,
+}